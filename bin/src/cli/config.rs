@@ -1,20 +1,67 @@
+use std::path::PathBuf;
+
 use anyhow::Result;
-use clap::Args;
-use sage_core::config;
+use clap::{Args, Subcommand};
+use sage_core::{config, config_edit, config_export, config_import};
 
 #[derive(Debug, Args)]
 pub struct ConfigCommand {
+    #[command(subcommand)]
+    pub command: Option<ConfigCmd>,
     #[arg(short, long)]
     pub key: Option<String>,
     #[arg(short, long)]
     pub value: Option<String>,
+    /// Print secret values (such as `ai.api_key`) in the clear
+    #[arg(long)]
+    pub reveal: bool,
+    /// Print entries as JSON with their value type and source
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCmd {
+    /// Print the effective config as TOML, with secrets redacted
+    Export {
+        /// Include secret values in the clear
+        #[arg(long)]
+        reveal: bool,
+        /// Write to FILE instead of stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Open the global config in $EDITOR, re-opening it until it parses
+    Edit,
+    /// Merge settings from a TOML file into the global config
+    Import {
+        #[arg(value_name = "FILE")]
+        path: PathBuf,
+    },
 }
 
 impl ConfigCommand {
     pub fn run(self) -> Result<()> {
-        let console = sage_fmt::Console::new();
-        console.header("config")?;
+        let console = sage_core::console()?;
 
-        config(self.key, self.value, &console)
+        match self.command {
+            Some(ConfigCmd::Export { reveal, output }) => {
+                config_export(reveal, output.as_deref(), &console)
+            }
+            Some(ConfigCmd::Edit) => {
+                console.header("config edit")?;
+                config_edit(&console)
+            }
+            Some(ConfigCmd::Import { path }) => {
+                console.header("config import")?;
+                config_import(&path, &console)
+            }
+            None => {
+                if !self.json {
+                    console.header("config")?;
+                }
+                config(self.key, self.value, self.reveal, self.json, &console)
+            }
+        }
     }
 }
@@ -0,0 +1,20 @@
+use anyhow::Result;
+use clap::Args;
+use sage_core::dash;
+
+#[derive(Debug, Args)]
+pub struct DashCommand {
+    /// Redraw whenever HEAD or the index changes
+    #[arg(short, long)]
+    pub watch: bool,
+    /// Print the snapshot as JSON (one object per line with --watch)
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl DashCommand {
+    pub fn run(self) -> Result<()> {
+        let console = sage_core::console()?;
+        dash(self.watch, self.json, &console)
+    }
+}
@@ -0,0 +1,26 @@
+use anyhow::Result;
+use clap::Args;
+use sage_core::diff;
+
+#[derive(Debug, Args)]
+pub struct DiffCommand {
+    /// Show the changes staged for the next commit instead of unstaged ones
+    #[arg(short, long)]
+    pub staged: bool,
+    /// Print per-file line counts as JSON
+    #[arg(long)]
+    pub json: bool,
+    /// Only show changes to these paths
+    #[arg(value_name = "PATH")]
+    pub paths: Vec<String>,
+}
+
+impl DiffCommand {
+    pub fn run(self) -> Result<()> {
+        let console = sage_core::console()?;
+        if !self.json {
+            console.header("diff")?;
+        }
+        diff(self.staged, self.paths, self.json, &console)
+    }
+}
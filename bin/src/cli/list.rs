@@ -1,18 +1,36 @@
 use anyhow::Result;
 use clap::Args;
 
-use sage_core::workflows::list_branches;
+use sage_core::workflows::{BranchSort, MergeFilter, list_branches};
 
 #[derive(Debug, Args)]
 pub struct ListCommand {
     #[arg(long)]
     stack: bool,
+    /// Only branches merged into REF (default: the default branch)
+    #[arg(long, value_name = "REF", num_args = 0..=1, conflicts_with = "no_merged")]
+    merged: Option<Option<String>>,
+    /// Only branches not yet merged into REF (default: the default branch)
+    #[arg(long, value_name = "REF", num_args = 0..=1)]
+    no_merged: Option<Option<String>>,
+    /// Order branches by name, or by most recent commit with `recent`
+    #[arg(long, value_name = "ORDER", value_parser = ["name", "recent"], default_value = "name")]
+    sort: String,
 }
 
 impl ListCommand {
     pub fn run(self) -> Result<()> {
-        let console = sage_fmt::Console::new();
+        let console = sage_core::console()?;
         console.header("list")?;
-        list_branches(self.stack)
+        let merge_filter = match (self.merged, self.no_merged) {
+            (Some(target), _) => Some(MergeFilter::Merged(target)),
+            (_, Some(target)) => Some(MergeFilter::NotMerged(target)),
+            (None, None) => None,
+        };
+        let sort = match self.sort.as_str() {
+            "recent" => BranchSort::Recent,
+            _ => BranchSort::Name,
+        };
+        list_branches(self.stack, merge_filter, sort, &console)
     }
 }
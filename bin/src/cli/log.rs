@@ -7,13 +7,18 @@ pub struct LogCommand {
     /// Limit the number of commits displayed
     #[arg(short, long)]
     pub limit: Option<usize>,
+    /// Print one line per commit using placeholders like "{short} {author} {summary}"
+    #[arg(long, value_name = "TEMPLATE")]
+    pub format: Option<String>,
 }
 
 impl LogCommand {
     pub fn run(self) -> Result<()> {
-        let console = sage_fmt::Console::new();
-        console.header("log")?;
+        let console = sage_core::console()?;
+        if self.format.is_none() {
+            console.header("log")?;
+        }
 
-        log(self.limit)
+        log(self.limit, self.format.as_deref())
     }
 }
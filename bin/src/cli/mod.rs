@@ -1,22 +1,35 @@
 pub mod config;
+pub mod dash;
+pub mod diff;
 pub mod list;
 pub mod log;
 pub mod save;
+pub mod share;
+pub mod stack;
 pub mod start;
 pub mod work;
 
 pub use config::ConfigCommand;
+pub use dash::DashCommand;
+pub use diff::DiffCommand;
 pub use list::ListCommand;
 pub use log::LogCommand;
 pub use save::SaveCommand;
+pub use share::ShareCommand;
+pub use stack::StackCommand;
 pub use start::StartCommand;
 pub use work::WorkCommand;
 
-use clap::{Parser, Subcommand};
+use anyhow::Result;
+use clap::{Args, Parser, Subcommand};
+use sage_core::{AiOverrides, AiProvider};
 
 #[derive(Debug, Parser)]
 #[command(name = "sg", version, author, about, long_about = None)]
 pub struct Cli {
+    /// Only print errors
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
     #[command(subcommand)]
     pub command: Command,
 }
@@ -29,4 +42,40 @@ pub enum Command {
     List(ListCommand),
     Log(LogCommand),
     Config(ConfigCommand),
+    Stack(StackCommand),
+    Share(ShareCommand),
+    Dash(DashCommand),
+    Diff(DiffCommand),
+}
+
+/// Per-invocation overrides for the `ai.*` config, shared by commands that call the AI.
+#[derive(Debug, Args)]
+pub struct AiArgs {
+    /// Use MODEL instead of `ai.model` for this run
+    #[arg(long = "model", value_name = "MODEL")]
+    pub model: Option<String>,
+    /// Talk to PROVIDER (`openai` or `anthropic`) instead of `ai.provider`
+    #[arg(long = "provider", value_name = "PROVIDER", value_parser = parse_provider)]
+    pub provider: Option<AiProvider>,
+    /// Log prompts and raw responses to .git/sage/ai-debug.log (also `SAGE_AI_DEBUG=1`)
+    #[arg(long = "ai-debug")]
+    pub ai_debug: bool,
+}
+
+impl AiArgs {
+    pub fn apply(self) -> Result<()> {
+        let debug_log = sage_core::ai_debug_log(self.ai_debug)?;
+        if self.model.is_none() && self.provider.is_none() && debug_log.is_none() {
+            return Ok(());
+        }
+        sage_core::set_ai_overrides(AiOverrides {
+            model: self.model,
+            provider: self.provider,
+            debug_log,
+        })
+    }
+}
+
+fn parse_provider(value: &str) -> Result<AiProvider, String> {
+    value.parse().map_err(|err: anyhow::Error| err.to_string())
 }
@@ -2,27 +2,68 @@ use anyhow::Result;
 use clap::Args;
 use sage_core::{SaveOptions, save};
 
+use super::AiArgs;
+
 #[derive(Debug, Args)]
 pub struct SaveCommand {
     #[arg(short = 'm', long = "message", value_name = "MESSAGE")]
     pub message: Option<String>,
+    /// Force-push without asking when the branch no longer matches its remote
     #[arg(short = 'f', long = "force")]
     pub force: bool,
     #[arg(short = 'a', long = "ai")]
     pub ai: bool,
+    #[command(flatten)]
+    pub ai_args: AiArgs,
     #[arg(short = 'p', long = "push")]
     pub push: bool,
+    /// Push as if the branch weren't part of a stack, never offering to force-push it
+    #[arg(long = "no-stack", requires = "push")]
+    pub no_stack: bool,
     #[arg(short = 'e', long = "empty")]
     pub empty: bool,
     #[arg(short = 'A', long = "amend")]
     pub amend: bool,
+    /// Keep the previous commit message when amending
+    #[arg(long = "no-edit", requires = "amend", conflicts_with_all = ["message", "ai"])]
+    pub no_edit: bool,
+    /// Review the --ai or --message message (or, with --amend, the previous one) in your editor
+    #[arg(long = "edit", conflicts_with_all = ["no_edit", "fixup", "reword"])]
+    pub edit: bool,
+    /// Only change the last commit's message; staged and unstaged changes are left alone
+    #[arg(long = "reword", conflicts_with_all = ["amend", "empty", "ai", "paths"])]
+    pub reword: bool,
+    /// Skip the pre-commit and commit-msg hooks
+    #[arg(short = 'n', long = "no-verify")]
+    pub no_verify: bool,
+    /// Create a `fixup!` commit for COMMIT, to be squashed in by an autosquash rebase
+    #[arg(
+        long = "fixup",
+        value_name = "COMMIT",
+        conflicts_with_all = ["message", "ai", "empty", "amend", "reword"]
+    )]
+    pub fixup: Option<String>,
+    /// Sign the commit with git's configured signing key (GPG or SSH)
+    #[arg(short = 'S', long = "sign", conflicts_with = "reword")]
+    pub sign: bool,
+    /// Choose which changed hunks to stage, like `git add -p`
+    #[arg(short = 'i', long = "interactive", conflicts_with_all = ["paths", "reword"])]
+    pub interactive: bool,
     #[arg(long = "paths", num_args = 1.., value_name = "PATH")]
     pub paths: Option<Vec<String>>,
+    /// Leave changes matching this gitignore-style pattern unstaged (repeatable)
+    #[arg(
+        long = "exclude",
+        value_name = "PATTERN",
+        conflicts_with_all = ["paths", "interactive", "reword"]
+    )]
+    pub exclude: Vec<String>,
 }
 
 impl SaveCommand {
     pub async fn run(self) -> Result<()> {
-        let console = sage_fmt::Console::new();
+        self.ai_args.apply()?;
+        let console = sage_core::console()?;
         console.header("save")?;
 
         let options = SaveOptions {
@@ -30,9 +71,18 @@ impl SaveCommand {
             force: self.force,
             ai: self.ai,
             push: self.push,
+            no_stack: self.no_stack,
             empty: self.empty,
             amend: self.amend,
+            no_edit: self.no_edit,
+            edit: self.edit,
+            reword: self.reword,
+            no_verify: self.no_verify,
+            fixup: self.fixup,
+            sign: self.sign,
+            interactive: self.interactive,
             paths: self.paths,
+            exclude: self.exclude,
         };
 
         save(options, &console).await
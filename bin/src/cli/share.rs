@@ -0,0 +1,36 @@
+use anyhow::Result;
+use clap::Args;
+use sage_core::{ShareOptions, share};
+
+use super::AiArgs;
+
+#[derive(Debug, Args)]
+pub struct ShareCommand {
+    /// Open the pull request as a draft
+    #[arg(short = 'd', long = "draft", conflicts_with = "ready")]
+    pub draft: bool,
+    /// Mark an existing draft pull request ready for review
+    #[arg(short = 'r', long = "ready")]
+    pub ready: bool,
+    /// Fill in the named template from .github/PULL_REQUEST_TEMPLATE/ or .gitlab/merge_request_templates/
+    #[arg(short = 't', long = "template", value_name = "NAME")]
+    pub template: Option<String>,
+    #[command(flatten)]
+    pub ai_args: AiArgs,
+}
+
+impl ShareCommand {
+    pub async fn run(self) -> Result<()> {
+        self.ai_args.apply()?;
+        let console = sage_core::console()?;
+        console.header("share")?;
+
+        let options = ShareOptions {
+            draft: self.draft,
+            ready: self.ready,
+            template: self.template,
+        };
+
+        share(options, &console).await
+    }
+}
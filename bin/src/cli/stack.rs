@@ -0,0 +1,64 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use sage_core::{stack_adopt, stack_goto, stack_next, stack_prev, stack_status};
+
+#[derive(Debug, Args)]
+pub struct StackCommand {
+    #[command(subcommand)]
+    pub command: StackCmd,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum StackCmd {
+    /// Show the sync state of each branch in the current stack
+    Status {
+        /// Print the status as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Track the current branch as a child of PARENT
+    Adopt {
+        /// Tracked branch to adopt the current branch under
+        parent: String,
+    },
+    /// Switch to another branch in the current stack
+    Goto {
+        /// Branch to switch to; pick one interactively when omitted
+        branch: Option<String>,
+    },
+    /// Switch to the branch built on the current one
+    Next,
+    /// Switch to the current branch's parent in the stack
+    Prev,
+}
+
+impl StackCommand {
+    pub fn run(self) -> Result<()> {
+        let console = sage_core::console()?;
+
+        match self.command {
+            StackCmd::Status { json } => {
+                if !json {
+                    console.header("stack status")?;
+                }
+                stack_status(json, &console)
+            }
+            StackCmd::Adopt { parent } => {
+                console.header("stack adopt")?;
+                stack_adopt(&parent, &console)
+            }
+            StackCmd::Goto { branch } => {
+                console.header("stack goto")?;
+                stack_goto(branch.as_deref(), &console)
+            }
+            StackCmd::Next => {
+                console.header("stack next")?;
+                stack_next(&console)
+            }
+            StackCmd::Prev => {
+                console.header("stack prev")?;
+                stack_prev(&console)
+            }
+        }
+    }
+}
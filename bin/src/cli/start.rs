@@ -15,7 +15,7 @@ pub struct StartCommand {
 
 impl StartCommand {
     pub fn run(self) -> Result<()> {
-        let console = sage_fmt::Console::new();
+        let console = sage_core::console()?;
         console.header("start")?;
 
         let options = StartOptions {
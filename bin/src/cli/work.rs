@@ -1,11 +1,34 @@
 use anyhow::Result;
 use clap::Args;
-use sage_core::work;
+use sage_core::{WorkOptions, delete_branch, issue_branch_name, work};
 
 #[derive(Debug, Args)]
 pub struct WorkCommand {
-    #[arg(value_name = "BRANCH")]
-    pub branch: String,
+    /// Branch to switch to or create; with `-z` and no name, pick one interactively
+    #[arg(
+        value_name = "BRANCH",
+        required_unless_present_any = ["fuzzy", "issue"],
+        conflicts_with = "issue"
+    )]
+    pub branch: Option<String>,
+    /// Name the new branch after an issue using `general.branch_template`
+    #[arg(long = "issue", value_name = "ID")]
+    pub issue: Option<String>,
+    /// Branch type for the `{type}` placeholder
+    #[arg(
+        long = "type",
+        value_name = "TYPE",
+        default_value = "feat",
+        requires = "issue"
+    )]
+    pub kind: String,
+    /// Short description of the work, used for the `{slug}` placeholder
+    #[arg(long = "summary", value_name = "TEXT", requires = "issue")]
+    pub summary: Option<String>,
+    /// Let the AI write the slug from `--summary`
+    #[arg(long = "ai", requires = "summary")]
+    pub ai: bool,
+    /// Base the new branch on PARENT; with tracking on it's recorded as the branch's parent
     #[arg(long = "parent", value_name = "PARENT")]
     pub parent: Option<String>,
     #[arg(short = 'z', long = "fuzzy")]
@@ -14,19 +37,76 @@ pub struct WorkCommand {
     pub push: bool,
     #[arg(short = 'r', long = "root")]
     pub root: bool,
+    /// Check the branch out in a linked worktree (see `general.worktree_dir`)
+    #[arg(short = 'w', long = "worktree")]
+    pub worktree: bool,
+    /// Print the worktree result as JSON
+    #[arg(long = "json", requires = "worktree")]
+    pub json: bool,
+    /// Record a newly created branch in the stack graph (default: `general.auto_track`)
+    #[arg(long = "track", overrides_with = "no_track")]
+    pub track: bool,
+    /// Create the branch without recording it in the stack graph, even with --parent
+    #[arg(long = "no-track", overrides_with = "track")]
+    pub no_track: bool,
+    /// Delete BRANCH instead of switching to it
+    #[arg(
+        short = 'd',
+        long = "delete",
+        requires = "branch",
+        conflicts_with_all = ["fuzzy", "issue", "worktree", "push", "root", "parent"]
+    )]
+    pub delete: bool,
+    /// Refuse to switch while there are uncommitted changes (default: `general.abort_on_dirty`)
+    #[arg(long = "abort-on-dirty", conflicts_with = "worktree")]
+    pub abort_on_dirty: bool,
+    /// With --delete, don't ask before deleting a branch that isn't merged
+    #[arg(short = 'f', long = "force", requires = "delete")]
+    pub force: bool,
 }
 
 impl WorkCommand {
-    pub fn run(self) -> Result<()> {
-        let console = sage_fmt::Console::new();
-        console.header("work")?;
-        work(
-            self.branch,
-            self.parent,
-            self.fuzzy,
-            self.push,
-            self.root,
-            &console,
-        )
+    pub async fn run(self) -> Result<()> {
+        let console = sage_core::console()?;
+        if !self.json {
+            console.header("work")?;
+        }
+
+        if self.delete {
+            let branch = self.branch.unwrap_or_default();
+            return delete_branch(&branch, self.force, &console);
+        }
+
+        let branch = match &self.issue {
+            Some(issue) => Some(
+                issue_branch_name(
+                    &self.kind,
+                    issue,
+                    self.summary.as_deref(),
+                    self.ai,
+                    &console,
+                )
+                .await?,
+            ),
+            None => self.branch,
+        };
+
+        let options = WorkOptions {
+            branch,
+            parent: self.parent,
+            fuzzy: self.fuzzy,
+            push: self.push,
+            root: self.root,
+            worktree: self.worktree,
+            json: self.json,
+            track: match (self.track, self.no_track) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            },
+            abort_on_dirty: self.abort_on_dirty.then_some(true),
+        };
+
+        work(options, &console)
     }
 }
@@ -8,24 +8,35 @@ mod cli;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    if let Err(err) = check_for_updates().await
+    let cli = Cli::parse();
+    sage_core::set_quiet(cli.quiet);
+
+    if !cli.quiet
+        && let Err(err) = check_for_updates().await
         && cfg!(debug_assertions)
     {
         eprintln!("check_for_updates failed: {err:#}");
     }
-    let cli = Cli::parse();
     match cli.command {
         // Start a new stack
         Command::Start(command) => command.run(),
         // Create commits
         Command::Save(command) => command.run().await,
         // Change branches
-        Command::Work(command) => command.run(),
+        Command::Work(command) => command.run().await,
         // List branches
         Command::List(command) => command.run(),
         // List commits
         Command::Log(command) => command.run(),
         // Manage configuration
         Command::Config(command) => command.run(),
+        // Inspect stacks
+        Command::Stack(command) => command.run(),
+        // Open or update a pull request
+        Command::Share(command) => command.run().await,
+        // Show a summary of the repository
+        Command::Dash(command) => command.run(),
+        // Show staged or unstaged changes
+        Command::Diff(command) => command.run(),
     }
 }
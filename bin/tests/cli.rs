@@ -0,0 +1,79 @@
+use std::process::{Command, Output};
+
+use anyhow::Result;
+use sage_git::testing::TestRepo;
+use tempfile::TempDir;
+
+/// Runs `sg` inside `repo` with an empty home directory and no update check.
+fn sg(repo: &TestRepo, home: &TempDir, args: &[&str]) -> Result<Output> {
+    let output = Command::new(env!("CARGO_BIN_EXE_sg"))
+        .args(args)
+        .current_dir(repo.path())
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path().join(".config"))
+        .env("SAGE_NO_UPDATE_CHECK", "1")
+        .env_remove("NO_COLOR")
+        .output()?;
+    Ok(output)
+}
+
+#[test]
+fn quiet_save_prints_nothing() -> Result<()> {
+    let repo = TestRepo::builder().with_initial_commit().build()?;
+    let home = TempDir::new()?;
+    repo.write("notes.txt", "hello\n")?;
+
+    let output = sg(&repo, &home, &["--quiet", "save", "-m", "add notes"])?;
+
+    assert!(output.status.success(), "{output:?}");
+    assert!(output.stdout.is_empty(), "{output:?}");
+    assert!(output.stderr.is_empty(), "{output:?}");
+    let subject = repo.git_stdout(["log", "-1", "--format=%s"])?;
+    assert_eq!(subject.trim(), "add notes");
+    Ok(())
+}
+
+#[test]
+fn json_output_keeps_stdout_to_the_document() -> Result<()> {
+    let repo = TestRepo::builder().with_initial_commit().build()?;
+    let home = TempDir::new()?;
+    repo.write("notes.txt", "hello\n")?;
+
+    let output = sg(&repo, &home, &["dash", "--json"])?;
+
+    assert!(output.status.success(), "{output:?}");
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(document["command"], "dash");
+    Ok(())
+}
+
+#[test]
+fn status_lines_go_to_stderr() -> Result<()> {
+    let repo = TestRepo::builder().with_initial_commit().build()?;
+    let home = TempDir::new()?;
+
+    let output = sg(&repo, &home, &["list"])?;
+
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout)?;
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stdout.contains("main"), "{stdout}");
+    assert!(stderr.contains("list"), "{stderr}");
+    assert!(!stdout.contains("list"), "{stdout}");
+    Ok(())
+}
+
+#[test]
+fn amend_with_edit_opens_the_editor() -> Result<()> {
+    let repo = TestRepo::builder().with_initial_commit().build()?;
+    let home = TempDir::new()?;
+    repo.write("notes.txt", "hello\n")?;
+
+    // Without a terminal the editor can't open, so the amend must fail rather than skip it.
+    let output = sg(&repo, &home, &["save", "--amend", "--edit"])?;
+
+    assert!(!output.status.success(), "{output:?}");
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.contains("interactive terminal"), "{stderr}");
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use anyhow::{Context, Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+
+const API_VERSION: &str = "2023-06-01";
+/// The messages API requires `max_tokens`, so use this when `ai.max_tokens` is 0.
+const DEFAULT_MAX_TOKENS: u64 = 2_048;
+
+/// Minimal client for Anthropic's messages API.
+pub(crate) struct AnthropicClient {
+    http: reqwest::Client,
+    api_key: String,
+    base_url: String,
+}
+
+#[derive(Serialize)]
+struct MessagesRequest<'a> {
+    model: &'a str,
+    max_tokens: u64,
+    messages: [Message<'a>; 1],
+}
+
+#[derive(Serialize)]
+struct Message<'a> {
+    role: &'static str,
+    content: &'a str,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum MessagesResponse {
+    Message { content: Vec<ContentBlock> },
+    Error { error: ApiError },
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ContentBlock {
+    Text {
+        text: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+struct ApiError {
+    #[serde(rename = "type")]
+    kind: String,
+    message: String,
+}
+
+impl AnthropicClient {
+    pub(crate) fn new(api_key: &str, base_url: &str) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .build()
+            .context("Failed to build Anthropic client")?;
+        Ok(Self {
+            http,
+            api_key: api_key.to_string(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    pub(crate) async fn complete(
+        &self,
+        model: &str,
+        prompt: &str,
+        max_tokens: Option<u64>,
+    ) -> Result<String> {
+        let request = MessagesRequest {
+            model,
+            max_tokens: max_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
+            messages: [Message {
+                role: "user",
+                content: prompt,
+            }],
+        };
+
+        let response = self
+            .http
+            .post(format!("{}/messages", self.base_url))
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", API_VERSION)
+            .json(&request)
+            .send()
+            .await?;
+        let status = response.status();
+        let body = response.text().await?;
+
+        parse_response(&body).map_err(|err| {
+            if status.is_success() {
+                err
+            } else {
+                err.context(format!("Anthropic API returned {status}"))
+            }
+        })
+    }
+}
+
+/// Joins the text blocks of a messages API response, or surfaces its error.
+fn parse_response(body: &str) -> Result<String> {
+    let response: MessagesResponse = serde_json::from_str(body)
+        .map_err(|err| anyhow!("Unexpected response from Anthropic API: {err}"))?;
+
+    match response {
+        MessagesResponse::Message { content } => Ok(content
+            .into_iter()
+            .filter_map(|block| match block {
+                ContentBlock::Text { text } => Some(text),
+                ContentBlock::Other => None,
+            })
+            .collect::<Vec<_>>()
+            .join("")),
+        MessagesResponse::Error { error } => bail!("{}: {}", error.kind, error.message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_blocks_are_joined() {
+        let body = r#"{
+            "id": "msg_01",
+            "type": "message",
+            "role": "assistant",
+            "model": "claude-sonnet-4-5",
+            "content": [
+                {"type": "thinking", "thinking": "...", "signature": "sig"},
+                {"type": "text", "text": "feat: add anthropic "},
+                {"type": "text", "text": "provider"}
+            ],
+            "stop_reason": "end_turn",
+            "usage": {"input_tokens": 10, "output_tokens": 5}
+        }"#;
+
+        assert_eq!(
+            parse_response(body).unwrap(),
+            "feat: add anthropic provider"
+        );
+    }
+
+    #[test]
+    fn api_errors_are_surfaced() {
+        let body = r#"{
+            "type": "error",
+            "error": {"type": "authentication_error", "message": "invalid x-api-key"}
+        }"#;
+
+        let err = parse_response(body).unwrap_err();
+        assert_eq!(err.to_string(), "authentication_error: invalid x-api-key");
+    }
+}
@@ -0,0 +1,13 @@
+use crate::{commit::clean_response, prompts};
+use anyhow::{Result, anyhow};
+
+pub async fn branch_slug(summary: &str) -> Result<String> {
+    let prompt = prompts::branch_slug_prompt(summary);
+    let res = clean_response(super::ask(&prompt).await?)?;
+
+    res.lines()
+        .map(|line| line.trim().trim_matches('`').trim_matches('"').trim())
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("AI returned an empty branch slug"))
+}
@@ -1,7 +1,8 @@
-use crate::prompts;
+use crate::{estimate_tokens, prompts, truncate_diff_for_model};
 use anyhow::{Result, anyhow};
 
-const MAX_DIFF_SIZE: usize = 12_288;
+/// Token budget for the diff part of the prompt.
+const MAX_DIFF_TOKENS: usize = 3_072;
 
 pub async fn commit_message(diff: &str, additional_prompt: Option<&str>) -> Result<String> {
     let diff = diff.trim();
@@ -9,11 +10,10 @@ pub async fn commit_message(diff: &str, additional_prompt: Option<&str>) -> Resu
         return Ok("chore: no functional changes".to_string());
     }
 
-    let diff_for_prompt = if diff.len() > MAX_DIFF_SIZE {
+    let diff_for_prompt = if estimate_tokens(diff) > MAX_DIFF_TOKENS {
         let summary = summarize_diff(diff);
-        let remaining_size = MAX_DIFF_SIZE.saturating_sub(summary.len().min(MAX_DIFF_SIZE / 2));
-        let content: String = diff.chars().take(remaining_size).collect();
-        format!("{summary}\n{content}\n[diff truncated]")
+        let budget = MAX_DIFF_TOKENS.saturating_sub(estimate_tokens(&summary));
+        format!("{summary}\n{}", truncate_diff_for_model(diff, budget))
     } else {
         diff.to_string()
     };
@@ -45,7 +45,7 @@ fn summarize_diff(diff: &str) -> String {
     format!("# Summary: modified {file_count} files, {line_count} lines changed")
 }
 
-fn clean_response(res: String) -> Result<String> {
+pub(crate) fn clean_response(res: String) -> Result<String> {
     let res = res.trim();
     let cleaned = if res.starts_with("```") {
         let without_opening = res.trim_start_matches("```");
@@ -1,20 +1,56 @@
 use anyhow::{Context, Result, anyhow};
 use once_cell::sync::OnceCell;
 use rig::providers::openai;
-use sage_config::ConfigManager;
-use std::time::Duration;
+use sage_config::{AiConfig, ConfigManager};
+use std::{path::PathBuf, time::Duration};
+
+use crate::{AiProvider, anthropic::AnthropicClient, debug::DebugLog, provider::ANTHROPIC_API_URL};
+
+pub(crate) enum AiClient {
+    OpenAi(openai::Client),
+    Anthropic(AnthropicClient),
+}
+
+impl AiClient {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Self::OpenAi(_) => "openai",
+            Self::Anthropic(_) => "anthropic",
+        }
+    }
+}
 
 pub(crate) struct AiContext {
-    pub(crate) client: openai::Client,
+    pub(crate) client: AiClient,
     pub(crate) model: String,
     pub(crate) timeout: Duration,
     pub(crate) max_tokens: Option<u64>,
     pub(crate) max_retries: usize,
     pub(crate) retry_delay: Duration,
     pub(crate) reasoning_effort: Option<String>,
+    pub(crate) debug_log: Option<DebugLog>,
+}
+
+/// Settings given on the command line that take precedence over the `ai.*` config.
+#[derive(Debug, Clone, Default)]
+pub struct AiOverrides {
+    pub model: Option<String>,
+    pub provider: Option<AiProvider>,
+    /// Append every prompt and raw response to this file.
+    pub debug_log: Option<PathBuf>,
 }
 
 static AI_CONTEXT: OnceCell<AiContext> = OnceCell::new();
+static AI_OVERRIDES: OnceCell<AiOverrides> = OnceCell::new();
+
+/// Override the configured model or provider for the rest of this process.
+///
+/// Must be called before the first AI request, since the client is built only once.
+pub fn set_ai_overrides(overrides: AiOverrides) -> Result<()> {
+    AI_OVERRIDES
+        .set(overrides)
+        .map_err(|_| anyhow!("AI overrides were already set"))
+}
 
 fn sanitize(value: String) -> String {
     value
@@ -28,57 +64,130 @@ fn sanitize(value: String) -> String {
 pub(crate) fn ai_context() -> Result<&'static AiContext> {
     AI_CONTEXT.get_or_try_init(|| {
         let manager = ConfigManager::load().context("Failed to load configuration")?;
-        let config = manager.get();
+        let overrides = AI_OVERRIDES.get().cloned().unwrap_or_default();
+        build_context(&manager.get().ai, &overrides)
+    })
+}
+
+pub(crate) fn build_context(config: &AiConfig, overrides: &AiOverrides) -> Result<AiContext> {
+    let ai_model = sanitize(
+        overrides
+            .model
+            .clone()
+            .unwrap_or_else(|| config.model.clone()),
+    );
 
-        let ai_model = sanitize(config.ai.model.clone());
+    if ai_model.is_empty() {
+        anyhow::bail!("AI model not set. Please configure it in your sage config.");
+    }
 
-        if ai_model.is_empty() {
-            anyhow::bail!("AI model not set. Please configure it in your sage config.");
-        }
+    let api_url = sanitize(config.api_url.clone());
+    let provider = match overrides.provider {
+        Some(provider) => provider,
+        None => AiProvider::from_config(
+            config.provider.clone().map(sanitize).as_deref(),
+            &api_url,
+            &ai_model,
+        )?,
+    };
 
-        let api_url = sanitize(config.ai.api_url.clone());
-
-        let api_key = config
-            .ai
-            .api_key
-            .as_ref()
-            .map(|s| sanitize(s.expose().to_string()))
-            .filter(|value| !value.is_empty())
-            .ok_or_else(|| {
-                anyhow!("AI API key not set. Please configure ai.api_key in your sage config.")
-            })?;
-
-        let timeout_secs = config.ai.timeout_secs;
-        let timeout_duration = Duration::from_secs(timeout_secs);
-        let max_tokens = config.ai.max_tokens;
-        let max_retries = config.ai.max_retries;
-        let retry_delay_ms = config.ai.retry_delay_ms;
-        let reasoning_effort = config
-            .ai
-            .reasoning_effort
-            .clone()
-            .map(sanitize)
-            .filter(|s| !s.eq_ignore_ascii_case("none"));
+    let api_key = config
+        .api_key
+        .as_ref()
+        .map(|s| sanitize(s.expose().to_string()))
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| {
+            anyhow!("AI API key not set. Please configure ai.api_key in your sage config.")
+        })?;
+
+    let timeout_secs = config.timeout_secs;
+    let timeout_duration = Duration::from_secs(timeout_secs);
+    let max_tokens = config.max_tokens;
+    let max_retries = config.max_retries;
+    let retry_delay_ms = config.retry_delay_ms;
+    let reasoning_effort = config
+        .reasoning_effort
+        .clone()
+        .map(sanitize)
+        .filter(|s| !s.eq_ignore_ascii_case("none"));
 
-        let mut client_builder = openai::Client::builder().api_key(&api_key);
+    let trimmed_api_url = api_url.trim_end_matches('/');
+    let client = match provider {
+        AiProvider::OpenAi => {
+            let mut client_builder = openai::Client::builder().api_key(&api_key);
+            if !trimmed_api_url.is_empty() {
+                client_builder = client_builder.base_url(trimmed_api_url);
+            }
 
-        let trimmed_api_url = api_url.trim_end_matches('/');
-        if !trimmed_api_url.is_empty() {
-            client_builder = client_builder.base_url(trimmed_api_url);
+            AiClient::OpenAi(
+                client_builder
+                    .build()
+                    .context("Failed to build OpenAI client")?,
+            )
         }
+        AiProvider::Anthropic => {
+            // A Claude model with the stock OpenAI URL means the URL was never set.
+            let base_url =
+                if trimmed_api_url.is_empty() || trimmed_api_url.contains("api.openai.com") {
+                    ANTHROPIC_API_URL
+                } else {
+                    trimmed_api_url
+                };
+            AiClient::Anthropic(AnthropicClient::new(&api_key, base_url)?)
+        }
+    };
 
-        let client = client_builder
-            .build()
-            .context("Failed to build OpenAI client")?;
-
-        Ok(AiContext {
-            client,
-            model: ai_model,
-            timeout: timeout_duration,
-            max_tokens: (max_tokens > 0).then_some(max_tokens),
-            max_retries,
-            retry_delay: Duration::from_millis(retry_delay_ms),
-            reasoning_effort,
-        })
+    Ok(AiContext {
+        client,
+        model: ai_model,
+        timeout: timeout_duration,
+        max_tokens: (max_tokens > 0).then_some(max_tokens),
+        max_retries,
+        retry_delay: Duration::from_millis(retry_delay_ms),
+        reasoning_effort,
+        debug_log: overrides.debug_log.clone().map(DebugLog::new),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sage_config::SecretString;
+
+    fn config() -> AiConfig {
+        AiConfig {
+            api_key: Some(SecretString::new("sk-test")),
+            ..AiConfig::default()
+        }
+    }
+
+    #[test]
+    fn model_override_replaces_configured_model() {
+        let overrides = AiOverrides {
+            model: Some("gpt-4.1".to_string()),
+            ..AiOverrides::default()
+        };
+        let context = build_context(&config(), &overrides).unwrap();
+
+        assert_eq!(context.model, "gpt-4.1");
+        assert!(matches!(context.client, AiClient::OpenAi(_)));
+    }
+
+    #[test]
+    fn overridden_model_drives_provider_detection() {
+        let overrides = AiOverrides {
+            model: Some("claude-sonnet-4-5".to_string()),
+            ..AiOverrides::default()
+        };
+        let context = build_context(&config(), &overrides).unwrap();
+        assert!(matches!(context.client, AiClient::Anthropic(_)));
+
+        let overrides = AiOverrides {
+            provider: Some(AiProvider::OpenAi),
+            ..overrides
+        };
+        let context = build_context(&config(), &overrides).unwrap();
+        assert_eq!(context.model, "claude-sonnet-4-5");
+        assert!(matches!(context.client, AiClient::OpenAi(_)));
+    }
+}
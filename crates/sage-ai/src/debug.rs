@@ -0,0 +1,86 @@
+//! Optional log of the exact prompts sent and raw responses received
+
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+
+use anyhow::Result;
+
+/// Past this size the log is moved aside to `<name>.old` before the next write.
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+
+/// Appends AI exchanges to a file. Only the prompt and response text are written;
+/// the API key and request headers never are.
+#[derive(Debug, Clone)]
+pub(crate) struct DebugLog {
+    path: PathBuf,
+}
+
+impl DebugLog {
+    pub(crate) fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Records one attempt; `outcome` is the raw response or the error it failed with.
+    pub(crate) fn record(
+        &self,
+        provider: &str,
+        model: &str,
+        prompt: &str,
+        outcome: Result<&str, &str>,
+    ) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        self.rotate()?;
+
+        let (label, body) = match outcome {
+            Ok(response) => ("response", response),
+            Err(error) => ("error", error),
+        };
+        let timestamp = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "=== {timestamp} {provider} {model} ===")?;
+        writeln!(file, "--- prompt ---\n{}", prompt.trim_end())?;
+        writeln!(file, "--- {label} ---\n{}\n", body.trim_end())?;
+        Ok(())
+    }
+
+    fn rotate(&self) -> Result<()> {
+        let Ok(metadata) = fs::metadata(&self.path) else {
+            return Ok(());
+        };
+        if metadata.len() > MAX_LOG_BYTES {
+            fs::rename(&self.path, self.path.with_extension("log.old"))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oversized_log_is_moved_aside() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sage").join("ai-debug.log");
+        let log = DebugLog::new(path.clone());
+
+        log.record("openai", "gpt-4", "first", Ok("one")).unwrap();
+        fs::write(&path, vec![b'x'; MAX_LOG_BYTES as usize + 1]).unwrap();
+        log.record("openai", "gpt-4", "second", Err("boom"))
+            .unwrap();
+
+        let current = fs::read_to_string(&path).unwrap();
+        assert!(current.contains("--- prompt ---\nsecond\n--- error ---\nboom\n"));
+        assert!(current.starts_with("=== "));
+        assert!(path.with_extension("log.old").exists());
+    }
+}
@@ -1,35 +1,76 @@
 use anyhow::{Result, anyhow};
 use rig::{client::CompletionClient, completion::Prompt};
 
+mod anthropic;
+mod branch;
 mod commit;
 mod context;
+mod debug;
+mod pr;
 mod prompts;
+mod provider;
+mod tokens;
 
-use context::ai_context;
+use context::{AiClient, AiContext, ai_context};
 
+pub use context::{AiOverrides, set_ai_overrides};
+
+pub use branch::branch_slug;
 pub use commit::commit_message;
+pub use pr::{pr_description, pr_title};
+pub use provider::AiProvider;
+pub use tokens::{estimate_tokens, truncate_diff_for_model, truncate_diff_with};
+
+async fn complete(context: &AiContext, prompt: &str) -> Result<String> {
+    match &context.client {
+        AiClient::OpenAi(client) => {
+            let mut builder = client.agent(&context.model);
+            if let Some(max_tokens) = context.max_tokens {
+                builder = builder.max_tokens(max_tokens);
+            }
+            if let Some(reasoning_effort) = &context.reasoning_effort {
+                builder = builder.additional_params(serde_json::json!({
+                    "reasoning_effort": reasoning_effort
+                }));
+            }
+            let response = builder.build().prompt(prompt).await?;
+            Ok(response.to_string())
+        }
+        AiClient::Anthropic(client) => {
+            client
+                .complete(&context.model, prompt, context.max_tokens)
+                .await
+        }
+    }
+}
 
 /// Asks the AI with a prompt
 pub async fn ask(prompt: &str) -> Result<String> {
-    let context = ai_context()?;
-
-    let mut builder = context.client.agent(&context.model);
-    if let Some(max_tokens) = context.max_tokens {
-        builder = builder.max_tokens(max_tokens);
-    }
-    if let Some(reasoning_effort) = &context.reasoning_effort {
-        builder = builder.additional_params(serde_json::json!({
-            "reasoning_effort": reasoning_effort
-        }));
-    }
-    let agent = builder.build();
+    ask_with(ai_context()?, prompt).await
+}
 
+async fn ask_with(context: &AiContext, prompt: &str) -> Result<String> {
     let mut attempts = context.max_retries;
     let mut last_error = None;
     while attempts > 0 {
-        match tokio::time::timeout(context.timeout, agent.prompt(prompt)).await {
-            Ok(Ok(response)) => {
-                let content = response.to_string();
+        let outcome = tokio::time::timeout(context.timeout, complete(context, prompt)).await;
+        if let Some(log) = &context.debug_log {
+            let recorded = match &outcome {
+                Ok(Ok(content)) => Ok(content.clone()),
+                Ok(Err(e)) => Err(format!("{e:#}")),
+                Err(_) => Err("timed out".to_string()),
+            };
+            // Debug logging must never turn a good response into a failure.
+            let _ = log.record(
+                context.client.name(),
+                &context.model,
+                prompt,
+                recorded.as_deref().map_err(String::as_str),
+            );
+        }
+
+        match outcome {
+            Ok(Ok(content)) => {
                 if content.trim().is_empty() {
                     last_error = Some(anyhow!("AI provider returned empty response"));
                 } else {
@@ -37,7 +78,7 @@ pub async fn ask(prompt: &str) -> Result<String> {
                 }
             }
             Ok(Err(e)) => {
-                last_error = Some(anyhow!("AI request failed: {}", e));
+                last_error = Some(anyhow!("AI request failed: {:#}", e));
             }
             Err(_) => {
                 last_error = Some(anyhow!(
@@ -61,3 +102,90 @@ pub async fn ask(prompt: &str) -> Result<String> {
         .unwrap_or_else(|| anyhow!("Failed to get response from AI provider"))
         .context("Failed to get chat completion"))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use sage_config::{AiConfig, SecretString};
+
+    use super::*;
+    use crate::context::build_context;
+
+    /// Answers a single HTTP request with a messages API response.
+    fn serve_once(body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/v1", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 4096];
+            loop {
+                let read = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..read]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some(end) = text.find("\r\n\r\n") {
+                    let length = text[..end]
+                        .lines()
+                        .find_map(|line| {
+                            let (name, value) = line.split_once(':')?;
+                            name.eq_ignore_ascii_case("content-length")
+                                .then(|| value.trim().parse::<usize>().ok())?
+                        })
+                        .unwrap_or(0);
+                    if request.len() >= end + 4 + length || read == 0 {
+                        break;
+                    }
+                }
+            }
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn debug_log_records_prompt_and_response() {
+        let api_url = serve_once(
+            r#"{"type":"message","content":[{"type":"text","text":"feat: log ai calls"}]}"#,
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = dir.path().join("sage").join("ai-debug.log");
+
+        let config = AiConfig {
+            api_key: Some(SecretString::new("sk-secret")),
+            provider: Some("anthropic".to_string()),
+            model: "claude-sonnet-4-5".to_string(),
+            api_url,
+            ..AiConfig::default()
+        };
+        let overrides = AiOverrides {
+            debug_log: Some(log_path.clone()),
+            ..AiOverrides::default()
+        };
+        let context = build_context(&config, &overrides).unwrap();
+
+        let response = ask_with(&context, "describe this diff").await.unwrap();
+        assert_eq!(response, "feat: log ai calls");
+
+        let log = std::fs::read_to_string(&log_path).unwrap();
+        assert!(log.contains("anthropic claude-sonnet-4-5 ==="), "{log}");
+        assert!(
+            log.contains("--- prompt ---\ndescribe this diff\n"),
+            "{log}"
+        );
+        assert!(
+            log.contains("--- response ---\nfeat: log ai calls\n"),
+            "{log}"
+        );
+        assert!(!log.contains("sk-secret"), "{log}");
+    }
+}
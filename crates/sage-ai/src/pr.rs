@@ -0,0 +1,20 @@
+use crate::{commit::clean_response, prompts};
+use anyhow::{Result, anyhow};
+
+pub async fn pr_title(commits: Vec<String>) -> Result<String> {
+    let prompt = prompts::pr_title_prompt(commits).await;
+    let res = clean_response(super::ask(&prompt).await?)?;
+
+    let title = res
+        .lines()
+        .map(|line| line.trim().trim_matches('`').trim_matches('"').trim())
+        .find(|line| !line.is_empty())
+        .ok_or_else(|| anyhow!("AI returned an empty pull request title"))?;
+
+    Ok(title.to_string())
+}
+
+pub async fn pr_description(title: &str, commits: Vec<String>, template: &str) -> Result<String> {
+    let prompt = prompts::pr_description_prompt(title, commits, template);
+    clean_response(super::ask(&prompt).await?)
+}
@@ -50,7 +50,6 @@ Bad examples (avoid):
 }
 
 /// Prompt for generating pull request title
-#[allow(dead_code)]
 pub async fn pr_title_prompt(commits: Vec<String>) -> String {
     let commit_log = commits.join("\n");
 
@@ -79,7 +78,6 @@ pub async fn pr_title_prompt(commits: Vec<String>) -> String {
 }
 
 /// Prompt for generating pull request descriptions
-#[allow(dead_code)]
 pub fn pr_description_prompt(title: &str, commits: Vec<String>, template: &str) -> String {
     let commit_log = commits.join("\n");
 
@@ -129,3 +127,19 @@ pub fn pr_description_prompt(title: &str, commits: Vec<String>, template: &str)
         )
     }
 }
+
+/// Prompt for turning a short task summary into a branch name slug
+pub fn branch_slug_prompt(summary: &str) -> String {
+    format!(
+        r#"You are an assistant that names git branches.
+
+        Summarize the following task in 2 to 5 lower-case words joined by hyphens, suitable for a branch name:
+        ```
+        {summary}
+        ```
+
+        Use only a-z, 0-9 and hyphens. Do not include a type prefix or ticket number.
+
+        Return **only** the slug, e.g. `add-login-rate-limit`."#
+    )
+}
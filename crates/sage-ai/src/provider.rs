@@ -0,0 +1,93 @@
+use std::str::FromStr;
+
+use anyhow::{Result, bail};
+
+pub(crate) const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1";
+
+/// The API dialect used to reach the configured model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AiProvider {
+    /// OpenAI's chat completions API, also spoken by Ollama and most proxies.
+    OpenAi,
+    /// Anthropic's messages API.
+    Anthropic,
+}
+
+impl AiProvider {
+    /// Uses `ai.provider` when set, otherwise guesses from the API URL and model name.
+    ///
+    /// The model name only counts when the URL is unset or the stock OpenAI one; a custom
+    /// `ai.api_url` is usually a proxy that serves Claude models over the OpenAI API.
+    pub fn from_config(provider: Option<&str>, api_url: &str, model: &str) -> Result<Self> {
+        if let Some(name) = provider.map(str::trim).filter(|name| !name.is_empty()) {
+            return name.parse();
+        }
+
+        let api_url = api_url.trim();
+        let default_url = api_url.is_empty() || api_url.contains("api.openai.com");
+        let is_anthropic = api_url.contains("anthropic.com")
+            || (default_url && model.trim().to_ascii_lowercase().starts_with("claude"));
+        Ok(if is_anthropic {
+            Self::Anthropic
+        } else {
+            Self::OpenAi
+        })
+    }
+}
+
+impl FromStr for AiProvider {
+    type Err = anyhow::Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "openai" | "ollama" => Ok(Self::OpenAi),
+            "anthropic" | "claude" => Ok(Self::Anthropic),
+            _ => bail!("Unknown AI provider '{name}'. Use 'openai' or 'anthropic'."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPENAI_URL: &str = "https://api.openai.com/v1";
+
+    #[test]
+    fn provider_is_detected_from_url_and_model() {
+        let detect = |url, model| AiProvider::from_config(None, url, model).unwrap();
+
+        assert_eq!(detect(OPENAI_URL, "gpt-4"), AiProvider::OpenAi);
+        assert_eq!(
+            detect("http://localhost:11434/v1", "llama3"),
+            AiProvider::OpenAi
+        );
+        assert_eq!(
+            detect(OPENAI_URL, "claude-sonnet-4-5"),
+            AiProvider::Anthropic
+        );
+        assert_eq!(
+            detect("https://api.anthropic.com/v1", "some-model"),
+            AiProvider::Anthropic
+        );
+        assert_eq!(detect("", "claude-3-haiku"), AiProvider::Anthropic);
+    }
+
+    #[test]
+    fn custom_url_keeps_claude_models_on_openai() {
+        let provider =
+            AiProvider::from_config(None, "https://llm.example.com/v1", "claude-sonnet-4-5");
+        assert_eq!(provider.unwrap(), AiProvider::OpenAi);
+    }
+
+    #[test]
+    fn explicit_provider_wins() {
+        let provider = AiProvider::from_config(Some("OpenAI"), OPENAI_URL, "claude-3-haiku");
+        assert_eq!(provider.unwrap(), AiProvider::OpenAi);
+
+        let provider = AiProvider::from_config(Some("anthropic"), OPENAI_URL, "gpt-4");
+        assert_eq!(provider.unwrap(), AiProvider::Anthropic);
+
+        assert!(AiProvider::from_config(Some("gemini"), OPENAI_URL, "gpt-4").is_err());
+    }
+}
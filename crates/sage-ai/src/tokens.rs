@@ -0,0 +1,158 @@
+//! Keeping diffs within a model's token budget
+
+const TRUNCATED_MARKER: &str = "… (truncated)";
+
+/// Rough token count: about four characters per token for code and English.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Shrinks a unified diff to roughly `max_tokens`, estimated with [`estimate_tokens`].
+pub fn truncate_diff_for_model(diff: &str, max_tokens: usize) -> String {
+    truncate_diff_with(diff, max_tokens, estimate_tokens)
+}
+
+/// Like [`truncate_diff_for_model`], with a custom token estimator.
+///
+/// File headers (`diff --git` up to the first `@@`) are always kept. Hunk bodies share
+/// what is left of the budget, and each file that loses lines ends with a marker.
+pub fn truncate_diff_with(
+    diff: &str,
+    max_tokens: usize,
+    estimate: impl Fn(&str) -> usize,
+) -> String {
+    if estimate(diff) <= max_tokens {
+        return diff.to_string();
+    }
+
+    let (preamble, files) = split_files(diff);
+    let fixed = estimate(&preamble)
+        + files
+            .iter()
+            .map(|file| estimate(&file.header))
+            .sum::<usize>();
+    let mut remaining = max_tokens.saturating_sub(fixed);
+
+    // Smallest files first, so budget they don't need rolls over to the larger ones.
+    let mut order: Vec<usize> = (0..files.len()).collect();
+    order.sort_by_key(|&index| estimate(&files[index].body));
+
+    let mut bodies = vec![String::new(); files.len()];
+    for (position, &index) in order.iter().enumerate() {
+        let share = remaining / (files.len() - position);
+        let body = &files[index].body;
+
+        let kept = if estimate(body) <= share {
+            body.clone()
+        } else {
+            let mut kept = String::new();
+            let mut used = estimate(TRUNCATED_MARKER);
+            for line in body.split_inclusive('\n') {
+                used += estimate(line);
+                if used > share {
+                    break;
+                }
+                kept.push_str(line);
+            }
+            kept.push_str(TRUNCATED_MARKER);
+            kept.push('\n');
+            kept
+        };
+        remaining = remaining.saturating_sub(estimate(&kept));
+        bodies[index] = kept;
+    }
+
+    let mut out = preamble;
+    for (file, body) in files.iter().zip(bodies) {
+        out.push_str(&file.header);
+        out.push_str(&body);
+    }
+    out
+}
+
+struct FileDiff {
+    header: String,
+    body: String,
+}
+
+/// Splits a diff into the text before the first file and one entry per `diff --git` section.
+fn split_files(diff: &str) -> (String, Vec<FileDiff>) {
+    let mut preamble = String::new();
+    let mut files: Vec<FileDiff> = Vec::new();
+
+    for line in diff.split_inclusive('\n') {
+        if line.starts_with("diff --git ") {
+            files.push(FileDiff {
+                header: line.to_string(),
+                body: String::new(),
+            });
+            continue;
+        }
+
+        match files.last_mut() {
+            None => preamble.push_str(line),
+            Some(file) if file.body.is_empty() && !line.starts_with("@@") => {
+                file.header.push_str(line)
+            }
+            Some(file) => file.body.push_str(line),
+        }
+    }
+
+    (preamble, files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_diff(path: &str, lines: usize) -> String {
+        let mut diff = format!(
+            "diff --git a/{path} b/{path}\nindex 1111111..2222222 100644\n--- a/{path}\n+++ b/{path}\n@@ -0,0 +1,{lines} @@\n"
+        );
+        for line in 0..lines {
+            diff.push_str(&format!("+line {line} of {path}\n"));
+        }
+        diff
+    }
+
+    #[test]
+    fn small_diffs_are_untouched() {
+        let diff = format!("# Diff Content\n{}", file_diff("src/lib.rs", 3));
+        assert_eq!(truncate_diff_for_model(&diff, 1_000), diff);
+    }
+
+    #[test]
+    fn large_diffs_are_trimmed_but_keep_every_header() {
+        let diff = format!(
+            "# Diff Content\n{}{}",
+            file_diff("src/big.rs", 500),
+            file_diff("src/small.rs", 2)
+        );
+        let budget = 400;
+        let truncated = truncate_diff_for_model(&diff, budget);
+
+        assert!(estimate_tokens(&truncated) < estimate_tokens(&diff));
+        assert!(estimate_tokens(&truncated) <= budget + 10);
+        assert!(truncated.starts_with("# Diff Content\n"));
+        for path in ["src/big.rs", "src/small.rs"] {
+            assert!(truncated.contains(&format!("diff --git a/{path} b/{path}\n")));
+            assert!(truncated.contains(&format!("+++ b/{path}\n")));
+        }
+
+        // The small file fits whole; only the big one is cut.
+        assert!(truncated.contains("+line 1 of src/small.rs\n"));
+        assert!(!truncated.contains("+line 499 of src/big.rs"));
+        assert_eq!(truncated.matches(TRUNCATED_MARKER).count(), 1);
+    }
+
+    #[test]
+    fn estimator_is_pluggable() {
+        let diff = file_diff("src/lib.rs", 10);
+        let by_lines = |text: &str| text.lines().count();
+
+        let truncated = truncate_diff_with(&diff, 8, by_lines);
+        // Four header lines, the `@@` line, two added lines and the marker.
+        assert_eq!(truncated.lines().count(), 8);
+        assert!(truncated.ends_with(&format!("{TRUNCATED_MARKER}\n")));
+    }
+}
@@ -1,20 +1,79 @@
-use std::path::PathBuf;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
 
 use crate::config::{SECRET_KEYS, SageConfig};
 use crate::error::{ConfigError, Result};
-use crate::secret::SecretString;
+use crate::secret::{MASK, SecretString};
 use crate::toml_utils::{insert_value, parse_scalar_value, remove_value};
 
+use serde::Serialize;
 use toml::value::{Table, Value};
 
 const UPDATE_PATH: &str = "<config update>";
 
+/// Alternative spellings accepted by `get`/`set`, mapped to their canonical key.
+const KEY_ALIASES: &[(&str, &str)] = &[
+    ("general.auto_update", "general.update_check"),
+    ("ai.timeout", "ai.timeout_secs"),
+    ("ai.url", "ai.api_url"),
+];
+
+/// What [`set_value`] accepts for a key, checked before the config is changed.
+enum ValueRule {
+    Bool,
+    Integer { min: i64, max: i64 },
+    Url,
+}
+
+/// Keys with a constrained format; anything else is only checked by deserializing.
+const VALUE_RULES: &[(&str, ValueRule)] = &[
+    ("ai.api_url", ValueRule::Url),
+    ("ai.timeout_secs", ValueRule::Integer { min: 1, max: 3_600 }),
+    (
+        "ai.max_tokens",
+        ValueRule::Integer {
+            min: 0,
+            max: 1_048_576,
+        },
+    ),
+    ("ai.max_retries", ValueRule::Integer { min: 1, max: 10 }),
+    (
+        "ai.retry_delay_ms",
+        ValueRule::Integer {
+            min: 0,
+            max: 60_000,
+        },
+    ),
+    ("git.auto_stage", ValueRule::Bool),
+    ("git.disable_intermittent_fetch", ValueRule::Bool),
+    ("general.update_check", ValueRule::Bool),
+    ("general.telemetry", ValueRule::Bool),
+    ("general.auto_track", ValueRule::Bool),
+    ("general.abort_on_dirty", ValueRule::Bool),
+    ("save.sign_commits", ValueRule::Bool),
+];
+
+/// Where a config value came from, in increasing order of precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigSource {
+    #[default]
+    Default,
+    Global,
+    Local,
+    Env,
+}
+
 #[derive(Debug, Clone)]
 pub struct ConfigEntry {
     pub key: String,
     pub raw_value: Option<String>,
     pub display_value: Option<String>,
+    /// The typed value; masked like `display_value` for secrets.
+    pub value: Option<Value>,
     pub is_secret: bool,
+    /// Only filled in by [`crate::ConfigManager`]; plain lookups report `Default`.
+    pub source: ConfigSource,
 }
 
 pub fn list_entries(config: &SageConfig) -> Result<Vec<ConfigEntry>> {
@@ -57,6 +116,7 @@ pub fn set_value(config: &mut SageConfig, key: &str, value: Option<&str>) -> Res
     let mut table = config_to_table(config)?;
 
     if let Some(raw) = value {
+        validate_value(&segments.join("."), raw)?;
         insert_value(&mut table, &segments, parse_scalar_value(raw));
     } else {
         remove_value(&mut table, &segments);
@@ -70,6 +130,109 @@ pub fn set_value(config: &mut SageConfig, key: &str, value: Option<&str>) -> Res
     Ok(())
 }
 
+/// The whole config as TOML, with secrets masked unless `reveal` is set.
+pub fn export_toml(config: &SageConfig, reveal: bool) -> Result<String> {
+    let mut table = config_to_table(config)?;
+    if !reveal {
+        for &secret_key in SECRET_KEYS {
+            let segments = parse_key(secret_key)?;
+            if find_value(&Value::Table(table.clone()), &segments).is_some() {
+                insert_value(&mut table, &segments, Value::String(MASK.to_string()));
+            }
+        }
+    }
+    Ok(toml::to_string_pretty(&table)?)
+}
+
+/// Parse a whole config file, failing on bad TOML or values of the wrong type.
+pub fn parse_toml(content: &str, path: &Path) -> Result<SageConfig> {
+    toml::from_str(content).map_err(|e| ConfigError::parse(path.to_path_buf(), e))
+}
+
+/// Merge the settings in `content` (read from `path`) into `config`.
+///
+/// Every key must be a canonical config key; unknown keys fail the whole import.
+/// Masked secrets from a redacted export are skipped. Returns the keys applied.
+pub fn import_toml(config: &mut SageConfig, content: &str, path: &Path) -> Result<Vec<String>> {
+    let imported: Table =
+        toml::from_str(content).map_err(|e| ConfigError::parse(path.to_path_buf(), e))?;
+    let mut entries = Vec::new();
+    flatten_table("", &imported, &mut entries);
+
+    // Deserializing checks the value types; keys serde ignored don't survive the round trip.
+    let parsed: SageConfig = Value::Table(imported)
+        .try_into()
+        .map_err(|e| ConfigError::parse(path.to_path_buf(), e))?;
+    let mut known = Vec::new();
+    flatten_table("", &config_to_table(&parsed)?, &mut known);
+    let known: BTreeSet<String> = known.into_iter().map(|(key, _)| key).collect();
+
+    let unknown: Vec<String> = entries
+        .iter()
+        .map(|(key, _)| key.clone())
+        .filter(|key| !known.contains(key))
+        .collect();
+    if !unknown.is_empty() {
+        return Err(ConfigError::unknown_keys(&unknown));
+    }
+
+    let mut table = config_to_table(config)?;
+    let mut applied = Vec::new();
+    for (key, value) in entries {
+        let is_masked = SECRET_KEYS.contains(&key.as_str()) && value.as_str() == Some(MASK);
+        if is_masked {
+            continue;
+        }
+        insert_value(&mut table, &parse_key(&key)?, value);
+        applied.push(key);
+    }
+
+    *config = Value::Table(table)
+        .try_into()
+        .map_err(|e| ConfigError::parse(path.to_path_buf(), e))?;
+    Ok(applied)
+}
+
+fn validate_value(key: &str, raw: &str) -> Result<()> {
+    let Some((_, rule)) = VALUE_RULES.iter().find(|(rule_key, _)| *rule_key == key) else {
+        return Ok(());
+    };
+    let trimmed = raw.trim();
+
+    let expected = match rule {
+        ValueRule::Bool => {
+            if matches!(parse_scalar_value(trimmed), Value::Boolean(_)) {
+                return Ok(());
+            }
+            "expected true or false".to_string()
+        }
+        ValueRule::Integer { min, max } => {
+            if trimmed
+                .parse::<i64>()
+                .is_ok_and(|number| (*min..=*max).contains(&number))
+            {
+                return Ok(());
+            }
+            format!("expected a whole number from {min} to {max}")
+        }
+        ValueRule::Url => {
+            let host = trimmed
+                .strip_prefix("https://")
+                .or_else(|| trimmed.strip_prefix("http://"));
+            if host.is_some_and(|rest| !rest.is_empty() && !rest.starts_with('/')) {
+                return Ok(());
+            }
+            "expected an http:// or https:// URL".to_string()
+        }
+    };
+
+    Err(ConfigError::invalid_value(
+        key.to_string(),
+        raw.to_string(),
+        expected,
+    ))
+}
+
 fn config_to_table(config: &SageConfig) -> Result<Table> {
     match Value::try_from(config.clone()).map_err(ConfigError::Serialize)? {
         Value::Table(table) => Ok(table),
@@ -103,19 +266,28 @@ fn to_entry(key: String, value: Option<Value>) -> ConfigEntry {
             } else {
                 raw_value.as_ref().unwrap().clone()
             });
+            let value = if is_secret {
+                display_value.clone().map(Value::String)
+            } else {
+                Some(value)
+            };
 
             ConfigEntry {
                 key,
                 raw_value,
                 display_value,
+                value,
                 is_secret,
+                source: ConfigSource::Default,
             }
         }
         None => ConfigEntry {
             key,
             raw_value: None,
             display_value: Some("<unset>".to_string()),
+            value: None,
             is_secret,
+            source: ConfigSource::Default,
         },
     }
 }
@@ -143,7 +315,7 @@ fn mask_secret(raw: &str) -> String {
     SecretString::from(raw).to_string()
 }
 
-fn parse_key(key: &str) -> Result<Vec<String>> {
+pub(crate) fn parse_key(key: &str) -> Result<Vec<String>> {
     let segments: Vec<String> = key
         .split('.')
         .filter(|segment| !segment.is_empty())
@@ -157,10 +329,15 @@ fn parse_key(key: &str) -> Result<Vec<String>> {
         ));
     }
 
+    let joined = segments.join(".");
+    if let Some((_, canonical)) = KEY_ALIASES.iter().find(|(alias, _)| *alias == joined) {
+        return Ok(canonical.split('.').map(str::to_string).collect());
+    }
+
     Ok(segments)
 }
 
-fn find_value<'a>(value: &'a Value, path: &[String]) -> Option<&'a Value> {
+pub(crate) fn find_value<'a>(value: &'a Value, path: &[String]) -> Option<&'a Value> {
     if path.is_empty() {
         return Some(value);
     }
@@ -173,3 +350,93 @@ fn find_value<'a>(value: &'a Value, path: &[String]) -> Option<&'a Value> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_import_round_trips() {
+        let mut source = SageConfig::default();
+        set_value(&mut source, "ai.model", Some("custom-model")).expect("set");
+        set_value(&mut source, "general.worktree_dir", Some("../trees")).expect("set");
+        source.ai.api_key = Some(SecretString::new("sk-test-123"));
+
+        let redacted = export_toml(&source, false).expect("export");
+        assert!(!redacted.contains("sk-test-123"), "{redacted}");
+        let revealed = export_toml(&source, true).expect("export");
+        assert!(revealed.contains("sk-test-123"), "{revealed}");
+
+        let path = Path::new("shared.toml");
+        let mut target = SageConfig::default();
+        target.ai.api_key = Some(SecretString::new("sk-mine"));
+        let applied = import_toml(&mut target, &redacted, path).expect("import");
+        assert!(applied.contains(&"ai.model".to_string()));
+        assert!(!applied.contains(&"ai.api_key".to_string()));
+        assert_eq!(target.ai.model, "custom-model");
+        assert_eq!(target.general.worktree_dir.as_deref(), Some("../trees"));
+        assert_eq!(
+            target.ai.api_key.as_ref().map(|key| key.expose()),
+            Some("sk-mine")
+        );
+
+        import_toml(&mut target, &revealed, path).expect("import");
+        assert_eq!(
+            target.ai.api_key.as_ref().map(|key| key.expose()),
+            Some("sk-test-123")
+        );
+    }
+
+    #[test]
+    fn import_rejects_unknown_keys() {
+        let mut config = SageConfig::default();
+        let content = "[ai]\nmodel = \"other\"\nmodle = \"typo\"\n\n[colors]\nfg = \"red\"\n";
+
+        let err =
+            import_toml(&mut config, content, Path::new("bad.toml")).expect_err("unknown keys");
+        let message = err.to_string();
+        assert!(message.contains("ai.modle"), "{message}");
+        assert!(message.contains("colors.fg"), "{message}");
+        assert_ne!(config.ai.model, "other", "nothing applied on error");
+    }
+
+    #[test]
+    fn typed_keys_reject_malformed_values() {
+        let mut config = SageConfig::default();
+
+        set_value(&mut config, "ai.timeout_secs", Some("120")).expect("valid timeout");
+        assert_eq!(config.ai.timeout_secs, 120);
+        let err = set_value(&mut config, "ai.timeout", Some("0")).expect_err("zero timeout");
+        assert!(err.to_string().contains("from 1 to 3600"), "{err}");
+        let err = set_value(&mut config, "ai.max_retries", Some("lots")).expect_err("not a number");
+        assert!(err.to_string().contains("ai.max_retries"), "{err}");
+
+        set_value(&mut config, "git.auto_stage", Some("false")).expect("valid bool");
+        let err = set_value(&mut config, "git.auto_stage", Some("yes")).expect_err("not a bool");
+        assert!(err.to_string().contains("true or false"), "{err}");
+
+        set_value(&mut config, "ai.api_url", Some("http://localhost:11434/v1")).expect("valid url");
+        let err =
+            set_value(&mut config, "ai.api_url", Some("api.openai.com")).expect_err("no scheme");
+        assert!(err.to_string().contains("URL"), "{err}");
+
+        assert_eq!(
+            config.ai.timeout_secs, 120,
+            "rejected values are not applied"
+        );
+        assert!(!config.git.auto_stage);
+        assert_eq!(config.ai.api_url, "http://localhost:11434/v1");
+    }
+
+    #[test]
+    fn auto_update_alias_sets_update_check() {
+        let mut config = SageConfig::default();
+        set_value(&mut config, "general.auto_update", Some("false")).expect("set alias");
+        assert!(!config.general.update_check);
+
+        let entry = get_entry(&config, "general.auto_update")
+            .expect("get alias")
+            .expect("entry");
+        assert_eq!(entry.raw_value.as_deref(), Some("false"));
+    }
+}
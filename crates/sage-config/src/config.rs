@@ -10,6 +10,8 @@ pub struct SageConfig {
     pub git: GitConfig,
     #[serde(default)]
     pub general: GeneralConfig,
+    #[serde(default)]
+    pub save: SaveConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +19,10 @@ pub struct AiConfig {
     #[serde(default)]
     pub api_key: Option<SecretString>,
 
+    /// `openai` or `anthropic`; when unset it is guessed from `api_url` and `model`.
+    #[serde(default)]
+    pub provider: Option<String>,
+
     #[serde(default = "default_model")]
     pub model: String,
 
@@ -46,6 +52,7 @@ impl Default for AiConfig {
     fn default() -> Self {
         Self {
             api_key: None,
+            provider: None,
             model: default_model(),
             api_url: default_api_url(),
             timeout_secs: default_timeout_secs(),
@@ -68,6 +75,18 @@ pub struct GitConfig {
 
     #[serde(default = "default_commit_template")]
     pub commit_template: String,
+
+    /// Token used by `sage share`; `GITHUB_TOKEN`/`GH_TOKEN` take precedence.
+    #[serde(default)]
+    pub github_token: Option<SecretString>,
+
+    /// Token used by `sage share` on GitLab; `GITLAB_TOKEN` takes precedence.
+    #[serde(default)]
+    pub gitlab_token: Option<SecretString>,
+
+    /// Host of a self-hosted GitLab instance, e.g. `git.example.com`.
+    #[serde(default)]
+    pub forge_host: Option<String>,
 }
 
 impl Default for GitConfig {
@@ -76,6 +95,9 @@ impl Default for GitConfig {
             auto_stage: default_auto_stage(),
             disable_intermittent_fetch: default_disable_intermittent_fetch(),
             commit_template: default_commit_template(),
+            github_token: None,
+            gitlab_token: None,
+            forge_host: None,
         }
     }
 }
@@ -87,6 +109,34 @@ pub struct GeneralConfig {
 
     #[serde(default = "default_telemetry")]
     pub telemetry: bool,
+
+    /// Where `sage work --worktree` creates linked worktrees.
+    /// Relative paths are resolved against the repository root.
+    #[serde(default)]
+    pub worktree_dir: Option<String>,
+
+    /// Whether `sage work` adds branches it creates to the stack graph.
+    /// `--track`/`--no-track` override it per invocation.
+    #[serde(default = "default_auto_track")]
+    pub auto_track: bool,
+
+    /// Make `sage work` refuse to switch branches with uncommitted changes
+    /// instead of carrying them along. `--abort-on-dirty` enables it per invocation.
+    #[serde(default)]
+    pub abort_on_dirty: bool,
+
+    /// Color theme preset: `default`, `high-contrast` or `deuteranopia`.
+    #[serde(default = "default_theme")]
+    pub theme: String,
+
+    /// Progress spinner animation: `wave`, `dots`, `line` or `braille`.
+    #[serde(default = "default_spinner")]
+    pub spinner: String,
+
+    /// Branch name for `sage work --issue`.
+    /// Supports `{type}`, `{issue}` and `{slug}` placeholders.
+    #[serde(default = "default_branch_template")]
+    pub branch_template: String,
 }
 
 impl Default for GeneralConfig {
@@ -94,6 +144,33 @@ impl Default for GeneralConfig {
         Self {
             update_check: default_update_check(),
             telemetry: default_telemetry(),
+            worktree_dir: None,
+            auto_track: default_auto_track(),
+            abort_on_dirty: false,
+            theme: default_theme(),
+            spinner: default_spinner(),
+            branch_template: default_branch_template(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveConfig {
+    /// Pre-filled message when `sage save` opens the editor.
+    /// Supports `{branch}` and `{diffstat}` placeholders.
+    #[serde(default = "default_save_template")]
+    pub template: String,
+
+    /// Sign commits made by `sage save` as if `--sign` were passed.
+    #[serde(default)]
+    pub sign_commits: bool,
+}
+
+impl Default for SaveConfig {
+    fn default() -> Self {
+        Self {
+            template: default_save_template(),
+            sign_commits: false,
         }
     }
 }
@@ -102,7 +179,7 @@ fn default_model() -> String {
     "gpt-4".to_string()
 }
 
-pub const SECRET_KEYS: &[&str] = &["ai.api_key"];
+pub const SECRET_KEYS: &[&str] = &["ai.api_key", "git.github_token", "git.gitlab_token"];
 
 fn default_api_url() -> String {
     "https://api.openai.com/v1".to_string()
@@ -144,6 +221,26 @@ fn default_telemetry() -> bool {
     false
 }
 
+fn default_auto_track() -> bool {
+    true
+}
+
+fn default_theme() -> String {
+    "default".to_string()
+}
+
+fn default_spinner() -> String {
+    "wave".to_string()
+}
+
+fn default_branch_template() -> String {
+    "{type}/{issue}-{slug}".to_string()
+}
+
+fn default_save_template() -> String {
+    String::new()
+}
+
 fn default_reasoning_effort() -> Option<String> {
     Some("minimal".to_string())
 }
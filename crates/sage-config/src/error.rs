@@ -31,6 +31,9 @@ pub enum ConfigError {
     #[error("Field '{field}' not found. Available fields: {available}")]
     FieldNotFound { field: String, available: String },
 
+    #[error("Unknown config keys: {keys}")]
+    UnknownKeys { keys: String },
+
     #[error("Invalid value '{value}' for field '{field}': {reason}")]
     InvalidValue {
         field: String,
@@ -72,6 +75,12 @@ impl ConfigError {
         Self::FieldNotFound { field, available }
     }
 
+    pub fn unknown_keys(keys: &[String]) -> Self {
+        Self::UnknownKeys {
+            keys: keys.join(", "),
+        }
+    }
+
     pub fn invalid_value(field: String, value: String, reason: String) -> Self {
         Self::InvalidValue {
             field,
@@ -6,8 +6,11 @@ mod manager;
 mod secret;
 mod toml_utils;
 
-pub use access::{ConfigEntry, get_entry, list_entries, set_value};
-pub use config::{AiConfig, GeneralConfig, GitConfig, SageConfig};
+pub use access::{
+    ConfigEntry, ConfigSource, export_toml, get_entry, import_toml, list_entries, parse_toml,
+    set_value,
+};
+pub use config::{AiConfig, GeneralConfig, GitConfig, SageConfig, SaveConfig};
 pub use error::{ConfigError, Result as ConfigResult};
 pub use manager::ConfigManager;
 pub use secret::SecretString;
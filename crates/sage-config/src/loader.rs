@@ -1,14 +1,17 @@
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use config::{Environment, File, FileFormat};
 
+use crate::access::{ConfigSource, find_value};
 use crate::config::SageConfig;
 use crate::error::{ConfigError, Result};
 
 const ENV_PREFIX: &str = "SAGE";
 const ENV_SEPARATOR: &str = "__";
+/// Top-level tables of [`SageConfig`], the `<SECTION>` in `SAGE_<SECTION>_<KEY>`.
+const ENV_SECTIONS: &[&str] = &["ai", "git", "general", "save"];
 
 pub struct ConfigLoader {
     global_path: PathBuf,
@@ -31,6 +34,15 @@ impl ConfigLoader {
     }
 
     pub fn load(&self) -> Result<SageConfig> {
+        self.load_with_env(env::vars())
+    }
+
+    /// Layers, lowest to highest precedence: defaults, global file, local file, then
+    /// `SAGE__<SECTION>__<KEY>` and `SAGE_<SECTION>_<KEY>` variables from `vars`.
+    fn load_with_env(
+        &self,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<SageConfig> {
         let mut builder = config::Config::builder().add_source(
             File::from(self.global_path.clone())
                 .format(FileFormat::Toml)
@@ -51,10 +63,22 @@ impl ConfigLoader {
                 .try_parsing(true),
         );
 
+        for (key, value) in env_overrides(vars) {
+            builder = builder.set_override(key, value)?;
+        }
+
         let config = builder.build()?.try_deserialize::<SageConfig>()?;
         Ok(config)
     }
 
+    /// The highest-precedence layer that sets the key at `path`.
+    pub fn source_of(&self, path: &[String]) -> ConfigSource {
+        let env_keys: Vec<String> = env::vars().map(|(key, _)| key).collect();
+        let local = self.local_path.as_deref().and_then(read_toml);
+        let global = read_toml(&self.global_path);
+        source_for(path, &env_keys, local.as_ref(), global.as_ref())
+    }
+
     fn global_config_path() -> Result<PathBuf> {
         #[cfg(windows)]
         let config_dir = dirs::config_dir()
@@ -90,3 +114,122 @@ impl ConfigLoader {
         None
     }
 }
+
+/// Maps `SAGE_AI_MODEL=x` style variables to `("ai.model", "x")`.
+///
+/// The section is the first word after the prefix and the rest is the key, so keys keep
+/// their underscores (`SAGE_AI_MAX_TOKENS` is `ai.max_tokens`).
+fn env_overrides(vars: impl IntoIterator<Item = (String, String)>) -> Vec<(String, String)> {
+    vars.into_iter()
+        .filter_map(|(name, value)| {
+            let key = env_key_from_var(&name)?;
+            Some((key, value))
+        })
+        .collect()
+}
+
+fn env_key_from_var(name: &str) -> Option<String> {
+    let name = name.to_ascii_lowercase();
+    let rest = name.strip_prefix(&format!("{}_", ENV_PREFIX.to_ascii_lowercase()))?;
+    let (section, key) = rest.split_once('_')?;
+    (ENV_SECTIONS.contains(&section) && !key.is_empty()).then(|| format!("{section}.{key}"))
+}
+
+fn read_toml(path: &Path) -> Option<toml::Value> {
+    fs::read_to_string(path).ok()?.parse().ok()
+}
+
+fn source_for(
+    path: &[String],
+    env_keys: &[String],
+    local: Option<&toml::Value>,
+    global: Option<&toml::Value>,
+) -> ConfigSource {
+    let env_key = format!(
+        "{ENV_PREFIX}{ENV_SEPARATOR}{}",
+        path.join(ENV_SEPARATOR).to_ascii_uppercase()
+    );
+    let dotted = path.join(".");
+    if env_keys.iter().any(|key| {
+        key.eq_ignore_ascii_case(&env_key) || env_key_from_var(key).as_deref() == Some(&dotted)
+    }) {
+        return ConfigSource::Env;
+    }
+
+    if local.is_some_and(|local| find_value(local, path).is_some()) {
+        return ConfigSource::Local;
+    }
+
+    if global.is_some_and(|global| find_value(global, path).is_some()) {
+        return ConfigSource::Global;
+    }
+
+    ConfigSource::Default
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(key: &str) -> Vec<String> {
+        key.split('.').map(str::to_string).collect()
+    }
+
+    #[test]
+    fn source_prefers_env_then_local_then_global() {
+        let global: toml::Value = "[ai]\nmodel = \"a\"\napi_url = \"b\"\n".parse().unwrap();
+        let local: toml::Value = "[ai]\nmodel = \"c\"\n".parse().unwrap();
+        let env_keys = vec!["SAGE__AI__MAX_TOKENS".to_string()];
+
+        let source = |key: &str| source_for(&path(key), &env_keys, Some(&local), Some(&global));
+        assert_eq!(source("ai.max_tokens"), ConfigSource::Env);
+        assert_eq!(source("ai.model"), ConfigSource::Local);
+        assert_eq!(source("ai.api_url"), ConfigSource::Global);
+        assert_eq!(source("git.auto_stage"), ConfigSource::Default);
+
+        let env_keys = vec!["SAGE_AI_MODEL".to_string()];
+        let source = source_for(&path("ai.model"), &env_keys, Some(&local), Some(&global));
+        assert_eq!(source, ConfigSource::Env);
+    }
+
+    #[test]
+    fn single_underscore_env_vars_map_to_keys() {
+        assert_eq!(
+            env_key_from_var("SAGE_AI_MODEL").as_deref(),
+            Some("ai.model")
+        );
+        assert_eq!(
+            env_key_from_var("SAGE_GENERAL_AUTO_TRACK").as_deref(),
+            Some("general.auto_track")
+        );
+        assert_eq!(env_key_from_var("SAGE_UNKNOWN_KEY"), None);
+        assert_eq!(env_key_from_var("SAGE__AI__MODEL"), None);
+        assert_eq!(env_key_from_var("SAGEAI_MODEL"), None);
+        assert_eq!(env_key_from_var("SAGE_AI_"), None);
+    }
+
+    #[test]
+    fn env_overrides_file_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let global_path = dir.path().join("config.toml");
+        fs::write(
+            &global_path,
+            "[ai]\nmodel = \"from-file\"\nmax_tokens = 100\n",
+        )
+        .unwrap();
+        let loader = ConfigLoader {
+            global_path,
+            local_path: None,
+        };
+
+        let vars = [
+            ("SAGE_AI_MODEL".to_string(), "from-env".to_string()),
+            ("SAGE_GIT_AUTO_STAGE".to_string(), "false".to_string()),
+            ("HOME".to_string(), "/home/someone".to_string()),
+        ];
+        let config = loader.load_with_env(vars).unwrap();
+        assert_eq!(config.ai.model, "from-env");
+        assert_eq!(config.ai.max_tokens, 100);
+        assert!(!config.git.auto_stage);
+    }
+}
@@ -1,6 +1,8 @@
 use std::fs;
 use std::io::Write;
+use std::path::Path;
 
+use crate::access::{ConfigEntry, get_entry, list_entries, parse_key};
 use crate::config::SageConfig;
 use crate::error::{ConfigError, Result};
 use crate::loader::ConfigLoader;
@@ -18,6 +20,17 @@ impl ConfigManager {
         Ok(Self { config, loader })
     }
 
+    /// Re-read the configuration from disk, discarding unsaved changes.
+    pub fn reload(&mut self) -> Result<()> {
+        self.config = self.loader.load()?;
+        Ok(())
+    }
+
+    /// The user-wide config file that [`ConfigManager::save`] writes to.
+    pub fn global_path(&self) -> &Path {
+        self.loader.global_path()
+    }
+
     pub fn get(&self) -> &SageConfig {
         &self.config
     }
@@ -26,6 +39,24 @@ impl ConfigManager {
         &mut self.config
     }
 
+    /// Like [`get_entry`], with the entry's source filled in.
+    pub fn get_entry(&self, key: &str) -> Result<Option<ConfigEntry>> {
+        let Some(mut entry) = get_entry(&self.config, key)? else {
+            return Ok(None);
+        };
+        entry.source = self.loader.source_of(&parse_key(key)?);
+        Ok(Some(entry))
+    }
+
+    /// Like [`list_entries`], with each entry's source filled in.
+    pub fn list_entries(&self) -> Result<Vec<ConfigEntry>> {
+        let mut entries = list_entries(&self.config)?;
+        for entry in &mut entries {
+            entry.source = self.loader.source_of(&parse_key(&entry.key)?);
+        }
+        Ok(entries)
+    }
+
     pub fn save(&self) -> Result<()> {
         let config_path = self.loader.global_path();
 
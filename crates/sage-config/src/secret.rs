@@ -54,24 +54,41 @@ impl AsRef<str> for SecretString {
     }
 }
 
+pub(crate) const MASK: &str = "••••••";
+
 fn mask(value: &str) -> String {
-    let chars: Vec<char> = value.chars().collect();
-    let len = chars.len();
-
-    match len {
-        0 => String::new(),
-        1..=4 => "*".repeat(len),
-        _ => {
-            let prefix: String = chars.iter().take(2).collect();
-            let suffix: String = chars
-                .iter()
-                .rev()
-                .take(2)
-                .collect::<Vec<_>>()
-                .into_iter()
-                .rev()
-                .collect();
-            format!("{prefix}***{suffix}")
+    if value.is_empty() {
+        String::new()
+    } else {
+        MASK.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_and_display_never_leak_value() {
+        let secret = SecretString::new("sk-test-1234567890");
+
+        let debug = format!("{secret:?}");
+        let display = format!("{secret}");
+
+        for rendered in [&debug, &display] {
+            assert!(
+                !rendered.contains("sk-test-1234567890"),
+                "leaked: {rendered}"
+            );
+            assert!(!rendered.contains("sk"), "leaked prefix: {rendered}");
+            assert!(!rendered.contains("90"), "leaked suffix: {rendered}");
         }
+        assert_eq!(display, MASK);
+        assert_eq!(secret.expose(), "sk-test-1234567890");
+    }
+
+    #[test]
+    fn empty_secret_renders_empty() {
+        assert_eq!(SecretString::new("").to_string(), "");
     }
 }
@@ -0,0 +1,20 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+pub use sage_ai::{AiOverrides, AiProvider, set_ai_overrides};
+
+/// Setting this to `1` has the same effect as `--ai-debug`.
+pub const AI_DEBUG_ENV: &str = "SAGE_AI_DEBUG";
+
+/// Where AI prompts and responses are logged, if `--ai-debug` or `SAGE_AI_DEBUG` asks for it.
+pub fn ai_debug_log(flag: bool) -> Result<Option<PathBuf>> {
+    let from_env = std::env::var(AI_DEBUG_ENV)
+        .is_ok_and(|value| matches!(value.trim().to_ascii_lowercase().as_str(), "1" | "true"));
+    if !flag && !from_env {
+        return Ok(None);
+    }
+
+    let repo = sage_git::Repo::open()?;
+    Ok(Some(repo.git_dir().join("sage").join("ai-debug.log")))
+}
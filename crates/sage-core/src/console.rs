@@ -0,0 +1,22 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{Context, Result};
+use sage_config::ConfigManager;
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Make every console built by [`console`] quiet; set once from the global `--quiet` flag.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Build a console using the theme and spinner selected by `general.theme` and `general.spinner`.
+pub fn console() -> Result<sage_fmt::Console> {
+    let config_manager = ConfigManager::load().context("Failed to load configuration")?;
+    let general = &config_manager.get().general;
+    let theme = sage_fmt::Theme::preset(&general.theme)?;
+    let spinner = sage_fmt::SpinnerStyle::from_name(&general.spinner)?;
+    Ok(sage_fmt::Console::with_theme(theme)
+        .with_spinner_style(spinner)
+        .quiet(QUIET.load(Ordering::Relaxed)))
+}
@@ -0,0 +1,83 @@
+use anyhow::{Context, Result, anyhow, bail};
+use octocrab::{Octocrab, models::pulls::PullRequest, params::State};
+
+use super::{ForgeClient, NewPullRequest, PullRequestInfo};
+
+pub(crate) struct GitHubClient {
+    client: Octocrab,
+    owner: String,
+    repo: String,
+}
+
+impl GitHubClient {
+    pub(crate) fn new(token: String, path: &str) -> Result<Self> {
+        let (owner, repo) = path
+            .split_once('/')
+            .ok_or_else(|| anyhow!("'{path}' is not an owner/repo path"))?;
+        let client = Octocrab::builder()
+            .personal_token(token)
+            .build()
+            .context("Failed to create GitHub client")?;
+        Ok(Self {
+            client,
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        })
+    }
+}
+
+impl ForgeClient for GitHubClient {
+    async fn find_pr(&self, head: &str) -> Result<Option<PullRequestInfo>> {
+        let page = self
+            .client
+            .pulls(&self.owner, &self.repo)
+            .list()
+            .state(State::Open)
+            .head(format!("{}:{head}", self.owner))
+            .send()
+            .await
+            .context("Failed to look up existing pull requests")?;
+        Ok(page.items.into_iter().next().map(to_info))
+    }
+
+    async fn create_pr(&self, request: NewPullRequest<'_>) -> Result<PullRequestInfo> {
+        let pr = self
+            .client
+            .pulls(&self.owner, &self.repo)
+            .create(request.title, request.head, request.base)
+            .body(request.body)
+            .draft(request.draft)
+            .send()
+            .await
+            .context("Failed to create pull request")?;
+        Ok(to_info(pr))
+    }
+
+    async fn mark_ready(&self, pr: &PullRequestInfo) -> Result<()> {
+        // The REST API can't un-draft a pull request, only GraphQL can.
+        let payload = serde_json::json!({
+            "query": "mutation($id: ID!) { markPullRequestReadyForReview(input: {pullRequestId: $id}) { pullRequest { number } } }",
+            "variables": { "id": pr.id },
+        });
+
+        let response: serde_json::Value = self
+            .client
+            .graphql(&payload)
+            .await
+            .context("Failed to mark pull request ready")?;
+        if let Some(errors) = response.get("errors") {
+            bail!("Failed to mark pull request ready: {errors}");
+        }
+        Ok(())
+    }
+}
+
+fn to_info(pr: PullRequest) -> PullRequestInfo {
+    PullRequestInfo {
+        number: pr.number,
+        title: pr.title.unwrap_or_default(),
+        url: pr.html_url.map(|url| url.to_string()),
+        draft: pr.draft.unwrap_or(false),
+        id: pr.node_id.unwrap_or_default(),
+    }
+}
@@ -0,0 +1,113 @@
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+use super::{ForgeClient, NewPullRequest, PullRequestInfo};
+
+const DRAFT_PREFIX: &str = "Draft: ";
+
+pub(crate) struct GitLabClient {
+    http: reqwest::Client,
+    api_url: String,
+    project: String,
+    token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MergeRequest {
+    iid: u64,
+    title: String,
+    web_url: Option<String>,
+    #[serde(default)]
+    draft: bool,
+}
+
+impl GitLabClient {
+    pub(crate) fn new(token: String, host: &str, path: &str) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            api_url: format!("https://{host}/api/v4"),
+            // Project paths are addressed as a single URL-encoded id.
+            project: path.replace('/', "%2F"),
+            token,
+        }
+    }
+
+    fn merge_requests_url(&self) -> String {
+        format!("{}/projects/{}/merge_requests", self.api_url, self.project)
+    }
+
+    async fn send<T: for<'de> Deserialize<'de>>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<T> {
+        let response = request
+            .header("PRIVATE-TOKEN", &self.token)
+            .send()
+            .await
+            .context("Failed to reach GitLab")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            bail!("GitLab request failed ({status}): {}", body.trim());
+        }
+
+        response
+            .json()
+            .await
+            .context("Failed to parse GitLab response")
+    }
+}
+
+impl ForgeClient for GitLabClient {
+    async fn find_pr(&self, head: &str) -> Result<Option<PullRequestInfo>> {
+        let request = self
+            .http
+            .get(self.merge_requests_url())
+            .query(&[("state", "opened"), ("source_branch", head)]);
+        let merge_requests: Vec<MergeRequest> = self.send(request).await?;
+        Ok(merge_requests.into_iter().next().map(to_info))
+    }
+
+    async fn create_pr(&self, request: NewPullRequest<'_>) -> Result<PullRequestInfo> {
+        let title = if request.draft {
+            format!("{DRAFT_PREFIX}{}", request.title)
+        } else {
+            request.title.to_string()
+        };
+        let body = serde_json::json!({
+            "source_branch": request.head,
+            "target_branch": request.base,
+            "title": title,
+            "description": request.body,
+        });
+        let merge_request: MergeRequest = self
+            .send(self.http.post(self.merge_requests_url()).json(&body))
+            .await?;
+        Ok(to_info(merge_request))
+    }
+
+    async fn mark_ready(&self, pr: &PullRequestInfo) -> Result<()> {
+        // GitLab tracks draft status through the title prefix.
+        let title = pr.title.strip_prefix(DRAFT_PREFIX).unwrap_or(&pr.title);
+        let url = format!("{}/{}", self.merge_requests_url(), pr.number);
+        let _: MergeRequest = self
+            .send(
+                self.http
+                    .put(url)
+                    .json(&serde_json::json!({ "title": title })),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+fn to_info(merge_request: MergeRequest) -> PullRequestInfo {
+    PullRequestInfo {
+        number: merge_request.iid,
+        draft: merge_request.draft || merge_request.title.starts_with(DRAFT_PREFIX),
+        title: merge_request.title,
+        url: merge_request.web_url,
+        id: merge_request.iid.to_string(),
+    }
+}
@@ -0,0 +1,142 @@
+use anyhow::Result;
+use sage_git::RemoteUrl;
+
+mod github;
+mod gitlab;
+
+pub(crate) use github::GitHubClient;
+pub(crate) use gitlab::GitLabClient;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForgeKind {
+    GitHub,
+    GitLab,
+}
+
+impl ForgeKind {
+    /// What the forge calls a pull request, for user-facing messages.
+    pub fn request_noun(self) -> &'static str {
+        match self {
+            ForgeKind::GitHub => "pull request",
+            ForgeKind::GitLab => "merge request",
+        }
+    }
+}
+
+/// A repository on a forge, parsed from a remote URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForgeRepo {
+    pub kind: ForgeKind,
+    pub host: String,
+    /// `owner/repo`, or `group/subgroup/repo` on GitLab.
+    pub path: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PullRequestInfo {
+    /// Number shown to users (`#12` on GitHub, `!12` on GitLab).
+    pub number: u64,
+    pub title: String,
+    pub url: Option<String>,
+    pub draft: bool,
+    /// Forge-specific identifier needed to update the request (GraphQL node id on GitHub).
+    pub id: String,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct NewPullRequest<'a> {
+    pub title: &'a str,
+    pub body: &'a str,
+    pub head: &'a str,
+    pub base: &'a str,
+    pub draft: bool,
+}
+
+pub(crate) trait ForgeClient {
+    async fn find_pr(&self, head: &str) -> Result<Option<PullRequestInfo>>;
+    async fn create_pr(&self, request: NewPullRequest<'_>) -> Result<PullRequestInfo>;
+    async fn mark_ready(&self, pr: &PullRequestInfo) -> Result<()>;
+}
+
+/// Work out which forge hosts `url`. `forge_host` names a self-hosted GitLab instance.
+pub fn detect_forge(url: &RemoteUrl, forge_host: Option<&str>) -> Option<ForgeRepo> {
+    let kind = if url.host == "github.com" {
+        ForgeKind::GitHub
+    } else if url.host == "gitlab.com"
+        || forge_host.is_some_and(|forge| forge.trim().eq_ignore_ascii_case(&url.host))
+    {
+        ForgeKind::GitLab
+    } else {
+        return None;
+    };
+
+    // GitHub has no nested groups.
+    if kind == ForgeKind::GitHub && url.owner.contains('/') {
+        return None;
+    }
+
+    Some(ForgeRepo {
+        kind,
+        host: url.host.clone(),
+        path: url.path(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(url: &str) -> Option<(ForgeKind, String, String)> {
+        let url = RemoteUrl::parse(url)?;
+        detect_forge(&url, Some("git.example.com")).map(|repo| (repo.kind, repo.host, repo.path))
+    }
+
+    #[test]
+    fn detects_github_remotes() {
+        let expected = Some((
+            ForgeKind::GitHub,
+            "github.com".to_string(),
+            "sage-scm/sage".to_string(),
+        ));
+        assert_eq!(detect("git@github.com:sage-scm/sage.git"), expected);
+        assert_eq!(detect("ssh://git@github.com/sage-scm/sage.git"), expected);
+        assert_eq!(detect("https://github.com/sage-scm/sage"), expected);
+        assert_eq!(detect("https://github.com/sage-scm/sage.git/"), expected);
+    }
+
+    #[test]
+    fn detects_gitlab_remotes() {
+        assert_eq!(
+            detect("git@gitlab.com:group/sub/project.git"),
+            Some((
+                ForgeKind::GitLab,
+                "gitlab.com".to_string(),
+                "group/sub/project".to_string()
+            ))
+        );
+        assert_eq!(
+            detect("https://gitlab.com/group/project.git"),
+            Some((
+                ForgeKind::GitLab,
+                "gitlab.com".to_string(),
+                "group/project".to_string()
+            ))
+        );
+        assert_eq!(
+            detect("ssh://git@git.example.com:2222/team/project.git"),
+            Some((
+                ForgeKind::GitLab,
+                "git.example.com".to_string(),
+                "team/project".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn unknown_hosts_are_not_forges() {
+        assert_eq!(detect("git@bitbucket.org:team/project.git"), None);
+        assert_eq!(detect("git@github.com:group/sub/project.git"), None);
+        let self_hosted = RemoteUrl::parse("https://git.example.com/team/project").unwrap();
+        assert_eq!(detect_forge(&self_hosted, None), None);
+    }
+}
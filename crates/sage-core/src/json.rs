@@ -0,0 +1,60 @@
+use anyhow::Result;
+use serde::Serialize;
+
+/// Version of the `--json` envelope and payloads.
+///
+/// Bump whenever a field is renamed, removed or changes type so consumers can detect it.
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+/// Wrapper around every `--json` payload: `{ "schema_version", "command", "data" }`.
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonEnvelope<T> {
+    pub schema_version: u32,
+    pub command: String,
+    pub data: T,
+}
+
+impl<T: Serialize> JsonEnvelope<T> {
+    pub fn new(command: impl Into<String>, data: T) -> Self {
+        Self {
+            schema_version: JSON_SCHEMA_VERSION,
+            command: command.into(),
+            data,
+        }
+    }
+}
+
+/// Print `data` for `command` wrapped in a [`JsonEnvelope`].
+pub fn print_json<T: Serialize>(command: &str, data: T) -> Result<()> {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&JsonEnvelope::new(command, data))?
+    );
+    Ok(())
+}
+
+/// Like [`print_json`], but on a single line for streaming output such as `dash --watch`.
+pub fn print_json_line<T: Serialize>(command: &str, data: T) -> Result<()> {
+    println!(
+        "{}",
+        serde_json::to_string(&JsonEnvelope::new(command, data))?
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn envelope_wraps_payload_with_version_and_command() -> Result<()> {
+        let payload = serde_json::json!({ "branch": "feature", "created": true });
+        let value = serde_json::to_value(JsonEnvelope::new("work", &payload))?;
+
+        assert_eq!(value["schema_version"], JSON_SCHEMA_VERSION);
+        assert_eq!(value["command"], "work");
+        assert_eq!(value["data"], payload);
+        assert_eq!(value.as_object().map(|object| object.len()), Some(3));
+        Ok(())
+    }
+}
@@ -1,7 +1,16 @@
+pub mod ai;
+pub mod console;
+mod forge;
+pub mod json;
 pub mod steps;
 pub mod update;
+pub mod watch;
 pub mod workflows;
 
+pub use ai::*;
+pub use console::*;
+pub use json::*;
 pub use steps::*;
 pub use update::*;
+pub use watch::*;
 pub use workflows::*;
@@ -0,0 +1,107 @@
+use anyhow::{Context, Result};
+use sage_config::ConfigManager;
+
+/// Longest slug kept from a summary, so generated names stay readable.
+const MAX_SLUG_LEN: usize = 40;
+
+/// Build a branch name for an issue from `general.branch_template`, letting the user adjust it.
+///
+/// The slug comes from `summary`, or from the AI when `ai` is set.
+pub async fn issue_branch_name(
+    kind: &str,
+    issue: &str,
+    summary: Option<&str>,
+    ai: bool,
+    console: &sage_fmt::Console,
+) -> Result<String> {
+    let config_manager = ConfigManager::load().context("Failed to load configuration")?;
+    let template = &config_manager.get().general.branch_template;
+
+    let slug = match summary {
+        Some(summary) if ai => slugify(&sage_ai::branch_slug(summary).await?),
+        Some(summary) => slugify(summary),
+        None => String::new(),
+    };
+
+    let proposed = render_branch_template(template, kind, issue, &slug);
+    sage_git::validate_branch_name(&proposed)?;
+
+    let name = console.input("Branch name", &proposed)?;
+    sage_git::validate_branch_name(&name)?;
+    Ok(name)
+}
+
+/// Lowercase, hyphen-separated and trimmed: `"Fix  Login: timeout!"` becomes `fix-login-timeout`.
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for ch in text.trim().chars() {
+        if ch.is_ascii_alphanumeric() {
+            slug.push(ch.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    if slug.len() > MAX_SLUG_LEN {
+        slug.truncate(MAX_SLUG_LEN);
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Fill in `{type}`, `{issue}` and `{slug}`, dropping separators left dangling by empty values.
+pub fn render_branch_template(template: &str, kind: &str, issue: &str, slug: &str) -> String {
+    let rendered = template
+        .replace("{type}", kind.trim())
+        .replace("{issue}", issue.trim())
+        .replace("{slug}", slug);
+
+    rendered
+        .split('/')
+        .map(|segment| segment.trim_matches(|ch| ch == '-' || ch == '_'))
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_lowercases_hyphenates_and_trims() {
+        assert_eq!(slugify("Fix Login Timeout"), "fix-login-timeout");
+        assert_eq!(slugify("  --Add: OAuth2 (beta)!  "), "add-oauth2-beta");
+        assert_eq!(
+            slugify("multiple   spaces__and--dashes"),
+            "multiple-spaces-and-dashes"
+        );
+        assert_eq!(slugify("!!!"), "");
+        assert!(slugify(&"word ".repeat(20)).len() <= MAX_SLUG_LEN);
+        assert!(!slugify(&"word ".repeat(20)).ends_with('-'));
+    }
+
+    #[test]
+    fn template_placeholders_are_substituted() {
+        let template = "{type}/{issue}-{slug}";
+        assert_eq!(
+            render_branch_template(template, "feat", "123", "login-timeout"),
+            "feat/123-login-timeout"
+        );
+        assert_eq!(
+            render_branch_template("{issue}/{slug}", "fix", "ABC-9", "crash"),
+            "ABC-9/crash"
+        );
+    }
+
+    #[test]
+    fn empty_values_leave_no_dangling_separators() {
+        assert_eq!(
+            render_branch_template("{type}/{issue}-{slug}", "feat", "123", ""),
+            "feat/123"
+        );
+        assert_eq!(
+            render_branch_template("{type}/{issue}-{slug}", "", "123", "login"),
+            "123-login"
+        );
+    }
+}
@@ -1,5 +1,6 @@
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use sage_config::ConfigManager;
+use sage_git::DiffStat;
 
 pub async fn commit_message(
     repo: &sage_git::Repo,
@@ -30,3 +31,170 @@ pub async fn commit_message(
 
     Ok(String::new())
 }
+
+/// Opens the editor pre-filled with the `save.template` config and the staged diffstat.
+///
+/// Lines starting with `#` are stripped; an empty result aborts the commit.
+pub fn edit_commit_message(repo: &sage_git::Repo, console: &sage_fmt::Console) -> Result<String> {
+    if !console.is_interactive() {
+        bail!("No commit message provided. Use --message or --ai.");
+    }
+
+    let config_manager = ConfigManager::load().context("Failed to load configuration")?;
+    let template = &config_manager.get().save.template;
+    let branch = repo.get_current_branch()?;
+    let stat = repo.diff_stat()?;
+
+    let initial = editor_message(template, &branch, &stat);
+    let edited = sage_fmt::TextEditor::new().edit(&initial)?;
+
+    let message = strip_comments(&edited);
+    if message.is_empty() {
+        bail!("Aborting commit due to empty commit message");
+    }
+
+    console.message(sage_fmt::MessageType::Info, "Using message from editor")?;
+    Ok(message)
+}
+
+/// Opens the editor on an already chosen message (from `--ai` or `--message`) so it can be
+/// reviewed before committing; the staged diffstat is appended as comments.
+pub fn review_commit_message(
+    repo: &sage_git::Repo,
+    console: &sage_fmt::Console,
+    message: &str,
+) -> Result<String> {
+    if !console.is_interactive() {
+        bail!("--edit needs an interactive terminal to open the editor");
+    }
+
+    let branch = repo.get_current_branch()?;
+    let stat = repo.diff_stat()?;
+    let message = review_with(&sage_fmt::TextEditor::new(), message, &branch, &stat)?;
+
+    console.message(sage_fmt::MessageType::Info, "Using message from editor")?;
+    Ok(message)
+}
+
+fn review_with(
+    editor: &sage_fmt::TextEditor,
+    message: &str,
+    branch: &str,
+    stat: &DiffStat,
+) -> Result<String> {
+    let mut initial = message.trim_end().to_string();
+    initial.push_str("\n\n");
+    initial.push_str(&comment_block(branch, stat));
+
+    let message = strip_comments(&editor.edit(&initial)?);
+    if message.is_empty() {
+        bail!("Aborting commit due to empty commit message");
+    }
+    Ok(message)
+}
+
+pub fn render_template(template: &str, branch: &str, diffstat: &str) -> String {
+    template
+        .replace("{branch}", branch)
+        .replace("{diffstat}", diffstat)
+}
+
+/// Drops `#` comment lines and surrounding blank lines, mirroring `git commit`.
+pub fn strip_comments(text: &str) -> String {
+    text.lines()
+        .filter(|line| !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+fn editor_message(template: &str, branch: &str, stat: &DiffStat) -> String {
+    let mut message = render_template(template, branch, &stat.summary());
+    message.push_str("\n\n");
+    message.push_str(&comment_block(branch, stat));
+    message
+}
+
+fn comment_block(branch: &str, stat: &DiffStat) -> String {
+    let mut message = String::new();
+    message.push_str("# Please enter the commit message for your changes. Lines starting\n");
+    message.push_str("# with '#' will be ignored, and an empty message aborts the commit.\n");
+    message.push_str("#\n");
+    message.push_str(&format!("# On branch {branch}\n"));
+    message.push_str(&format!("# {}\n", stat.summary()));
+    for file in &stat.files {
+        message.push_str(&format!(
+            "#   {} | +{} -{}\n",
+            file.path, file.additions, file.deletions
+        ));
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sage_git::FileStat;
+
+    #[test]
+    fn template_placeholders_are_substituted() {
+        let rendered = render_template(
+            "feat({branch}): \n\n{diffstat}",
+            "auth/login",
+            "1 file changed, +2 -0",
+        );
+        assert_eq!(rendered, "feat(auth/login): \n\n1 file changed, +2 -0");
+    }
+
+    #[test]
+    fn comment_lines_are_stripped() {
+        let edited =
+            "\nfix: handle empty input\n# a comment\n\nbody line\n#   src/lib.rs | +1 -0\n";
+        assert_eq!(
+            strip_comments(edited),
+            "fix: handle empty input\n\nbody line"
+        );
+    }
+
+    #[test]
+    fn comment_only_message_is_empty() {
+        let stat = DiffStat {
+            files: vec![FileStat {
+                path: "src/lib.rs".to_owned(),
+                additions: 3,
+                deletions: 1,
+            }],
+        };
+        let initial = editor_message("", "main", &stat);
+
+        assert!(initial.contains("#   src/lib.rs | +3 -1"));
+        assert!(initial.contains("# 1 file changed, +3 -1"));
+        assert!(strip_comments(&initial).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn review_seeds_editor_with_message() {
+        let stat = DiffStat {
+            files: vec![FileStat {
+                path: "src/lib.rs".to_owned(),
+                additions: 1,
+                deletions: 0,
+            }],
+        };
+        // `true` leaves the seeded file untouched, so the result is the seed minus comments.
+        let editor = sage_fmt::TextEditor::new().editor("true");
+        let message = review_with(&editor, "feat: generated\n\nbody\n", "main", &stat).unwrap();
+        assert_eq!(message, "feat: generated\n\nbody");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn review_with_empty_buffer_aborts() {
+        let stat = DiffStat { files: Vec::new() };
+        let editor = sage_fmt::TextEditor::new().editor("truncate -s 0");
+        let err = review_with(&editor, "feat: generated", "main", &stat).unwrap_err();
+        assert!(err.to_string().contains("empty commit message"));
+    }
+}
@@ -45,13 +45,12 @@ pub fn fetch_if_stale(repo: &sage_git::Repo, console: &sage_fmt::Console) -> Res
         return Ok(false);
     }
 
-    let progress = console.progress("Fetching remote");
+    let spinner = console.spinner("Fetching remote");
     repo.fetch()?;
 
     fs::write(&stamp_path, b"fetched")
         .with_context(|| format!("failed to update fetch stamp at {}", stamp_path.display()))?;
-    progress.done();
-    console.message(sage_fmt::MessageType::Success, "Fetched remote")?;
+    spinner.success("Fetched remote");
 
     Ok(true)
 }
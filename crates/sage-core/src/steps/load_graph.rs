@@ -0,0 +1,18 @@
+use anyhow::Result;
+use sage_fmt::MessageType;
+use sage_graph::SageGraph;
+
+/// Load the branch graph, warning instead of failing when the stored file is corrupt.
+pub fn load_graph(repo: &sage_git::Repo, console: &sage_fmt::Console) -> Result<SageGraph> {
+    let (graph, backup) = SageGraph::load_or_recover(repo)?;
+    if let Some(backup) = backup {
+        console.message(
+            MessageType::Warning,
+            &format!(
+                "Branch tracking data was unreadable and has been reset; the old file is at {}",
+                backup.display()
+            ),
+        )?;
+    }
+    Ok(graph)
+}
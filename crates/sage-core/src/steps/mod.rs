@@ -1,13 +1,17 @@
 // General
+pub mod branch_name;
 pub mod fetch;
 pub mod fuzzy_match_branch;
+pub mod load_graph;
 
 // Commit related
 pub mod commit_message;
 pub mod stage_changes;
 
 // Exports
+pub use branch_name::*;
 pub use commit_message::*;
 pub use fetch::*;
 pub use fuzzy_match_branch::*;
+pub use load_graph::*;
 pub use stage_changes::*;
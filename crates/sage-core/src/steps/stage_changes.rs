@@ -1,10 +1,12 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
 use sage_fmt::MessageType;
+use sage_git::HunkSelection;
 
 pub fn stage_changes(
     repo: &sage_git::Repo,
     console: &sage_fmt::Console,
     paths: Option<Vec<String>>,
+    exclude: &[String],
 ) -> Result<()> {
     if let Some(stage_paths) = paths {
         repo.stage_paths(stage_paths)?;
@@ -29,13 +31,94 @@ pub fn stage_changes(
         return Ok(());
     }
 
-    repo.stage_all()?;
+    if exclude.is_empty() {
+        repo.stage_all()?;
+        console.message(MessageType::Success, "Staged all changes")?;
+        return Ok(());
+    }
 
-    console.message(MessageType::Success, "Staged all changes")?;
+    let excluded = repo.stage_all_except(exclude)?;
+    console.message(
+        MessageType::Success,
+        &format!("Staged all changes except {} excluded", excluded.len()),
+    )?;
 
     Ok(())
 }
 
+/// Walk the unstaged hunks like `git add -p`, staging the ones the user accepts.
+///
+/// Returns the number of hunks staged.
+pub fn stage_hunks_interactively(
+    repo: &sage_git::Repo,
+    console: &sage_fmt::Console,
+) -> Result<usize> {
+    if !console.is_interactive() {
+        bail!("Interactive staging needs an interactive terminal");
+    }
+
+    let files = repo.unstaged_hunks()?;
+    if files.is_empty() {
+        console.message(MessageType::Info, "No unstaged changes to tracked files")?;
+        return Ok(0);
+    }
+
+    let mut selections = Vec::new();
+    for file in &files {
+        let count = file.hunks.len();
+        for (index, hunk) in file.hunks.iter().enumerate() {
+            console.message(
+                MessageType::Info,
+                &format!(
+                    "{} ({}/{count}, +{} -{})",
+                    file.path,
+                    index + 1,
+                    hunk.additions(),
+                    hunk.deletions()
+                ),
+            )?;
+            console.diff(std::slice::from_ref(&hunk.header));
+            console.diff(&hunk.lines);
+
+            if console.confirm("Stage this hunk?", false)? {
+                selections.push(HunkSelection {
+                    path: file.path.clone(),
+                    hunk: index,
+                });
+            }
+        }
+    }
+
+    repo.stage_hunks(&selections)?;
+    if !selections.is_empty() {
+        console.message(
+            MessageType::Success,
+            &format!(
+                "Staged {} of {} hunks",
+                selections.len(),
+                total_hunks(&files)
+            ),
+        )?;
+    }
+    Ok(selections.len())
+}
+
+fn total_hunks(files: &[sage_git::FileDiff]) -> usize {
+    files.iter().map(|file| file.hunks.len()).sum()
+}
+
+/// Print a one-line diffstat of what is about to be committed.
+pub fn summarize_staged(
+    repo: &sage_git::Repo,
+    console: &sage_fmt::Console,
+) -> Result<sage_git::DiffStat> {
+    let stat = repo.diff_stat()?;
+    if !stat.is_empty() {
+        console.message(MessageType::Info, &stat.summary())?;
+    }
+    Ok(stat)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -53,7 +136,7 @@ mod tests {
         repo.write("file.txt", "line 1\nline 2 staged\nline 3 unstaged\n")?;
 
         let console = sage_fmt::Console::new();
-        stage_changes(repo.repo(), &console, None)?;
+        stage_changes(repo.repo(), &console, None, &[])?;
 
         let staged = repo.staged_changes()?;
         assert_eq!(staged, vec!["file.txt"]);
@@ -63,4 +146,44 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn summarizes_changes_across_files() -> anyhow::Result<()> {
+        let repo = TestRepo::builder().with_initial_commit().build()?;
+
+        repo.write("keep.txt", "a\nb\nc\n")?;
+        repo.write("gone.txt", "x\ny\n")?;
+        repo.commit_all("add files")?;
+
+        repo.write("keep.txt", "a\nB\nc\nd\n")?;
+        repo.write("new.txt", "1\n2\n3\n")?;
+        std::fs::remove_file(repo.path().join("gone.txt"))?;
+
+        let console = sage_fmt::Console::new();
+        stage_changes(repo.repo(), &console, None, &[])?;
+        let stat = summarize_staged(repo.repo(), &console)?;
+
+        assert_eq!(stat.files.len(), 3);
+        assert_eq!(stat.additions(), 5);
+        assert_eq!(stat.deletions(), 3);
+        assert_eq!(stat.summary(), "3 files changed, +5 -3");
+
+        Ok(())
+    }
+
+    #[test]
+    fn excluded_paths_stay_unstaged() -> anyhow::Result<()> {
+        let repo = TestRepo::builder().with_initial_commit().build()?;
+
+        repo.write("src/main.rs", "fn main() {}\n")?;
+        repo.write("notes/todo.md", "- ship it\n")?;
+
+        let console = sage_fmt::Console::new();
+        stage_changes(repo.repo(), &console, None, &["notes/".to_string()])?;
+
+        assert_eq!(repo.staged_changes()?, vec!["src/main.rs"]);
+        assert_eq!(repo.untracked_files()?, vec!["notes/todo.md"]);
+
+        Ok(())
+    }
 }
@@ -6,15 +6,19 @@ use semver::Version;
 use serde::{Deserialize, Serialize};
 use std::{
     fs,
+    future::Future,
     io::{Error, ErrorKind},
     path::PathBuf,
-    time::Duration,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
-use tokio::time::timeout;
+use tokio::time::{sleep, timeout};
 
 const CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60); // 24 hours;
 const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
-const UPDATE_CHECK_TIMEOUT: Duration = Duration::from_millis(500);
+const UPDATE_CHECK_ATTEMPTS: usize = 3;
+const UPDATE_CHECK_ATTEMPT_TIMEOUT: Duration = Duration::from_millis(500);
+const UPDATE_CHECK_BASE_BACKOFF: Duration = Duration::from_millis(100);
+const NO_UPDATE_CHECK_ENV: &str = "SAGE_NO_UPDATE_CHECK";
 
 #[derive(Debug, Serialize, Deserialize, Default)]
 struct UpdateCheck {
@@ -61,11 +65,110 @@ fn update_check_record(latest: Option<&str>) -> Result<()> {
 }
 
 fn should_check_for_updates() -> Result<bool> {
+    if update_check_disabled(|var| std::env::var(var).ok()) {
+        return Ok(false);
+    }
+
+    let enabled = sage_config::ConfigManager::load()
+        .map(|manager| manager.get().general.update_check)
+        .unwrap_or(true);
+    if !enabled {
+        return Ok(false);
+    }
+
     let check = load_update_check()?;
     let now = Utc::now().timestamp();
     Ok(now - check.last_check >= CHECK_INTERVAL.as_secs() as i64)
 }
 
+/// `SAGE_NO_UPDATE_CHECK` and CI environments skip the check regardless of config.
+fn update_check_disabled(env: impl Fn(&str) -> Option<String>) -> bool {
+    if let Some(value) = env(NO_UPDATE_CHECK_ENV) {
+        let value = value.trim();
+        if !value.is_empty() && value != "0" && !value.eq_ignore_ascii_case("false") {
+            return true;
+        }
+    }
+
+    sage_fmt::CI_ENV_VARS.iter().any(|var| env(var).is_some())
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RetryPolicy {
+    attempts: usize,
+    attempt_timeout: Duration,
+    base_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: UPDATE_CHECK_ATTEMPTS,
+            attempt_timeout: UPDATE_CHECK_ATTEMPT_TIMEOUT,
+            base_backoff: UPDATE_CHECK_BASE_BACKOFF,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Exponential backoff before the given (1-based) retry, plus up to 50% jitter.
+    fn backoff(&self, retry: usize) -> Duration {
+        let exponent = u32::try_from(retry.saturating_sub(1)).unwrap_or(u32::MAX);
+        let delay = self
+            .base_backoff
+            .saturating_mul(2u32.saturating_pow(exponent));
+        delay + jitter(delay / 2)
+    }
+}
+
+/// Cheap jitter source; we only need to avoid synchronized retries, not randomness.
+fn jitter(max: Duration) -> Duration {
+    let max_nanos = max.as_nanos() as u64;
+    if max_nanos == 0 {
+        return Duration::ZERO;
+    }
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos() as u64)
+        .unwrap_or_default();
+    Duration::from_nanos(seed % max_nanos)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum FetchOutcome {
+    Latest(Option<String>),
+    TimedOut,
+}
+
+/// Runs `fetch` up to `policy.attempts` times, each bounded by its own timeout.
+///
+/// The first successful attempt wins. If every attempt fails, the last error is
+/// returned, unless that attempt timed out, in which case the check is skipped.
+async fn fetch_with_retry<F, Fut>(policy: RetryPolicy, mut fetch: F) -> Result<FetchOutcome>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Option<String>>>,
+{
+    let mut last_error = None;
+
+    for attempt in 0..policy.attempts {
+        if attempt > 0 {
+            sleep(policy.backoff(attempt)).await;
+        }
+
+        match timeout(policy.attempt_timeout, fetch()).await {
+            Ok(Ok(version)) => return Ok(FetchOutcome::Latest(version)),
+            Ok(Err(err)) => last_error = Some(err),
+            Err(_) => last_error = None,
+        }
+    }
+
+    match last_error {
+        Some(err) => Err(err),
+        None => Ok(FetchOutcome::TimedOut),
+    }
+}
+
 async fn get_latest_version() -> Result<Option<String>> {
     let octo = Octocrab::builder().build()?;
 
@@ -258,6 +361,11 @@ fn show_update_notification(current: &str, latest: &str) {
         }
     }
 
+    println!(
+        "{} {}",
+        "Turn off update checks:".dimmed(),
+        "sg config --key general.auto_update --value false".cyan()
+    );
     println!(
         "{}",
         "────────────────────────────────────────────────────────".dimmed()
@@ -269,15 +377,15 @@ pub async fn check_for_updates() -> Result<()> {
         return Ok(());
     }
 
-    let latest_version = match timeout(UPDATE_CHECK_TIMEOUT, get_latest_version()).await {
-        Ok(Ok(version)) => version,
-        Ok(Err(err)) => {
+    let latest_version = match fetch_with_retry(RetryPolicy::default(), get_latest_version).await {
+        Ok(FetchOutcome::Latest(version)) => version,
+        Ok(FetchOutcome::TimedOut) => {
             update_check_record(None)?;
-            return Err(err);
+            return Ok(());
         }
-        Err(_) => {
+        Err(err) => {
             update_check_record(None)?;
-            return Ok(());
+            return Err(err);
         }
     };
 
@@ -295,3 +403,110 @@ pub async fn check_for_updates() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn env_from(vars: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+        move |name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    #[test]
+    fn env_override_disables_update_check() {
+        assert!(update_check_disabled(env_from(&[(
+            "SAGE_NO_UPDATE_CHECK",
+            "1"
+        )])));
+        assert!(update_check_disabled(env_from(&[(
+            "SAGE_NO_UPDATE_CHECK",
+            "true"
+        )])));
+        assert!(!update_check_disabled(env_from(&[(
+            "SAGE_NO_UPDATE_CHECK",
+            "0"
+        )])));
+        assert!(!update_check_disabled(env_from(&[(
+            "SAGE_NO_UPDATE_CHECK",
+            ""
+        )])));
+        assert!(!update_check_disabled(env_from(&[])));
+    }
+
+    #[test]
+    fn ci_environment_skips_update_check() {
+        assert!(update_check_disabled(env_from(&[("CI", "true")])));
+        assert!(update_check_disabled(env_from(&[(
+            "GITHUB_ACTIONS",
+            "true"
+        )])));
+        assert!(!update_check_disabled(env_from(&[("HOME", "/home/sage")])));
+    }
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy {
+            attempts: 3,
+            attempt_timeout: Duration::from_millis(50),
+            base_backoff: Duration::from_millis(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn later_attempt_wins_after_failure() {
+        let calls = AtomicUsize::new(0);
+
+        let outcome = fetch_with_retry(fast_policy(), || {
+            let attempt = calls.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if attempt == 0 {
+                    Err(anyhow!("connection reset"))
+                } else {
+                    Ok(Some("2.0.0".to_string()))
+                }
+            }
+        })
+        .await
+        .expect("retry succeeds");
+
+        assert_eq!(outcome, FetchOutcome::Latest(Some("2.0.0".to_string())));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn exhausted_timeouts_are_silent() {
+        let calls = AtomicUsize::new(0);
+
+        let outcome = fetch_with_retry(fast_policy(), || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async {
+                sleep(Duration::from_secs(1)).await;
+                Ok(None)
+            }
+        })
+        .await
+        .expect("timeouts are not errors");
+
+        assert_eq!(outcome, FetchOutcome::TimedOut);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_with_bounded_jitter() {
+        let policy = RetryPolicy {
+            attempts: 3,
+            attempt_timeout: Duration::from_millis(50),
+            base_backoff: Duration::from_millis(100),
+        };
+
+        let first = policy.backoff(1);
+        let second = policy.backoff(2);
+        assert!(first >= Duration::from_millis(100) && first < Duration::from_millis(150));
+        assert!(second >= Duration::from_millis(200) && second < Duration::from_millis(300));
+    }
+}
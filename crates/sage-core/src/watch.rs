@@ -0,0 +1,90 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Polls a repository's `HEAD` and index for modifications.
+///
+/// Cheap enough to run in a loop: each poll is one `stat` per watched file.
+#[derive(Debug)]
+pub struct RepoWatcher {
+    files: Vec<PathBuf>,
+    stamps: Vec<Option<SystemTime>>,
+    interval: Duration,
+}
+
+impl RepoWatcher {
+    pub fn new(repo: &sage_git::Repo) -> Self {
+        let git_dir = repo.git_dir();
+        let files = vec![git_dir.join("HEAD"), git_dir.join("index")];
+        let stamps = files.iter().map(|file| modified(file)).collect();
+
+        Self {
+            files,
+            stamps,
+            interval: DEFAULT_INTERVAL,
+        }
+    }
+
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Files whose modification time changed since the last poll.
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (file, stamp) in self.files.iter().zip(self.stamps.iter_mut()) {
+            let current = modified(file);
+            if current != *stamp {
+                *stamp = current;
+                changed.push(file.clone());
+            }
+        }
+        changed
+    }
+
+    /// Block until something changes, returning the changed files.
+    pub fn wait(&mut self) -> Vec<PathBuf> {
+        loop {
+            let changed = self.poll();
+            if !changed.is_empty() {
+                return changed;
+            }
+            thread::sleep(self.interval);
+        }
+    }
+}
+
+fn modified(file: &Path) -> Option<SystemTime> {
+    fs::metadata(file).and_then(|meta| meta.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sage_git::testing::TestRepo;
+
+    #[test]
+    fn touching_head_is_reported() -> anyhow::Result<()> {
+        let repo = TestRepo::builder().with_initial_commit().build()?;
+        let mut watcher = RepoWatcher::new(&repo);
+        assert!(watcher.poll().is_empty());
+
+        let head = repo.git_dir().join("HEAD");
+        let later = SystemTime::now() + Duration::from_secs(5);
+        fs::File::options()
+            .append(true)
+            .open(&head)?
+            .set_modified(later)?;
+
+        assert_eq!(watcher.poll(), vec![head]);
+        assert!(watcher.poll().is_empty());
+
+        Ok(())
+    }
+}
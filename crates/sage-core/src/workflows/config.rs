@@ -1,10 +1,16 @@
-use anyhow::{Result, anyhow};
+use std::{fs, io, path::Path};
+
+use anyhow::{Context, Result, anyhow, bail};
 use sage_config::{ConfigEntry, ConfigManager};
-use sage_fmt::MessageType;
+use sage_fmt::{MessageType, TextEditor};
+
+use crate::print_json;
 
 pub fn config(
     key: Option<String>,
     value: Option<String>,
+    reveal: bool,
+    json: bool,
     console: &sage_fmt::Console,
 ) -> Result<()> {
     match (key, value) {
@@ -21,13 +27,11 @@ pub fn config(
         }
         (Some(key), None) => {
             let manager = ConfigManager::load()?;
-            match sage_config::get_entry(manager.get(), &key)? {
+            match manager.get_entry(&key)? {
+                Some(entry) if json => print_json("config", entry_json(&entry, reveal)?)?,
                 Some(entry) => {
-                    if let Some(raw) = entry.raw_value {
-                        println!("{}: {}", key, raw);
-                    } else {
-                        println!("{}: <unset>", key);
-                    }
+                    let value = entry_value(&entry, reveal).unwrap_or("<unset>");
+                    println!("{}: {}", key, value);
                 }
                 None => {
                     return Err(anyhow!("Unknown config key: {}", key));
@@ -39,13 +43,17 @@ pub fn config(
         }
         (None, None) => {
             let manager = ConfigManager::load()?;
-            let entries = sage_config::list_entries(manager.get())?;
-            for ConfigEntry {
-                key, display_value, ..
-            } in entries
-            {
-                if let Some(display) = display_value {
-                    println!("{key}: {display}");
+            let entries = manager.list_entries()?;
+            if json {
+                let entries = entries
+                    .iter()
+                    .map(|entry| entry_json(entry, reveal))
+                    .collect::<Result<Vec<_>>>()?;
+                return print_json("config", entries);
+            }
+            for entry in entries {
+                if let Some(value) = entry_value(&entry, reveal) {
+                    println!("{}: {}", entry.key, value);
                 }
             }
         }
@@ -53,3 +61,200 @@ pub fn config(
 
     Ok(())
 }
+
+/// Write the effective config as TOML to `output`, or stdout when `None`.
+pub fn config_export(
+    reveal: bool,
+    output: Option<&Path>,
+    console: &sage_fmt::Console,
+) -> Result<()> {
+    let manager = ConfigManager::load()?;
+    let toml = sage_config::export_toml(manager.get(), reveal)?;
+
+    match output {
+        Some(path) => {
+            fs::write(path, toml).with_context(|| format!("Failed to write {}", path.display()))?;
+            console.message(
+                MessageType::Success,
+                &format!("Exported config to {}", path.display()),
+            )?;
+        }
+        None => print!("{toml}"),
+    }
+    Ok(())
+}
+
+/// Merge the settings from a TOML file into the global config.
+pub fn config_import(path: &Path, console: &sage_fmt::Console) -> Result<()> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let mut manager = ConfigManager::load()?;
+    let applied = sage_config::import_toml(manager.get_mut(), &content, path)?;
+    manager.save()?;
+
+    console.message(
+        MessageType::Success,
+        &format!(
+            "Imported {} settings from {}",
+            applied.len(),
+            path.display()
+        ),
+    )?;
+    Ok(())
+}
+
+/// Open the global config in the editor, offering to re-open it until it parses.
+///
+/// The file on disk is only replaced by a valid edit, so giving up keeps the old config.
+pub fn config_edit(console: &sage_fmt::Console) -> Result<()> {
+    if !console.is_interactive() {
+        bail!("Editing the config needs an interactive terminal");
+    }
+
+    let manager = ConfigManager::load()?;
+    let path = manager.global_path();
+    let original = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(error) => {
+            return Err(error).with_context(|| format!("Failed to read {}", path.display()));
+        }
+    };
+
+    let editor = TextEditor::new().suffix(".toml");
+    let edited = edit_until_valid(&editor, &original, path, |error| {
+        console.message(MessageType::Error, &error.to_string())?;
+        console.confirm("Re-open the editor to fix it?", true)
+    })?;
+
+    match edited {
+        Some(content) if content == original => {
+            console.message(MessageType::Info, "Config unchanged")?
+        }
+        Some(content) => {
+            fs::write(path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            console.message(MessageType::Success, &format!("Saved {}", path.display()))?;
+        }
+        None => console.message(MessageType::Warning, "Kept the previous config")?,
+    }
+    Ok(())
+}
+
+/// Edits `original` until it parses as a config, asking `retry` after each failure.
+///
+/// Returns `None` when `retry` gives up. Each retry starts from the broken text so
+/// fixes aren't lost.
+fn edit_until_valid(
+    editor: &TextEditor,
+    original: &str,
+    path: &Path,
+    mut retry: impl FnMut(&sage_config::ConfigError) -> Result<bool>,
+) -> Result<Option<String>> {
+    let mut content = original.to_string();
+    loop {
+        content = editor.edit(&content)?;
+        match sage_config::parse_toml(&content, path) {
+            Ok(_) => return Ok(Some(content)),
+            Err(error) => {
+                if !retry(&error)? {
+                    return Ok(None);
+                }
+            }
+        }
+    }
+}
+
+/// Secrets are only printed in the clear when explicitly requested.
+fn entry_value(entry: &ConfigEntry, reveal: bool) -> Option<&str> {
+    if reveal && entry.raw_value.is_some() {
+        entry.raw_value.as_deref()
+    } else {
+        entry.display_value.as_deref()
+    }
+}
+
+/// `{key, value, source}` with the value kept as its TOML type.
+fn entry_json(entry: &ConfigEntry, reveal: bool) -> Result<serde_json::Value> {
+    let value = if reveal && entry.is_secret {
+        entry.raw_value.clone().map(serde_json::Value::String)
+    } else {
+        entry.value.as_ref().map(serde_json::to_value).transpose()?
+    };
+
+    Ok(serde_json::json!({
+        "key": entry.key,
+        "value": value,
+        "source": entry.source,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sage_config::{SageConfig, SecretString};
+
+    #[test]
+    fn json_entry_is_typed_and_redacts_secrets() -> Result<()> {
+        let mut config = SageConfig::default();
+        config.ai.api_key = Some(SecretString::new("sk-test-123"));
+
+        let entry = sage_config::get_entry(&config, "ai.max_tokens")?.expect("entry");
+        let json = entry_json(&entry, false)?;
+        assert_eq!(json["key"], "ai.max_tokens");
+        assert!(json["value"].is_number());
+        assert_eq!(json["source"], "default");
+
+        let secret = sage_config::get_entry(&config, "ai.api_key")?.expect("entry");
+        let redacted = entry_json(&secret, false)?;
+        assert!(!redacted.to_string().contains("sk-test-123"));
+        assert_eq!(
+            redacted.as_object().map(|object| object.len()),
+            Some(3),
+            "unexpected shape: {redacted}"
+        );
+
+        let revealed = entry_json(&secret, true)?;
+        assert_eq!(revealed["value"], "sk-test-123");
+        Ok(())
+    }
+
+    /// An editor that overwrites the file with `content`.
+    #[cfg(unix)]
+    fn scripted_editor(dir: &Path, content: &str) -> Result<TextEditor> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let script = dir.join("editor.sh");
+        fs::write(
+            &script,
+            format!("#!/bin/sh\nprintf '%s' '{content}' > \"$1\"\n"),
+        )?;
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755))?;
+        Ok(TextEditor::new().editor(script.display().to_string()))
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn invalid_edit_keeps_the_original() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("config.toml");
+        let original = "[ai]\nmodel = \"gpt-4\"\n";
+        let editor = scripted_editor(dir.path(), "[ai\nmodel = ")?;
+
+        let mut attempts = 0;
+        let edited = edit_until_valid(&editor, original, &path, |error| {
+            assert!(error.to_string().contains("Failed to parse config file"));
+            attempts += 1;
+            Ok(attempts < 2)
+        })?;
+
+        assert_eq!(edited, None);
+        assert_eq!(attempts, 2);
+
+        let editor = scripted_editor(dir.path(), "[ai]\nmodel = \"gpt-5\"\n")?;
+        let edited = edit_until_valid(&editor, original, &path, |_| Ok(false))?;
+        assert_eq!(edited.as_deref(), Some("[ai]\nmodel = \"gpt-5\"\n"));
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::BTreeSet;
+
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+
+use sage_graph::SageGraph;
+
+use super::stack_status::{BranchSyncState, build_stack_status, print_state};
+use crate::{RepoWatcher, load_graph, print_json, print_json_line};
+
+const RECENT_COMMITS: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DashSnapshot {
+    pub branch: String,
+    pub stack: Option<String>,
+    /// The current branch's stack, or just the current branch outside a stack.
+    pub branches: Vec<BranchSyncState>,
+    /// Files with staged, unstaged or untracked changes.
+    pub dirty_files: usize,
+    /// Newest first.
+    pub recent_commits: Vec<DashCommit>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DashCommit {
+    pub hash: String,
+    pub summary: String,
+    pub author: String,
+    pub date: String,
+}
+
+pub fn dash(watch: bool, json: bool, console: &sage_fmt::Console) -> Result<()> {
+    let repo = sage_git::Repo::open()?;
+
+    if !watch {
+        let snapshot = build_dash_snapshot(&repo, &load_graph(&repo, console)?)?;
+        if json {
+            print_json("dash", &snapshot)?;
+        } else {
+            console.header("dash")?;
+            print_snapshot(&snapshot);
+        }
+        return Ok(());
+    }
+
+    let mut watcher = RepoWatcher::new(&repo);
+    loop {
+        let snapshot = build_dash_snapshot(&repo, &load_graph(&repo, console)?)?;
+        if json {
+            print_json_line("dash", &snapshot)?;
+        } else {
+            // Clear the screen and move the cursor home before redrawing.
+            print!("\x1b[2J\x1b[H");
+            console.header("dash")?;
+            print_snapshot(&snapshot);
+            println!();
+            println!("{}", "Watching for changes, Ctrl-C to exit".dimmed());
+        }
+        watcher.wait();
+    }
+}
+
+pub fn build_dash_snapshot(repo: &sage_git::Repo, graph: &SageGraph) -> Result<DashSnapshot> {
+    let branch = repo.get_current_branch()?;
+
+    let dirty: BTreeSet<String> = repo
+        .staged_changes()?
+        .into_iter()
+        .chain(repo.unstaged_files()?)
+        .chain(repo.untracked_files()?)
+        .collect();
+
+    let recent_commits = repo
+        .get_commits(Some(RECENT_COMMITS))?
+        .into_iter()
+        .map(|commit| DashCommit {
+            hash: commit.hash,
+            summary: commit
+                .message
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            author: commit.author,
+            date: commit.date,
+        })
+        .collect();
+
+    Ok(DashSnapshot {
+        stack: graph.stack_name_for_branch(&branch).cloned(),
+        branches: build_stack_status(repo, graph, &branch)?,
+        dirty_files: dirty.len(),
+        recent_commits,
+        branch,
+    })
+}
+
+fn print_snapshot(snapshot: &DashSnapshot) {
+    match &snapshot.stack {
+        Some(stack) => println!("{} {}", "Stack:".bright_green().bold(), stack.yellow()),
+        None => println!(
+            "{} {}",
+            "Branch:".bright_green().bold(),
+            snapshot.branch.yellow()
+        ),
+    }
+    for state in &snapshot.branches {
+        print_state(state);
+    }
+
+    println!();
+    if snapshot.dirty_files == 0 {
+        println!("{}", "Working tree clean".dimmed());
+    } else {
+        let noun = if snapshot.dirty_files == 1 {
+            "file"
+        } else {
+            "files"
+        };
+        println!(
+            "{}",
+            format!("{} changed {noun}", snapshot.dirty_files).bright_yellow()
+        );
+    }
+
+    println!();
+    println!("{}", "Recent commits:".bright_green().bold());
+    for commit in &snapshot.recent_commits {
+        println!(
+            "  {} {} {}",
+            commit.hash.yellow(),
+            commit.summary,
+            format!("({})", commit.author).dimmed()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sage_git::testing::TestRepo;
+
+    #[test]
+    fn snapshot_covers_stack_changes_and_commits() -> Result<()> {
+        let repo = TestRepo::builder().with_initial_commit().build()?;
+        let mut graph = SageGraph::load(&repo)?;
+
+        repo.run_git(["checkout", "-b", "feat/base"])?;
+        for n in 1..=4 {
+            repo.write("base.txt", format!("{n}\n"))?;
+            repo.commit_all(&format!("base {n}"))?;
+        }
+        graph.create_stack(
+            &repo,
+            "feat".to_owned(),
+            "feat/base".to_owned(),
+            "main".to_owned(),
+        )?;
+
+        repo.write("base.txt", "dirty\n")?;
+        repo.write("untracked.txt", "new\n")?;
+
+        let snapshot = build_dash_snapshot(&repo, &graph)?;
+        assert_eq!(snapshot.branch, "feat/base");
+        assert_eq!(snapshot.stack.as_deref(), Some("feat"));
+        assert_eq!(snapshot.branches.len(), 1);
+        assert!(snapshot.branches[0].current);
+        assert_eq!(snapshot.dirty_files, 2);
+
+        let summaries: Vec<&str> = snapshot
+            .recent_commits
+            .iter()
+            .map(|commit| commit.summary.as_str())
+            .collect();
+        assert_eq!(summaries, vec!["base 4", "base 3", "base 2"]);
+
+        Ok(())
+    }
+}
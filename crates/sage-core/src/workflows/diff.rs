@@ -0,0 +1,85 @@
+use std::io::{self, IsTerminal, Write};
+
+use anyhow::Result;
+use sage_fmt::MessageType;
+use sage_git::DiffOptions;
+use serde::Serialize;
+
+use crate::print_json;
+
+/// One file in `sg diff --json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileChange {
+    pub path: String,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+/// Show the working tree changes, or the staged ones with `staged`, through the pager.
+pub fn diff(
+    staged: bool,
+    paths: Vec<String>,
+    json: bool,
+    console: &sage_fmt::Console,
+) -> Result<()> {
+    let repo = sage_git::Repo::open()?;
+    // git runs from the repository root, so anchor paths to where the user is.
+    let cwd = std::env::current_dir()?;
+    let options = DiffOptions {
+        staged,
+        paths: paths
+            .iter()
+            .map(|path| cwd.join(path).display().to_string())
+            .collect(),
+    };
+
+    if json {
+        let files: Vec<FileChange> = repo
+            .diff_stat_of(&options)?
+            .files
+            .into_iter()
+            .map(|file| FileChange {
+                path: file.path,
+                additions: file.additions,
+                deletions: file.deletions,
+            })
+            .collect();
+        return print_json("diff", files);
+    }
+
+    let text = repo.diff_text(&options)?;
+    if text.trim().is_empty() {
+        let what = if staged {
+            "No staged changes"
+        } else {
+            "No changes"
+        };
+        return console.message(MessageType::Info, what);
+    }
+
+    let mut out = sage_fmt::Pager::start()?;
+    let to_terminal = out.is_paging() || io::stdout().is_terminal();
+    write!(out, "{}", render_for_output(&text, console, to_terminal))?;
+    out.finish()
+}
+
+/// Colorizes only output headed for a terminal, so `sg diff > x.patch` stays a valid patch.
+fn render_for_output(text: &str, console: &sage_fmt::Console, to_terminal: bool) -> String {
+    if to_terminal {
+        console.render_diff(text, true)
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redirected_output_is_the_raw_patch() {
+        let patch = "diff --git a/x b/x\n--- a/x\n+++ b/x\n@@ -1 +1 @@\n-old\n+new\n";
+        let console = sage_fmt::Console::new();
+        assert_eq!(render_for_output(patch, &console, false), patch);
+    }
+}
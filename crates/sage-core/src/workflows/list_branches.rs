@@ -2,9 +2,32 @@ use anyhow::Result;
 use colored::{Color, Colorize};
 use std::collections::HashMap;
 
-pub fn list_branches(show_stack: bool) -> Result<()> {
+use crate::load_graph;
+
+/// Keep only branches merged (or not) into a revision; `None` means the default branch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeFilter {
+    Merged(Option<String>),
+    NotMerged(Option<String>),
+}
+
+/// The order `sg list` prints branches in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BranchSort {
+    #[default]
+    Name,
+    /// Most recently committed to first.
+    Recent,
+}
+
+pub fn list_branches(
+    show_stack: bool,
+    merge_filter: Option<MergeFilter>,
+    sort: BranchSort,
+    console: &sage_fmt::Console,
+) -> Result<()> {
     let repo = sage_git::Repo::open()?;
-    let graph = sage_graph::SageGraph::load(&repo)?;
+    let graph = load_graph(&repo, console)?;
     let current_branch = repo.get_current_branch()?;
     let default_branch = repo.get_default_branch()?.replace("origin/", "");
 
@@ -22,6 +45,14 @@ pub fn list_branches(show_stack: bool) -> Result<()> {
 
     combined.sort();
 
+    if let Some(filter) = &merge_filter {
+        combined = filter_merged(&repo, combined, filter)?;
+    }
+
+    if sort == BranchSort::Recent {
+        combined = sort_by_recent(&repo, combined)?;
+    }
+
     let mut stack_colors: HashMap<String, Color> = HashMap::new();
 
     if show_stack {
@@ -100,3 +131,118 @@ pub fn list_branches(show_stack: bool) -> Result<()> {
     }
     Ok(())
 }
+
+/// Keeps the branches whose tip is (or, for `NotMerged`, isn't) reachable from the target.
+pub fn filter_merged(
+    repo: &sage_git::Repo,
+    branches: Vec<String>,
+    filter: &MergeFilter,
+) -> Result<Vec<String>> {
+    let (target, want_merged) = match filter {
+        MergeFilter::Merged(target) => (target, true),
+        MergeFilter::NotMerged(target) => (target, false),
+    };
+    let target = match target {
+        Some(target) => target.clone(),
+        None => repo.get_default_branch()?,
+    };
+
+    let mut kept = Vec::new();
+    for branch in branches {
+        if repo.is_ancestor(&branch, &target)? == want_merged {
+            kept.push(branch);
+        }
+    }
+    Ok(kept)
+}
+
+/// Orders branches by their tip's committer date, newest first, looking each tip up once.
+pub fn sort_by_recent(repo: &sage_git::Repo, branches: Vec<String>) -> Result<Vec<String>> {
+    let mut activity = HashMap::with_capacity(branches.len());
+    for branch in &branches {
+        activity.insert(branch.clone(), repo.branch_last_activity(branch)?);
+    }
+
+    let mut sorted = branches;
+    // Stable, so branches with the same date stay in name order.
+    sorted.sort_by(|a, b| activity[b].cmp(&activity[a]));
+    Ok(sorted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sage_git::testing::TestRepo;
+
+    #[test]
+    fn merge_filters_partition_branches() -> Result<()> {
+        let repo = TestRepo::builder()
+            .initial_branch("main")
+            .with_initial_commit()
+            .build()?;
+
+        repo.run_git(["checkout", "-b", "done"])?;
+        repo.write("done.txt", "done")?;
+        repo.commit_all("feat: finished work")?;
+        repo.run_git(["checkout", "main"])?;
+        repo.run_git(["merge", "--no-ff", "-m", "Merge done", "done"])?;
+
+        repo.run_git(["checkout", "-b", "wip"])?;
+        repo.write("wip.txt", "wip")?;
+        repo.commit_all("feat: in flight")?;
+        repo.run_git(["checkout", "main"])?;
+
+        let branches = repo.list_branches()?;
+        let merged = filter_merged(&repo, branches.clone(), &MergeFilter::Merged(None))?;
+        let not_merged = filter_merged(
+            &repo,
+            branches.clone(),
+            &MergeFilter::NotMerged(Some("main".to_owned())),
+        )?;
+
+        assert_eq!(merged, vec!["refs/heads/done", "refs/heads/main"]);
+        assert_eq!(not_merged, vec!["refs/heads/wip"]);
+
+        // Against `wip` everything is merged, since it was cut from main after the merge.
+        let into_wip = filter_merged(
+            &repo,
+            branches,
+            &MergeFilter::Merged(Some("wip".to_owned())),
+        )?;
+        assert_eq!(into_wip.len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn recent_sort_puts_latest_commit_first() -> Result<()> {
+        let repo = TestRepo::builder()
+            .initial_branch("main")
+            .with_initial_commit()
+            .build()?;
+
+        for (branch, date) in [
+            ("older", "2024-01-01T10:00:00Z"),
+            ("newest", "2024-03-01T10:00:00Z"),
+            ("middle", "2024-02-01T10:00:00Z"),
+        ] {
+            repo.run_git(["checkout", "-q", "-b", branch, "main"])?;
+            repo.write(format!("{branch}.txt"), branch)?;
+            repo.run_git(["add", "--all"])?;
+            repo.git()
+                .env("GIT_COMMITTER_DATE", date)
+                .args(["commit", "-q", "-m", branch])
+                .run()?;
+        }
+        repo.run_git(["checkout", "-q", "main"])?;
+
+        let mut branches = repo.list_branches()?;
+        branches.retain(|branch| branch != "refs/heads/main");
+        branches.sort();
+
+        assert_eq!(
+            sort_by_recent(&repo, branches)?,
+            vec!["refs/heads/newest", "refs/heads/middle", "refs/heads/older"]
+        );
+        Ok(())
+    }
+}
@@ -1,22 +1,40 @@
-use anyhow::Result;
+use std::io::Write;
+
+use anyhow::{Result, bail};
+use chrono::{
+    DateTime, Utc,
+    format::{Item, StrftimeItems},
+};
 use colored::Colorize;
 
-pub fn log(limit: Option<usize>) -> Result<()> {
+pub fn log(limit: Option<usize>, format: Option<&str>) -> Result<()> {
     let repo = sage_git::Repo::open()?;
+
+    if let Some(format) = format {
+        let format = LogFormat::parse(format)?;
+        let mut out = sage_fmt::Pager::start()?;
+        for commit in repo.recent_commits(limit)? {
+            writeln!(out, "{}", format.render(&commit))?;
+        }
+        return out.finish();
+    }
     let current_branch = repo.get_current_branch()?;
 
     let mut logs = repo.get_commits(limit)?;
     logs.reverse();
 
-    println!(
+    let mut out = sage_fmt::Pager::start()?;
+
+    writeln!(
+        out,
         "{} {}",
         "Branch History:".bright_green().bold(),
         current_branch.yellow()
-    );
+    )?;
 
     if logs.is_empty() {
-        println!("{}", "No commits found".bright_red());
-        return Ok(());
+        writeln!(out, "{}", "No commits found".bright_red())?;
+        return out.finish();
     }
 
     // Group commits by date
@@ -26,18 +44,19 @@ pub fn log(limit: Option<usize>) -> Result<()> {
         // If we encounter a new date, print it
         if commit.date != current_date {
             current_date = commit.date.clone();
-            println!();
-            println!("{} {}", "Date:".bright_blue(), current_date.bold());
+            writeln!(out)?;
+            writeln!(out, "{} {}", "Date:".bright_blue(), current_date.bold())?;
         }
 
         // Print commit info in the desired format
-        println!(
+        writeln!(
+            out,
             " {} {} {} @{}",
             "●".bright_green(),
             commit.hash.bright_yellow(),
             "by".dimmed(),
             commit.author
-        );
+        )?;
 
         // Print he commit message indented
         if !commit.message.is_empty() {
@@ -45,10 +64,162 @@ pub fn log(limit: Option<usize>) -> Result<()> {
             let padding = "    ";
             let lines = commit.message.split('\n').collect::<Vec<&str>>();
             for line in lines {
-                println!("{}{}", padding, line.dimmed());
+                writeln!(out, "{}{}", padding, line.dimmed())?;
+            }
+        }
+    }
+
+    out.finish()
+}
+
+/// Valid placeholders for `sg log --format`.
+pub const LOG_FORMAT_TOKENS: &[&str] = &[
+    "{id}",
+    "{short}",
+    "{author}",
+    "{date}",
+    "{date:<strftime>}",
+    "{summary}",
+];
+
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// A parsed `--format` template such as `"{short} {author} {summary}"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFormat {
+    parts: Vec<FormatPart>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FormatPart {
+    Literal(String),
+    Id,
+    Short,
+    Author,
+    Date(String),
+    Summary,
+}
+
+impl LogFormat {
+    /// Parse a template, rejecting unknown placeholders and invalid date formats up front.
+    pub fn parse(template: &str) -> Result<Self> {
+        let mut parts = Vec::new();
+        let mut rest = template;
+
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                parts.push(FormatPart::Literal(rest[..start].to_string()));
             }
+            let Some(len) = rest[start..].find('}') else {
+                bail!("Unclosed '{{' in log format '{template}'");
+            };
+            let token = &rest[start + 1..start + len];
+            parts.push(parse_token(token)?);
+            rest = &rest[start + len + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(FormatPart::Literal(rest.to_string()));
         }
+
+        Ok(Self { parts })
     }
 
-    Ok(())
+    pub fn render(&self, commit: &sage_git::CommitInfo) -> String {
+        let mut out = String::new();
+        for part in &self.parts {
+            match part {
+                FormatPart::Literal(text) => out.push_str(text),
+                FormatPart::Id => out.push_str(&commit.id),
+                FormatPart::Short => out.push_str(&commit.short_id),
+                FormatPart::Author => out.push_str(&commit.author),
+                FormatPart::Summary => out.push_str(&commit.summary),
+                FormatPart::Date(format) => {
+                    if let Some(date) = DateTime::<Utc>::from_timestamp(commit.timestamp, 0) {
+                        out.push_str(&date.format(format).to_string());
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+fn parse_token(token: &str) -> Result<FormatPart> {
+    let part = match token {
+        "id" => FormatPart::Id,
+        "short" => FormatPart::Short,
+        "author" => FormatPart::Author,
+        "summary" => FormatPart::Summary,
+        "date" => FormatPart::Date(DEFAULT_DATE_FORMAT.to_string()),
+        _ => match token.strip_prefix("date:") {
+            Some(format) => {
+                if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+                    bail!("Invalid date format '{format}' in log format");
+                }
+                FormatPart::Date(format.to_string())
+            }
+            None => bail!(
+                "Unknown log format placeholder '{{{token}}}'. Valid placeholders: {}",
+                LOG_FORMAT_TOKENS.join(", ")
+            ),
+        },
+    };
+    Ok(part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit() -> sage_git::CommitInfo {
+        sage_git::CommitInfo {
+            id: "0123456789abcdef0123456789abcdef01234567".to_string(),
+            short_id: "01234567".to_string(),
+            summary: "feat: add log formats".to_string(),
+            message: "feat: add log formats\n\nWith a body".to_string(),
+            author: "Test User".to_string(),
+            // 2024-03-05 14:07:09 UTC
+            timestamp: 1_709_647_629,
+        }
+    }
+
+    #[test]
+    fn renders_each_token() {
+        let commit = commit();
+        let cases = [
+            ("{id}", commit.id.as_str()),
+            ("{short}", "01234567"),
+            ("{author}", "Test User"),
+            ("{summary}", "feat: add log formats"),
+            ("{date}", "2024-03-05 14:07"),
+            ("{date:%Y-%m-%d}", "2024-03-05"),
+            ("{date:%d/%m %H:%M:%S}", "05/03 14:07:09"),
+        ];
+
+        for (template, expected) in cases {
+            let format = LogFormat::parse(template).expect(template);
+            assert_eq!(format.render(&commit), expected, "template {template}");
+        }
+    }
+
+    #[test]
+    fn keeps_literal_text_around_tokens() {
+        let format = LogFormat::parse("[{short}] {summary} ({author})").unwrap();
+        assert_eq!(
+            format.render(&commit()),
+            "[01234567] feat: add log formats (Test User)"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_tokens_and_bad_dates() {
+        let err = LogFormat::parse("{short} {hash}").unwrap_err().to_string();
+        assert!(err.contains("{hash}"), "{err}");
+        for token in LOG_FORMAT_TOKENS {
+            assert!(err.contains(token), "missing {token} in: {err}");
+        }
+
+        assert!(LogFormat::parse("{date:%Q}").is_err());
+        assert!(LogFormat::parse("{short").is_err());
+    }
 }
@@ -1,13 +1,27 @@
 pub mod config;
+pub mod dash;
+pub mod diff;
 pub mod list_branches;
 pub mod log;
 pub mod save;
+pub mod share;
+pub mod stack_adopt;
+pub mod stack_goto;
+pub mod stack_navigate;
+pub mod stack_status;
 pub mod start;
 pub mod work;
 
 pub use config::*;
+pub use dash::*;
+pub use diff::*;
 pub use list_branches::*;
 pub use log::*;
 pub use save::*;
+pub use share::*;
+pub use stack_adopt::*;
+pub use stack_goto::*;
+pub use stack_navigate::*;
+pub use stack_status::*;
 pub use start::*;
 pub use work::*;
@@ -1,17 +1,40 @@
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
 use colored::Colorize;
+use sage_config::ConfigManager;
 use sage_fmt::MessageType;
+use sage_git::{CommitOptions, HeadState, HookFailure, PushMode};
 
-use crate::{commit_message, fetch_if_stale, stage_changes};
+use crate::{
+    commit_message, edit_commit_message, fetch_if_stale, load_graph, review_commit_message,
+    stage_changes, stage_hunks_interactively, summarize_staged,
+};
 
 pub struct SaveOptions {
     pub message: Option<String>,
+    /// Force-push without asking when the branch no longer matches its remote.
     pub force: bool,
     pub ai: bool,
     pub push: bool,
+    /// Push as if the branch weren't part of a stack.
+    pub no_stack: bool,
     pub empty: bool,
     pub amend: bool,
+    /// Keep the previous message when amending, even if none was given.
+    pub no_edit: bool,
+    /// Open the `--ai` or `--message` message, or the amended commit's, in the editor first.
+    pub edit: bool,
+    /// Only change the last commit's message, leaving the index untouched.
+    pub reword: bool,
+    pub no_verify: bool,
+    /// Create a `fixup!` commit for this revision instead of a regular commit.
+    pub fixup: Option<String>,
+    /// Sign the commit; also enabled by `save.sign_commits`.
+    pub sign: bool,
+    /// Pick which unstaged hunks to commit instead of staging everything.
+    pub interactive: bool,
     pub paths: Option<Vec<String>>,
+    /// Gitignore-style patterns to leave out when staging everything.
+    pub exclude: Vec<String>,
 }
 
 pub async fn save(options: SaveOptions, console: &sage_fmt::Console) -> Result<()> {
@@ -20,34 +43,241 @@ pub async fn save(options: SaveOptions, console: &sage_fmt::Console) -> Result<(
         force,
         ai,
         push,
+        no_stack,
         empty,
         amend,
+        no_edit,
+        edit,
+        reword,
+        no_verify,
+        fixup,
+        sign,
+        interactive,
         paths,
+        exclude,
     } = options;
 
     let repo = sage_git::Repo::open()?;
+    let head = repo.head_state()?;
+    if let HeadState::Unborn { branch } = &head
+        && (amend || reword)
+    {
+        bail!("Nothing to amend: '{branch}' has no commits yet");
+    }
+
+    if reword {
+        repo.reword_last_commit(message.as_deref(), no_verify)?;
+        console.message(
+            MessageType::Success,
+            &format!("Reworded commit {}", short_head(&repo)?.dimmed()),
+        )?;
+        if push {
+            push_saved(&repo, console, true, force, no_stack)?;
+        }
+        return Ok(());
+    }
     let _ = fetch_if_stale(&repo, console)?;
-    let _current_branch = repo.get_current_branch()?;
+    match head {
+        HeadState::Detached { .. } => {
+            bail!("HEAD is detached; switch to a branch with `sg work` before saving")
+        }
+        HeadState::Unborn { branch } => console.message(
+            MessageType::Info,
+            &format!("Creating the first commit on {branch}"),
+        )?,
+        HeadState::Branch { .. } => {}
+    }
 
-    stage_changes(&repo, console, paths)?;
+    let sign = sign
+        || ConfigManager::load()
+            .context("Failed to load configuration")?
+            .get()
+            .save
+            .sign_commits;
+    if sign && repo.signing_key().is_none() {
+        bail!(
+            "Commit signing requested but no signing key is configured; set git's user.signingkey"
+        );
+    }
 
-    let msg = commit_message(&repo, console, message, ai).await?;
+    // Resolve before staging so a bad target doesn't leave the index changed.
+    if let Some(target) = &fixup {
+        repo.last_commit(target)
+            .with_context(|| format!("Fixup target '{target}' not found"))?;
+    }
 
-    repo.create_commit(&msg, empty, amend)?;
+    if interactive {
+        let staged = stage_hunks_interactively(&repo, console)?;
+        if staged == 0 && !amend && !empty && repo.staged_changes()?.is_empty() {
+            bail!("No hunks selected; nothing to commit");
+        }
+    } else {
+        stage_changes(&repo, console, paths, &exclude)?;
+    }
+    summarize_staged(&repo, console)?;
 
-    let last_commit = repo.get_current_commit()?;
-    let mut hash = last_commit.to_hex().to_string();
-    hash.truncate(8);
+    let msg = if fixup.is_some() || no_edit {
+        String::new()
+    } else {
+        let msg = commit_message(&repo, console, message, ai).await?;
+        if msg.is_empty() && !amend {
+            edit_commit_message(&repo, console)?
+        } else if edit && !msg.is_empty() {
+            review_commit_message(&repo, console, &msg)?
+        } else if edit {
+            // `--amend --edit` alone reviews the message being replaced, like `git commit --amend`.
+            let previous = repo
+                .current_commit_info()?
+                .map(|commit| commit.message)
+                .unwrap_or_default();
+            review_commit_message(&repo, console, &previous)?
+        } else {
+            msg
+        }
+    };
+
+    let commit_options = CommitOptions {
+        allow_empty: empty,
+        amend,
+        // Amending without any message keeps the previous one.
+        no_edit: amend && (no_edit || msg.is_empty()),
+        no_verify,
+        fixup,
+        sign,
+    };
+    while let Err(err) = repo.create_commit(&msg, &commit_options) {
+        let Some(failure) = err.downcast_ref::<HookFailure>() else {
+            return Err(err);
+        };
+        if !console.is_interactive() {
+            console.details(MessageType::Error, &failure.output);
+            return Err(err);
+        }
+        console.message(MessageType::Error, &failure.to_string())?;
+        console.details(MessageType::Error, &failure.output);
+        let prompt = format!(
+            "Fix the {} issues, stage any changes, and retry?",
+            failure.hook.name()
+        );
+        if !console.confirm(&prompt, true)? {
+            bail!("Commit aborted by the {} hook", failure.hook.name());
+        }
+    }
+
+    if sign && !repo.verify_commit_signature("HEAD")? {
+        console.message(
+            MessageType::Warning,
+            "Commit was created but its signature could not be verified",
+        )?;
+    }
 
     console.message(
         MessageType::Success,
-        &format!("Created commit {}", hash.dimmed()),
+        &format!("Created commit {}", commit_label(&repo)?.dimmed()),
     )?;
 
     if push {
-        repo.push(force)?;
-        console.message(MessageType::Success, "Pushed to remote")?;
+        push_saved(&repo, console, amend, force, no_stack)?;
+    }
+
+    Ok(())
+}
+
+/// How `save --push` pushes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushPlan {
+    Push(PushMode),
+    /// The remote has commits the branch lost; overwriting them needs the user's go-ahead.
+    ConfirmForce,
+}
+
+/// Decide how to push a branch the remote is `behind` commits ahead of.
+///
+/// Amended commits and restacked branches legitimately diverge from their remote, so they
+/// may be force-pushed with a lease; `force` only skips asking first. Anything else is
+/// pushed plainly and left for the remote to reject.
+pub fn plan_push(stacked: bool, rewritten: bool, behind: usize, force: bool) -> PushPlan {
+    if behind > 0 && (stacked || rewritten) {
+        if force {
+            PushPlan::Push(PushMode::ForceWithLease)
+        } else {
+            PushPlan::ConfirmForce
+        }
+    } else {
+        PushPlan::Push(PushMode::Plain)
     }
+}
+
+fn push_saved(
+    repo: &sage_git::Repo,
+    console: &sage_fmt::Console,
+    rewritten: bool,
+    force: bool,
+    no_stack: bool,
+) -> Result<()> {
+    let branch = repo.get_current_branch()?;
+    let stacked = !no_stack
+        && load_graph(repo, console)?
+            .stack_for_branch(&branch)
+            .is_some();
+    let behind = repo
+        .upstream_ahead_behind(&branch)?
+        .map_or(0, |(_, behind)| behind);
+
+    let mode = match plan_push(stacked, rewritten, behind, force) {
+        PushPlan::Push(mode) => mode,
+        PushPlan::ConfirmForce => {
+            let prompt = format!("'{branch}' no longer matches its remote; force-push with lease?");
+            if !console.confirm(&prompt, false)? {
+                bail!("Not pushing '{branch}'; pass --force to overwrite the remote branch");
+            }
+            PushMode::ForceWithLease
+        }
+    };
 
+    repo.push_with(mode)?;
+    console.message(MessageType::Success, "Pushed to remote")?;
     Ok(())
 }
+
+/// Short id and summary, e.g. `1a2b3c4d fixup! add parser`.
+fn commit_label(repo: &sage_git::Repo) -> Result<String> {
+    Ok(repo
+        .current_commit_info()?
+        .map(|commit| format!("{} {}", commit.short_id, commit.summary))
+        .unwrap_or_default())
+}
+
+fn short_head(repo: &sage_git::Repo) -> Result<String> {
+    Ok(repo
+        .current_commit_info()?
+        .map(|commit| commit.short_id)
+        .unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn force_push_only_for_rewritten_or_stacked_branches() {
+        let plain = PushPlan::Push(PushMode::Plain);
+
+        // In sync with the remote, or no upstream yet: nothing to force.
+        assert_eq!(plan_push(true, true, 0, false), plain);
+        assert_eq!(plan_push(false, false, 0, false), plain);
+
+        // A restacked or amended branch diverges from its remote on purpose.
+        assert_eq!(plan_push(true, false, 2, false), PushPlan::ConfirmForce);
+        assert_eq!(plan_push(false, true, 1, false), PushPlan::ConfirmForce);
+
+        // A plain branch behind its remote has someone else's commits; don't clobber them.
+        assert_eq!(plan_push(false, false, 1, false), plain);
+        assert_eq!(plan_push(false, false, 1, true), plain);
+
+        // --force only skips the question; the push still goes out with a lease.
+        let lease = PushPlan::Push(PushMode::ForceWithLease);
+        assert_eq!(plan_push(true, false, 2, true), lease);
+        assert_eq!(plan_push(false, true, 1, true), lease);
+    }
+}
@@ -0,0 +1,401 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use colored::Colorize;
+use sage_config::{ConfigManager, SageConfig};
+use sage_fmt::MessageType;
+
+use crate::forge::{
+    ForgeClient, ForgeKind, ForgeRepo, GitHubClient, GitLabClient, NewPullRequest, detect_forge,
+};
+use crate::load_graph;
+
+const GITHUB_TOKEN_ENV_VARS: &[&str] = &["GITHUB_TOKEN", "GH_TOKEN"];
+const GITLAB_TOKEN_ENV_VARS: &[&str] = &["GITLAB_TOKEN"];
+const PR_TEMPLATE_PATHS: &[&str] = &[
+    ".github/pull_request_template.md",
+    ".github/PULL_REQUEST_TEMPLATE.md",
+    ".gitlab/merge_request_templates/Default.md",
+    "PULL_REQUEST_TEMPLATE.md",
+    "docs/pull_request_template.md",
+];
+/// Directories holding several named templates, picked with `--template <name>`.
+const PR_TEMPLATE_DIRS: &[&str] = &[
+    ".github/PULL_REQUEST_TEMPLATE",
+    ".gitlab/merge_request_templates",
+];
+
+#[derive(Debug, Clone, Default)]
+pub struct ShareOptions {
+    /// Open the pull request as a draft.
+    pub draft: bool,
+    /// Mark an existing draft pull request ready for review.
+    pub ready: bool,
+    /// Name (file stem) of the template to fill in, from the template directories.
+    pub template: Option<String>,
+}
+
+pub async fn share(options: ShareOptions, console: &sage_fmt::Console) -> Result<()> {
+    let mut repo = sage_git::Repo::open()?;
+    let branch = repo.get_current_branch()?;
+    // Without a fetched `origin/HEAD`, assume the same default as repos without a remote.
+    let default_branch = repo
+        .get_default_branch()
+        .map(|branch| branch.replace("origin/", ""))
+        .unwrap_or_else(|_| "main".to_string());
+    if branch == default_branch {
+        bail!("Cannot share the default branch '{branch}'; switch to a feature branch first");
+    }
+
+    let config_manager = ConfigManager::load().context("Failed to load configuration")?;
+    let config = config_manager.get();
+
+    let remote = repo
+        .remote_name()?
+        .ok_or_else(|| anyhow!("No remote configured; add one with `git remote add`"))?;
+    let url = repo
+        .remote_url(&remote)?
+        .ok_or_else(|| anyhow!("Remote '{remote}' is not a hosted repository URL"))?;
+    let forge = detect_forge(&url, config.git.forge_host.as_deref()).ok_or_else(|| {
+        anyhow!(
+            "Remote '{remote}' ({}) is not on GitHub or GitLab. For self-hosted GitLab, run `sg config --key git.forge_host --value <host>`",
+            url.host
+        )
+    })?;
+    let token = forge_token(forge.kind, config)?;
+    let template = pr_template(&repo.repo_root(), options.template.as_deref())?;
+
+    let mut graph = load_graph(&repo, console)?;
+    let base = graph
+        .get_info(&branch)
+        .map(|info| info.parent.clone())
+        .filter(|parent| *parent != branch)
+        .unwrap_or(default_branch);
+
+    if repo.upstream_ahead_behind(&branch)?.is_none() {
+        repo.set_upstream_named(&branch, &remote, &branch)?;
+    }
+    let spinner = console.spinner(format!("Pushing '{branch}'"));
+    repo.push(false)?;
+    spinner.success(&format!("Pushed '{}'", branch.bright_blue()));
+
+    let request = ShareRequest {
+        repo: &repo,
+        forge: &forge,
+        branch: &branch,
+        base: &base,
+        template: &template,
+        options,
+    };
+    let number = match forge.kind {
+        ForgeKind::GitHub => {
+            let client = GitHubClient::new(token, &forge.path)?;
+            request.run(&client, console).await?
+        }
+        ForgeKind::GitLab => {
+            let client = GitLabClient::new(token, &forge.host, &forge.path);
+            request.run(&client, console).await?
+        }
+    };
+
+    if graph.set_pr_number(&branch, Some(number)) {
+        graph.save(&repo)?;
+    }
+
+    Ok(())
+}
+
+struct ShareRequest<'a> {
+    repo: &'a sage_git::Repo,
+    forge: &'a ForgeRepo,
+    branch: &'a str,
+    base: &'a str,
+    template: &'a str,
+    options: ShareOptions,
+}
+
+impl ShareRequest<'_> {
+    /// Opens or updates the request for the branch, returning its number.
+    async fn run(&self, client: &impl ForgeClient, console: &sage_fmt::Console) -> Result<u64> {
+        let noun = self.forge.kind.request_noun();
+
+        let pr = match client.find_pr(self.branch).await? {
+            Some(pr) => {
+                if self.options.ready && pr.draft {
+                    client.mark_ready(&pr).await?;
+                    console.message(
+                        MessageType::Success,
+                        &format!("Marked {noun} #{} ready for review", pr.number),
+                    )?;
+                } else {
+                    console.message(
+                        MessageType::Info,
+                        &format!("The {noun} #{} is already open", pr.number),
+                    )?;
+                }
+                pr
+            }
+            None => {
+                let commits = self
+                    .repo
+                    .commits_between(self.base, self.branch)?
+                    .into_iter()
+                    .map(|commit| commit.summary)
+                    .collect();
+                let (title, body) =
+                    pr_content(self.branch, commits, self.template, console, &AiWriter).await?;
+
+                let pr = client
+                    .create_pr(NewPullRequest {
+                        title: &title,
+                        body: &body,
+                        head: self.branch,
+                        base: self.base,
+                        draft: self.options.draft,
+                    })
+                    .await?;
+                let kind = if self.options.draft {
+                    format!("draft {noun}")
+                } else {
+                    noun.to_string()
+                };
+                console.message(
+                    MessageType::Success,
+                    &format!("Opened {kind} #{} into '{}'", pr.number, self.base.yellow()),
+                )?;
+                pr
+            }
+        };
+
+        if let Some(url) = &pr.url {
+            println!("  {}", url.bright_blue().underline());
+        }
+
+        Ok(pr.number)
+    }
+}
+
+fn forge_token(kind: ForgeKind, config: &SageConfig) -> Result<String> {
+    let (env_vars, configured, key) = match kind {
+        ForgeKind::GitHub => (
+            GITHUB_TOKEN_ENV_VARS,
+            config.git.github_token.as_ref(),
+            "git.github_token",
+        ),
+        ForgeKind::GitLab => (
+            GITLAB_TOKEN_ENV_VARS,
+            config.git.gitlab_token.as_ref(),
+            "git.gitlab_token",
+        ),
+    };
+
+    for var in env_vars {
+        if let Ok(token) = env::var(var)
+            && !token.trim().is_empty()
+        {
+            return Ok(token.trim().to_string());
+        }
+    }
+
+    configured
+        .map(|token| token.expose().trim().to_string())
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| {
+            anyhow!(
+                "No token found. Set {} or run `sg config --key {key} --value <token>`",
+                env_vars[0]
+            )
+        })
+}
+
+/// Writes the title and body of a new request from its commits.
+trait PrWriter {
+    async fn title(&self, commits: Vec<String>) -> Result<String>;
+    /// Fills in `template`, or the default sections when it's empty.
+    async fn description(
+        &self,
+        title: &str,
+        commits: Vec<String>,
+        template: &str,
+    ) -> Result<String>;
+}
+
+struct AiWriter;
+
+impl PrWriter for AiWriter {
+    async fn title(&self, commits: Vec<String>) -> Result<String> {
+        sage_ai::pr_title(commits).await
+    }
+
+    async fn description(
+        &self,
+        title: &str,
+        commits: Vec<String>,
+        template: &str,
+    ) -> Result<String> {
+        sage_ai::pr_description(title, commits, template).await
+    }
+}
+
+/// Generate a title and body with `writer`, falling back to the commit list when it fails.
+async fn pr_content(
+    branch: &str,
+    commits: Vec<String>,
+    template: &str,
+    console: &sage_fmt::Console,
+    writer: &impl PrWriter,
+) -> Result<(String, String)> {
+    let spinner = console.spinner("Writing description");
+    let generated = match writer.title(commits.clone()).await {
+        Ok(title) => writer
+            .description(&title, commits.clone(), template)
+            .await
+            .map(|body| (title, body)),
+        Err(err) => Err(err),
+    };
+
+    match generated {
+        Ok(content) => {
+            spinner.success("Generated title and description");
+            Ok(content)
+        }
+        Err(err) => {
+            spinner.fail(&format!("AI unavailable ({err}); using commit messages"));
+            let title = match commits.as_slice() {
+                [only] => only.clone(),
+                _ => branch.to_string(),
+            };
+            let body = commits
+                .iter()
+                .map(|subject| format!("- {subject}"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            Ok((title, body))
+        }
+    }
+}
+
+/// The template text to fill in; empty when the repository has none.
+///
+/// Without a `name`, the first single-file template wins.
+fn pr_template(root: &Path, name: Option<&str>) -> Result<String> {
+    let Some(name) = name else {
+        return Ok(PR_TEMPLATE_PATHS
+            .iter()
+            .find_map(|path| fs::read_to_string(root.join(path)).ok())
+            .unwrap_or_default());
+    };
+
+    let templates = named_pr_templates(root);
+    match templates
+        .iter()
+        .find(|(stem, _)| stem.eq_ignore_ascii_case(name))
+    {
+        Some((_, path)) => fs::read_to_string(path)
+            .with_context(|| format!("Failed to read template {}", path.display())),
+        None => {
+            let available: Vec<&str> = templates.iter().map(|(stem, _)| stem.as_str()).collect();
+            bail!(
+                "No pull request template named '{name}'. Available: {}",
+                if available.is_empty() {
+                    "none".to_string()
+                } else {
+                    available.join(", ")
+                }
+            )
+        }
+    }
+}
+
+/// Markdown templates in [`PR_TEMPLATE_DIRS`], keyed by file stem and sorted by name.
+fn named_pr_templates(root: &Path) -> Vec<(String, PathBuf)> {
+    let mut templates: Vec<(String, PathBuf)> = PR_TEMPLATE_DIRS
+        .iter()
+        .filter_map(|dir| fs::read_dir(root.join(dir)).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+        .filter_map(|path| Some((path.file_stem()?.to_str()?.to_string(), path)))
+        .collect();
+    templates.sort();
+    templates
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+    use sage_git::testing::TestRepo;
+
+    /// Records the template each description is asked to fill in.
+    #[derive(Default)]
+    struct RecordingWriter {
+        templates: RefCell<Vec<String>>,
+    }
+
+    impl PrWriter for RecordingWriter {
+        async fn title(&self, _commits: Vec<String>) -> Result<String> {
+            Ok("Add login".to_string())
+        }
+
+        async fn description(
+            &self,
+            _title: &str,
+            _commits: Vec<String>,
+            template: &str,
+        ) -> Result<String> {
+            self.templates.borrow_mut().push(template.to_string());
+            Ok(template.to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn chosen_template_reaches_the_description_prompt() -> Result<()> {
+        let repo = TestRepo::builder().with_initial_commit().build()?;
+        repo.write(".github/pull_request_template.md", "## Summary\n")?;
+        repo.write(".github/PULL_REQUEST_TEMPLATE/bugfix.md", "## Root cause\n")?;
+        let console = sage_fmt::Console::new().with_writer(std::io::sink());
+        let writer = RecordingWriter::default();
+
+        for name in [None, Some("bugfix")] {
+            let template = pr_template(repo.path(), name)?;
+            let commits = vec!["Add login".to_string()];
+            pr_content("feature", commits, &template, &console, &writer).await?;
+        }
+
+        assert_eq!(
+            *writer.templates.borrow(),
+            ["## Summary\n", "## Root cause\n"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn resolves_default_and_named_templates() -> Result<()> {
+        let repo = TestRepo::builder().with_initial_commit().build()?;
+        let root = repo.path();
+        assert_eq!(pr_template(root, None)?, "");
+
+        repo.write(".github/pull_request_template.md", "## Summary\n")?;
+        repo.write(".github/PULL_REQUEST_TEMPLATE/bugfix.md", "## Root cause\n")?;
+        repo.write(
+            ".gitlab/merge_request_templates/Release.md",
+            "## Changelog\n",
+        )?;
+
+        assert_eq!(pr_template(root, None)?, "## Summary\n");
+        assert_eq!(pr_template(root, Some("bugfix"))?, "## Root cause\n");
+        assert_eq!(pr_template(root, Some("release"))?, "## Changelog\n");
+
+        let err = pr_template(root, Some("missing")).expect_err("unknown template");
+        assert!(
+            err.to_string().contains("Available: Release, bugfix"),
+            "{err}"
+        );
+        Ok(())
+    }
+}
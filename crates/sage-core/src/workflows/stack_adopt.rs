@@ -0,0 +1,122 @@
+use anyhow::{Result, bail};
+use colored::Colorize;
+use sage_fmt::MessageType;
+use sage_graph::SageGraph;
+
+use crate::load_graph;
+
+/// Start tracking the current branch as a child of the tracked branch `parent`.
+pub fn stack_adopt(parent: &str, console: &sage_fmt::Console) -> Result<()> {
+    let repo = sage_git::Repo::open()?;
+    let mut graph = load_graph(&repo, console)?;
+    let branch = repo.get_current_branch()?;
+
+    adopt_branch(&repo, &mut graph, &branch, parent)?;
+    graph.save(&repo)?;
+
+    console.message(
+        MessageType::Success,
+        &format!(
+            "Adopted '{}' as a child of '{}'",
+            branch.bright_blue(),
+            parent.bright_blue()
+        ),
+    )?;
+    Ok(())
+}
+
+/// Track `branch` under `parent`, joining `parent`'s stack when it has one.
+pub fn adopt_branch(
+    repo: &sage_git::Repo,
+    graph: &mut SageGraph,
+    branch: &str,
+    parent: &str,
+) -> Result<()> {
+    if branch == parent {
+        bail!("Cannot adopt '{branch}' onto itself");
+    }
+    if let Some(info) = graph.get_info(branch) {
+        bail!(
+            "'{branch}' is already tracked under '{}'; untrack it first",
+            info.parent
+        );
+    }
+    if !graph.is_tracked(parent) {
+        bail!("Parent '{parent}' is not tracked by sage");
+    }
+
+    match graph.stack_name_for_branch(parent).cloned() {
+        Some(stack) => graph.add_to_stack(repo, &stack, parent, branch.to_owned()),
+        None => graph.add_loose_branch(repo, branch.to_owned(), parent.to_owned()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sage_git::testing::TestRepo;
+
+    #[test]
+    fn adopts_branch_onto_loose_parent() -> Result<()> {
+        let repo = TestRepo::builder().with_initial_commit().build()?;
+        let mut graph = SageGraph::load(&repo)?;
+
+        repo.run_git(["checkout", "-b", "base"])?;
+        graph.add_loose_branch(&repo, "base".to_owned(), "main".to_owned())?;
+        repo.run_git(["checkout", "-b", "feature"])?;
+
+        adopt_branch(&repo, &mut graph, "feature", "base")?;
+        assert!(graph.is_loose("feature"));
+        let info = graph.get_info("feature").expect("feature tracked");
+        assert_eq!(info.parent, "base");
+        assert_eq!(info.depth, graph.get_info("base").expect("base").depth + 1);
+        Ok(())
+    }
+
+    #[test]
+    fn adopts_branch_into_parent_stack() -> Result<()> {
+        let repo = TestRepo::builder().with_initial_commit().build()?;
+        let mut graph = SageGraph::load(&repo)?;
+
+        repo.run_git(["checkout", "-b", "feat/base"])?;
+        graph.create_stack(
+            &repo,
+            "feat".to_owned(),
+            "feat/base".to_owned(),
+            "main".to_owned(),
+        )?;
+        repo.run_git(["checkout", "-b", "feat/child"])?;
+
+        adopt_branch(&repo, &mut graph, "feat/child", "feat/base")?;
+        assert_eq!(
+            graph
+                .stack_name_for_branch("feat/child")
+                .map(String::as_str),
+            Some("feat")
+        );
+        assert!(graph.in_same_stack("feat/base", "feat/child"));
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_tracked_branch_and_untracked_parent() -> Result<()> {
+        let repo = TestRepo::builder().with_initial_commit().build()?;
+        let mut graph = SageGraph::load(&repo)?;
+
+        repo.run_git(["checkout", "-b", "feature"])?;
+        graph.add_loose_branch(&repo, "feature".to_owned(), "main".to_owned())?;
+        repo.run_git(["checkout", "-b", "other"])?;
+
+        let err = adopt_branch(&repo, &mut graph, "feature", "other").unwrap_err();
+        assert!(
+            err.to_string().contains("already tracked under 'main'"),
+            "{err}"
+        );
+        assert_eq!(graph.get_info("feature").expect("feature").parent, "main");
+
+        let err = adopt_branch(&repo, &mut graph, "other", "missing").unwrap_err();
+        assert!(err.to_string().contains("not tracked"), "{err}");
+        assert!(!graph.is_tracked("other"));
+        Ok(())
+    }
+}
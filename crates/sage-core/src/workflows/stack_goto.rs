@@ -0,0 +1,136 @@
+use anyhow::{Result, bail};
+use colored::Colorize;
+use sage_fmt::MessageType;
+use sage_graph::SageGraph;
+
+use crate::load_graph;
+
+/// Switch to `branch` within the current stack, or pick one when it's missing or outside the stack.
+pub fn stack_goto(branch: Option<&str>, console: &sage_fmt::Console) -> Result<()> {
+    let repo = sage_git::Repo::open()?;
+    let graph = load_graph(&repo, console)?;
+    let current = repo.get_current_branch()?;
+
+    let Some(stack) = graph.stack_for_branch(&current) else {
+        bail!("'{current}' is not part of a stack");
+    };
+    let branches = stack.descendants(&stack.root);
+
+    let target = match branch {
+        Some(branch) if branches.iter().any(|name| name == branch) => branch.to_string(),
+        requested => {
+            if let Some(branch) = requested {
+                if !console.is_interactive() {
+                    bail!("'{branch}' is not in stack '{}'", stack.name);
+                }
+                console.message(
+                    MessageType::Warning,
+                    &format!("'{branch}' is not in stack '{}'", stack.name),
+                )?;
+            }
+            match pick_stack_branch(&repo, &branches, &current, console)? {
+                Some(branch) => branch,
+                None => return Ok(()),
+            }
+        }
+    };
+
+    if target == current {
+        console.message(MessageType::Info, "Already on the current branch")?;
+        return Ok(());
+    }
+
+    if repo.is_dirty()?
+        && !console.confirm(
+            &format!("Carry uncommitted changes over to '{target}'?"),
+            true,
+        )?
+    {
+        return Ok(());
+    }
+
+    goto_branch(&repo, &graph, &target)?;
+    console.message(
+        MessageType::Success,
+        &format!("Switched to '{}'", target.bright_blue()),
+    )?;
+    Ok(())
+}
+
+/// Switch to `target`, which must be in the same stack as the current branch.
+pub fn goto_branch(repo: &sage_git::Repo, graph: &SageGraph, target: &str) -> Result<()> {
+    let current = repo.get_current_branch()?;
+    if !graph.in_same_stack(&current, target) {
+        bail!("'{target}' is not in the same stack as '{current}'");
+    }
+    repo.switch_branch(target)
+}
+
+/// Let the user choose a stack branch other than `current`, in stack order.
+fn pick_stack_branch(
+    repo: &sage_git::Repo,
+    branches: &[String],
+    current: &str,
+    console: &sage_fmt::Console,
+) -> Result<Option<String>> {
+    if !console.is_interactive() {
+        bail!("No branch given; pass a branch from the stack when not running in a terminal");
+    }
+
+    let candidates: Vec<String> = branches
+        .iter()
+        .filter(|branch| *branch != current)
+        .cloned()
+        .collect();
+    if candidates.is_empty() {
+        console.message(MessageType::Info, "No other branches in this stack")?;
+        return Ok(None);
+    }
+
+    let choice = console
+        .select("Go to")
+        .items(candidates.iter().cloned())
+        .fuzzy_search(true)
+        .with_preview(|branch| {
+            repo.last_commit(branch)
+                .ok()
+                .map(|commit| format!("{} {}", commit.short_id, commit.summary))
+        })
+        .interact()?;
+
+    Ok(choice.map(|index| candidates[index].clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sage_git::testing::TestRepo;
+
+    #[test]
+    fn goto_switches_to_sibling_in_stack() -> Result<()> {
+        let repo = TestRepo::builder().with_initial_commit().build()?;
+        let mut graph = SageGraph::load(&repo)?;
+
+        repo.run_git(["checkout", "-b", "feat/base"])?;
+        graph.create_stack(
+            &repo,
+            "feat".to_owned(),
+            "feat/base".to_owned(),
+            "main".to_owned(),
+        )?;
+        for sibling in ["feat/one", "feat/two"] {
+            repo.run_git(["checkout", "-b", sibling, "feat/base"])?;
+            graph.add_to_stack(&repo, "feat", "feat/base", sibling.to_owned())?;
+        }
+        repo.run_git(["checkout", "main"])?;
+        repo.run_git(["checkout", "-b", "loose"])?;
+        repo.run_git(["checkout", "feat/one"])?;
+
+        goto_branch(&repo, &graph, "feat/two")?;
+        assert_eq!(repo.get_current_branch()?, "feat/two");
+
+        assert!(goto_branch(&repo, &graph, "loose").is_err());
+        assert_eq!(repo.get_current_branch()?, "feat/two");
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+use anyhow::{Result, bail};
+use colored::Colorize;
+use sage_fmt::MessageType;
+use sage_graph::SageGraph;
+
+use crate::{goto_branch, load_graph};
+
+/// Where `next`/`prev` lead from a branch in a stack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StackStep {
+    Move(String),
+    /// The branch has several children; one has to be picked.
+    Choose(Vec<String>),
+    /// No child to move up to.
+    Top,
+    /// The branch is the stack's root.
+    Bottom,
+}
+
+/// Move to the current branch's child in its stack.
+pub fn stack_next(console: &sage_fmt::Console) -> Result<()> {
+    navigate(console, next_in_stack)
+}
+
+/// Move to the current branch's parent in its stack.
+pub fn stack_prev(console: &sage_fmt::Console) -> Result<()> {
+    navigate(console, prev_in_stack)
+}
+
+fn navigate(
+    console: &sage_fmt::Console,
+    step: fn(&SageGraph, &str) -> Result<StackStep>,
+) -> Result<()> {
+    let repo = sage_git::Repo::open()?;
+    let graph = load_graph(&repo, console)?;
+    let current = repo.get_current_branch()?;
+
+    let Some(target) = resolve_step(step(&graph, &current)?, console)? else {
+        return Ok(());
+    };
+
+    goto_branch(&repo, &graph, &target)?;
+    console.message(
+        MessageType::Success,
+        &format!("Switched to '{}'", target.bright_blue()),
+    )?;
+    Ok(())
+}
+
+pub fn next_in_stack(graph: &SageGraph, branch: &str) -> Result<StackStep> {
+    let Some(stack) = graph.stack_for_branch(branch) else {
+        bail!("'{branch}' is not part of a stack");
+    };
+
+    Ok(match stack.children(branch) {
+        [] => StackStep::Top,
+        [child] => StackStep::Move(child.clone()),
+        children => StackStep::Choose(children.to_vec()),
+    })
+}
+
+pub fn prev_in_stack(graph: &SageGraph, branch: &str) -> Result<StackStep> {
+    let Some(stack) = graph.stack_for_branch(branch) else {
+        bail!("'{branch}' is not part of a stack");
+    };
+
+    // The root's parent lives outside the stack.
+    Ok(match stack.parent_id(branch) {
+        Some(parent) if parent != branch && stack.contains(parent) => {
+            StackStep::Move(parent.clone())
+        }
+        _ => StackStep::Bottom,
+    })
+}
+
+/// The branch to switch to, or `None` when there's nowhere to go.
+fn resolve_step(step: StackStep, console: &sage_fmt::Console) -> Result<Option<String>> {
+    match step {
+        StackStep::Move(branch) => Ok(Some(branch)),
+        StackStep::Top => {
+            console.message(MessageType::Info, "Already at the top of the stack")?;
+            Ok(None)
+        }
+        StackStep::Bottom => {
+            console.message(MessageType::Info, "Already at the bottom of the stack")?;
+            Ok(None)
+        }
+        StackStep::Choose(children) => {
+            if !console.is_interactive() {
+                bail!(
+                    "Several branches build on this one ({}); use `sg stack goto <branch>`",
+                    children.join(", ")
+                );
+            }
+            let choice = console
+                .select("Next branch")
+                .items(children.iter().cloned())
+                .interact()?;
+            Ok(choice.map(|index| children[index].clone()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sage_git::testing::TestRepo;
+
+    fn three_branch_stack(repo: &TestRepo) -> Result<SageGraph> {
+        let mut graph = SageGraph::load(repo)?;
+        repo.run_git(["checkout", "-b", "feat/base"])?;
+        graph.create_stack(
+            repo,
+            "feat".to_owned(),
+            "feat/base".to_owned(),
+            "main".to_owned(),
+        )?;
+        repo.run_git(["checkout", "-b", "feat/mid"])?;
+        graph.add_to_stack(repo, "feat", "feat/base", "feat/mid".to_owned())?;
+        repo.run_git(["checkout", "-b", "feat/top"])?;
+        graph.add_to_stack(repo, "feat", "feat/mid", "feat/top".to_owned())?;
+        Ok(graph)
+    }
+
+    #[test]
+    fn next_and_prev_follow_the_child_chain() -> Result<()> {
+        let repo = TestRepo::builder().with_initial_commit().build()?;
+        let graph = three_branch_stack(&repo)?;
+
+        let move_to = |branch: &str| StackStep::Move(branch.to_owned());
+        assert_eq!(next_in_stack(&graph, "feat/base")?, move_to("feat/mid"));
+        assert_eq!(next_in_stack(&graph, "feat/mid")?, move_to("feat/top"));
+        assert_eq!(next_in_stack(&graph, "feat/top")?, StackStep::Top);
+
+        assert_eq!(prev_in_stack(&graph, "feat/top")?, move_to("feat/mid"));
+        assert_eq!(prev_in_stack(&graph, "feat/mid")?, move_to("feat/base"));
+        assert_eq!(prev_in_stack(&graph, "feat/base")?, StackStep::Bottom);
+
+        assert!(next_in_stack(&graph, "main").is_err());
+
+        let console = sage_fmt::Console::new().with_writer(std::io::sink());
+        assert_eq!(resolve_step(StackStep::Top, &console)?, None);
+        assert_eq!(
+            resolve_step(move_to("feat/mid"), &console)?,
+            Some("feat/mid".to_owned())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn multiple_children_need_a_choice() -> Result<()> {
+        let repo = TestRepo::builder().with_initial_commit().build()?;
+        let mut graph = three_branch_stack(&repo)?;
+        repo.run_git(["checkout", "-b", "feat/side", "feat/mid"])?;
+        graph.add_to_stack(&repo, "feat", "feat/mid", "feat/side".to_owned())?;
+
+        let step = next_in_stack(&graph, "feat/mid")?;
+        assert_eq!(
+            step,
+            StackStep::Choose(vec!["feat/top".to_owned(), "feat/side".to_owned()])
+        );
+
+        // Without a terminal there's no picker, so the choices come back as an error.
+        let console = sage_fmt::Console::new().with_writer(std::io::sink());
+        let err = resolve_step(step, &console).unwrap_err();
+        assert!(err.to_string().contains("feat/top, feat/side"), "{err}");
+        Ok(())
+    }
+}
@@ -0,0 +1,195 @@
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+
+use sage_graph::{Discrepancy, SageGraph};
+
+use crate::{load_graph, print_json};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BranchSyncState {
+    pub name: String,
+    pub parent: Option<String>,
+    pub depth: usize,
+    pub current: bool,
+    /// `None` when the branch has no upstream to compare against.
+    pub ahead: Option<usize>,
+    pub behind: Option<usize>,
+    pub needs_restack: bool,
+    pub pr_number: Option<u64>,
+}
+
+pub fn stack_status(json: bool, console: &sage_fmt::Console) -> Result<()> {
+    let repo = sage_git::Repo::open()?;
+    let graph = load_graph(&repo, console)?;
+    let current_branch = repo.get_current_branch()?;
+
+    let states = build_stack_status(&repo, &graph, &current_branch)?;
+
+    if json {
+        return print_json("stack status", &states);
+    }
+
+    match graph.stack_name_for_branch(&current_branch) {
+        Some(stack) => println!("{} {}", "Stack:".bright_green().bold(), stack.yellow()),
+        None => console.message(
+            sage_fmt::MessageType::Info,
+            "Current branch is not part of a stack",
+        )?,
+    }
+
+    for state in &states {
+        print_state(state);
+    }
+
+    Ok(())
+}
+
+/// Collects the sync state of every branch in the current branch's stack.
+///
+/// When the current branch isn't part of a stack, only its own state is returned.
+pub fn build_stack_status(
+    repo: &sage_git::Repo,
+    graph: &SageGraph,
+    current_branch: &str,
+) -> Result<Vec<BranchSyncState>> {
+    let branches = match graph.stack_for_branch(current_branch) {
+        Some(stack) => stack.descendants(&stack.root),
+        None => vec![current_branch.to_string()],
+    };
+
+    let discrepancies = graph.validate_against_repo(repo)?;
+    let root_depth = branches
+        .first()
+        .and_then(|root| graph.get_info(root))
+        .map_or(0, |info| info.depth);
+
+    let mut states = Vec::with_capacity(branches.len());
+    for name in branches {
+        let info = graph.get_info(&name);
+        let (ahead, behind) = match repo.upstream_ahead_behind(&name)? {
+            Some((ahead, behind)) => (Some(ahead), Some(behind)),
+            None => (None, None),
+        };
+        let needs_restack = discrepancies.iter().any(|discrepancy| {
+            matches!(discrepancy, Discrepancy::ParentNotAncestor { branch, .. } if *branch == name)
+        });
+
+        states.push(BranchSyncState {
+            parent: info
+                .map(|info| info.parent.clone())
+                .filter(|parent| *parent != name),
+            depth: info.map_or(0, |info| info.depth.saturating_sub(root_depth)),
+            current: name == current_branch,
+            ahead,
+            behind,
+            needs_restack,
+            pr_number: info.and_then(|info| info.pr_number),
+            name,
+        });
+    }
+
+    Ok(states)
+}
+
+pub(crate) fn print_state(state: &BranchSyncState) {
+    let indent = "  ".repeat(state.depth);
+
+    if state.current {
+        print!(
+            " {indent}{} {}",
+            "●".bright_green(),
+            state.name.bold().bright_yellow()
+        );
+    } else {
+        print!(" {indent}{} {}", "○".dimmed(), state.name);
+    }
+
+    match (state.ahead, state.behind) {
+        (Some(ahead), Some(behind)) => {
+            if ahead > 0 {
+                print!("{}", format!(" ↑{ahead}").bright_green().bold());
+            }
+            if behind > 0 {
+                print!("{}", format!(" ↓{behind}").bright_red().bold());
+            }
+            if ahead == 0 && behind == 0 {
+                print!(" {}", "(synced)".dimmed());
+            }
+        }
+        _ => print!(" {}", "(no remote)".dimmed()),
+    }
+
+    if state.needs_restack {
+        print!(" {}", "needs restack".bright_yellow());
+    }
+
+    if let Some(pr) = state.pr_number {
+        print!(" {}", format!("#{pr}").bright_blue());
+    }
+
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sage_git::testing::TestRepo;
+
+    #[test]
+    fn reports_restack_for_diverged_stack_branch() -> Result<()> {
+        let repo = TestRepo::builder().with_initial_commit().build()?;
+        let mut graph = SageGraph::load(&repo)?;
+
+        repo.run_git(["checkout", "-b", "feat/base"])?;
+        repo.write("base.txt", "base\n")?;
+        repo.commit_all("base work")?;
+        repo.run_git(["checkout", "-b", "feat/child"])?;
+        repo.write("child.txt", "child\n")?;
+        repo.commit_all("child work")?;
+
+        graph.create_stack(
+            &repo,
+            "feat".to_owned(),
+            "feat/base".to_owned(),
+            "main".to_owned(),
+        )?;
+        graph.add_to_stack(&repo, "feat", "feat/base", "feat/child".to_owned())?;
+
+        repo.run_git(["checkout", "feat/base"])?;
+        repo.write("base.txt", "base amended\n")?;
+        repo.commit_all("more base work")?;
+
+        let states = build_stack_status(&repo, &graph, "feat/base")?;
+        let names: Vec<&str> = states.iter().map(|state| state.name.as_str()).collect();
+        assert_eq!(names, vec!["feat/base", "feat/child"]);
+
+        let base = &states[0];
+        assert!(base.current);
+        assert!(!base.needs_restack);
+        assert_eq!(base.parent.as_deref(), Some("main"));
+        assert_eq!(base.depth, 0);
+        assert_eq!((base.ahead, base.behind), (None, None));
+
+        let child = &states[1];
+        assert!(!child.current);
+        assert!(child.needs_restack);
+        assert_eq!(child.depth, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn falls_back_to_single_branch_outside_stack() -> Result<()> {
+        let repo = TestRepo::builder().with_initial_commit().build()?;
+        let graph = SageGraph::load(&repo)?;
+
+        let states = build_stack_status(&repo, &graph, "main")?;
+        assert_eq!(states.len(), 1);
+        assert_eq!(states[0].name, "main");
+        assert_eq!(states[0].parent, None);
+        assert!(states[0].current);
+
+        Ok(())
+    }
+}
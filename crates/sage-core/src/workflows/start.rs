@@ -1,7 +1,7 @@
 use anyhow::{Result, bail};
 use sage_fmt::MessageType;
 
-use crate::fetch_if_stale;
+use crate::{fetch_if_stale, load_graph};
 
 #[derive(Debug, Clone)]
 pub struct StartOptions {
@@ -13,7 +13,7 @@ pub struct StartOptions {
 
 pub fn start(options: &StartOptions, console: &sage_fmt::Console) -> Result<()> {
     let repo = sage_git::Repo::open()?;
-    let mut graph = sage_graph::SageGraph::load(&repo)?;
+    let mut graph = load_graph(&repo, console)?;
 
     fetch_if_stale(&repo, console)?;
 
@@ -1,17 +1,56 @@
-use anyhow::{Result, bail};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
 use colored::Colorize;
+use sage_config::ConfigManager;
 use sage_fmt::MessageType;
 
-use crate::fuzzy_match_branch;
+use crate::{fuzzy_match_branch, load_graph, print_json};
+
+#[derive(Debug, Clone, Default)]
+pub struct WorkOptions {
+    /// With `fuzzy` set and no branch, the branch is picked interactively.
+    pub branch: Option<String>,
+    pub parent: Option<String>,
+    pub fuzzy: bool,
+    pub push: bool,
+    pub root: bool,
+    pub worktree: bool,
+    pub json: bool,
+    /// Add a newly created branch to the stack graph; `None` uses `general.auto_track`.
+    pub track: Option<bool>,
+    /// Fail instead of switching with uncommitted changes; `None` uses
+    /// `general.abort_on_dirty`.
+    pub abort_on_dirty: Option<bool>,
+}
+
+pub fn work(mut options: WorkOptions, console: &sage_fmt::Console) -> Result<()> {
+    if options.branch.is_none() {
+        if !options.fuzzy {
+            bail!("A branch name is required");
+        }
+        match pick_branch(console)? {
+            Some(branch) => options.branch = Some(branch),
+            None => return Ok(()),
+        }
+    }
+
+    if options.worktree {
+        return work_in_worktree(options, console);
+    }
+
+    let WorkOptions {
+        branch,
+        parent,
+        fuzzy,
+        push,
+        root,
+        track,
+        abort_on_dirty,
+        ..
+    } = options;
+    let branch = branch.unwrap_or_default();
 
-pub fn work(
-    branch: String,
-    parent: Option<String>,
-    fuzzy: bool,
-    push: bool,
-    root: bool,
-    console: &sage_fmt::Console,
-) -> Result<()> {
     let mut repo = sage_git::Repo::open()?;
     let current_branch = repo.get_current_branch()?;
 
@@ -20,7 +59,7 @@ pub fn work(
         return Ok(());
     }
 
-    let mut graph = sage_graph::SageGraph::load(&repo)?;
+    ensure_clean_tree(&repo, resolve_abort_on_dirty(abort_on_dirty)?)?;
 
     if repo.has_branch(branch.to_string())? {
         repo.switch_branch(&branch)?;
@@ -49,24 +88,9 @@ pub fn work(
         }
     }
 
-    if root {
-        let default_branch = repo.get_default_branch()?.replace("origin/", "");
-        repo.switch_branch(&default_branch)?;
-        println!("Switched to default branch");
-    }
-
-    if let Some(parent) = parent {
-        if !repo.has_branch(parent.to_string())? {
-            bail!("Parent branch not found");
-        }
-        repo.switch_branch(&parent)?;
-    }
-
-    let current_parent = repo.get_current_branch()?;
-
-    repo.create_branch(&branch)?;
-    console.message(MessageType::Success, "Created branch")?;
+    create_branch(&repo, &branch, parent, root, track, console)?;
     repo.switch_branch(&branch)?;
+    console.message(MessageType::Success, "Created branch")?;
     console.message(
         MessageType::Success,
         &format!("Switched to '{}'", branch.bright_blue()),
@@ -77,8 +101,619 @@ pub fn work(
         console.message(MessageType::Success, "Set upstream tracking")?;
     }
 
-    graph.add_loose_branch(&repo, branch.clone(), current_parent)?;
+    Ok(())
+}
+
+/// Create `branch` without checking it out and track it under its parent.
+///
+/// The parent is `parent` when given, the default branch with `root`, or else detected
+/// from the current branch and confirmed with the user.
+fn create_branch(
+    repo: &sage_git::Repo,
+    branch: &str,
+    parent: Option<String>,
+    root: bool,
+    track: Option<bool>,
+    console: &sage_fmt::Console,
+) -> Result<()> {
+    let mut graph = load_graph(repo, console)?;
+
+    let (base, stack) = match parent {
+        Some(parent) => {
+            if !repo.has_branch(parent.to_string())? {
+                bail!("Parent branch not found");
+            }
+            (parent, None)
+        }
+        None if root => (repo.get_default_branch()?.replace("origin/", ""), None),
+        None => {
+            let choice = detect_parent(repo, &graph, &repo.get_current_branch()?)?;
+            confirm_parent(&choice, branch, console)?;
+            match choice {
+                ParentChoice::Stack { stack, parent, .. } => (parent, Some(stack)),
+                choice => (choice.parent().to_string(), None),
+            }
+        }
+    };
+
+    repo.create_branch_from(branch, &base)?;
+    if track_new_branch(
+        repo,
+        &mut graph,
+        branch,
+        base,
+        stack.as_deref(),
+        resolve_track(track)?,
+    )? {
+        graph.save(repo)?;
+    }
+    Ok(())
+}
+
+fn resolve_track(track: Option<bool>) -> Result<bool> {
+    match track {
+        Some(track) => Ok(track),
+        None => Ok(ConfigManager::load()
+            .context("Failed to load configuration")?
+            .get()
+            .general
+            .auto_track),
+    }
+}
+
+fn resolve_abort_on_dirty(abort_on_dirty: Option<bool>) -> Result<bool> {
+    match abort_on_dirty {
+        Some(abort_on_dirty) => Ok(abort_on_dirty),
+        None => Ok(ConfigManager::load()
+            .context("Failed to load configuration")?
+            .get()
+            .general
+            .abort_on_dirty),
+    }
+}
+
+/// Fails on uncommitted changes when `abort_on_dirty` is set, leaving them untouched.
+fn ensure_clean_tree(repo: &sage_git::Repo, abort_on_dirty: bool) -> Result<()> {
+    if abort_on_dirty && repo.is_dirty()? {
+        bail!("Working tree has uncommitted changes; commit or stash them before switching");
+    }
+    Ok(())
+}
+
+/// Record a freshly created `branch` under `parent`, in `stack` when given.
+///
+/// Does nothing when `track` is off. Returns whether the graph changed.
+fn track_new_branch(
+    repo: &sage_git::Repo,
+    graph: &mut sage_graph::SageGraph,
+    branch: &str,
+    parent: String,
+    stack: Option<&str>,
+    track: bool,
+) -> Result<bool> {
+    if !track {
+        return Ok(false);
+    }
+    match stack {
+        Some(stack) => graph.add_to_stack(repo, stack, &parent, branch.to_owned())?,
+        None => graph.add_loose_branch(repo, branch.to_owned(), parent)?,
+    }
+    Ok(true)
+}
+
+/// Where a new branch goes when `--parent` isn't given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParentChoice {
+    /// On the default branch; the new branch starts fresh from it.
+    DefaultBranch(String),
+    /// On a stack member; the new branch extends that stack. `chain` runs from
+    /// the stack's base to `parent`.
+    Stack {
+        stack: String,
+        parent: String,
+        chain: Vec<String>,
+    },
+    /// Any other branch; the new branch is tracked as a loose child of it.
+    Current(String),
+}
+
+impl ParentChoice {
+    pub fn parent(&self) -> &str {
+        match self {
+            ParentChoice::DefaultBranch(parent)
+            | ParentChoice::Stack { parent, .. }
+            | ParentChoice::Current(parent) => parent,
+        }
+    }
+}
+
+pub fn detect_parent(
+    repo: &sage_git::Repo,
+    graph: &sage_graph::SageGraph,
+    current_branch: &str,
+) -> Result<ParentChoice> {
+    let default_branch = repo.get_default_branch()?.replace("origin/", "");
+    if current_branch == default_branch {
+        return Ok(ParentChoice::DefaultBranch(default_branch));
+    }
+
+    if let (Some(name), Some(stack)) = (
+        graph.stack_name_for_branch(current_branch),
+        graph.stack_for_branch(current_branch),
+    ) {
+        let mut chain = stack.ancestors(current_branch);
+        chain.reverse();
+        chain.push(current_branch.to_string());
+        return Ok(ParentChoice::Stack {
+            stack: name.clone(),
+            parent: current_branch.to_string(),
+            chain,
+        });
+    }
+
+    Ok(ParentChoice::Current(current_branch.to_string()))
+}
+
+fn confirm_parent(choice: &ParentChoice, branch: &str, console: &sage_fmt::Console) -> Result<()> {
+    let prompt = match choice {
+        ParentChoice::DefaultBranch(_) => return Ok(()),
+        ParentChoice::Stack { stack, chain, .. } => format!(
+            "Add '{}' to stack '{}' on top of {}?",
+            branch.bright_blue(),
+            stack.yellow(),
+            chain.join(" → ")
+        ),
+        ParentChoice::Current(parent) => format!(
+            "Create '{}' on top of '{}'?",
+            branch.bright_blue(),
+            parent.yellow()
+        ),
+    };
+
+    if !console.confirm(&prompt, true)? {
+        bail!("Aborted; pass --parent to choose a different parent");
+    }
+    Ok(())
+}
+
+/// Delete a local branch and stop tracking it, switching away first if it's checked out.
+pub fn delete_branch(branch: &str, force: bool, console: &sage_fmt::Console) -> Result<()> {
+    let repo = sage_git::Repo::open()?;
+    let mut graph = load_graph(&repo, console)?;
+
+    let last_commit = remove_branch(&repo, &mut graph, branch, force, console)?;
     graph.save(&repo)?;
 
+    console.message(
+        MessageType::Success,
+        &format!(
+            "Deleted '{}' (was {})",
+            repo.remove_ref(branch).bright_blue(),
+            last_commit.short_id.dimmed()
+        ),
+    )?;
+    console.message(
+        MessageType::Info,
+        &format!(
+            "Recreate it with: git branch {} {}",
+            repo.remove_ref(branch),
+            last_commit.short_id
+        ),
+    )
+}
+
+/// Returns the deleted branch's tip so it can be recreated.
+fn remove_branch(
+    repo: &sage_git::Repo,
+    graph: &mut sage_graph::SageGraph,
+    branch: &str,
+    force: bool,
+    console: &sage_fmt::Console,
+) -> Result<sage_git::CommitInfo> {
+    let branch = repo.remove_ref(branch);
+    if !repo.has_branch(branch.clone())? {
+        bail!("Branch '{branch}' not found");
+    }
+
+    let default_branch = repo
+        .get_default_branch()
+        .map(|name| name.replace("origin/", ""))
+        .unwrap_or_else(|_| "main".to_string());
+    if branch == default_branch {
+        bail!("Refusing to delete the default branch '{branch}'");
+    }
+
+    let merged =
+        repo.has_branch(default_branch.clone())? && repo.is_ancestor(&branch, &default_branch)?;
+    if !merged
+        && !force
+        && !console.confirm(
+            &format!("'{branch}' has commits not in '{default_branch}'. Delete anyway?"),
+            false,
+        )?
+    {
+        bail!("Aborted; pass --force to delete unmerged branches");
+    }
+
+    let last_commit = repo.last_commit(&branch)?;
+    let parent = graph
+        .get_info(&branch)
+        .map(|info| info.parent.clone())
+        .filter(|parent| *parent != branch && repo.has_branch(parent.clone()).unwrap_or(false))
+        .unwrap_or(default_branch);
+
+    // Fails if tracked branches still build on this one, before anything is deleted.
+    graph.untrack_branch(&branch)?;
+
+    if repo.get_current_branch()? == branch {
+        repo.switch_branch(&parent)?;
+        console.message(
+            MessageType::Info,
+            &format!("Switched to '{}'", parent.bright_blue()),
+        )?;
+    }
+    repo.delete_branch(&branch, true)?;
+
+    Ok(last_commit)
+}
+
+/// Let the user choose a local branch to switch to, showing each branch's last commit.
+fn pick_branch(console: &sage_fmt::Console) -> Result<Option<String>> {
+    let repo = sage_git::Repo::open()?;
+    choose_branch(&repo, console)
+}
+
+/// The default branch is preselected, and chosen outright when there's no terminal.
+fn choose_branch(repo: &sage_git::Repo, console: &sage_fmt::Console) -> Result<Option<String>> {
+    let current = repo.get_current_branch()?;
+    let candidates = pick_candidates(repo.list_branches()?, &current);
+    if candidates.is_empty() {
+        console.message(MessageType::Info, "No other local branches")?;
+        return Ok(None);
+    }
+
+    let default_branch = repo.get_default_branch()?.replace("origin/", "");
+    let select = console
+        .select("Switch to")
+        .items(candidates.iter().cloned())
+        .fuzzy_search(true)
+        .with_preview(|branch| {
+            repo.last_commit(branch)
+                .ok()
+                .map(|commit| format!("{} {}", commit.short_id, commit.summary))
+        });
+    let select = match select.select_by(|branch| branch == default_branch) {
+        Some(index) => select.default_index(index),
+        None if !console.is_interactive() => {
+            bail!("No branch given; pass a branch name when not running in a terminal")
+        }
+        None => select,
+    };
+
+    Ok(select.interact()?.map(|index| candidates[index].clone()))
+}
+
+/// Local branches offered by the picker: everything but the current branch.
+fn pick_candidates(branches: Vec<String>, current: &str) -> Vec<String> {
+    let mut candidates: Vec<String> = branches
+        .into_iter()
+        .map(|branch| {
+            branch
+                .strip_prefix("refs/heads/")
+                .map(str::to_string)
+                .unwrap_or(branch)
+        })
+        .filter(|branch| branch != current)
+        .collect();
+    candidates.sort();
+    candidates.dedup();
+    candidates
+}
+
+/// Check the branch out in a linked worktree instead of switching the current checkout.
+fn work_in_worktree(options: WorkOptions, console: &sage_fmt::Console) -> Result<()> {
+    let WorkOptions {
+        branch,
+        parent,
+        fuzzy,
+        root,
+        json,
+        track,
+        ..
+    } = options;
+    let branch = branch.unwrap_or_default();
+
+    let repo = sage_git::Repo::open()?;
+
+    let branch = if repo.has_branch(branch.clone())? {
+        branch
+    } else if fuzzy {
+        let branch_list = repo.list_branches()?;
+        match fuzzy_match_branch(&branch, branch_list)? {
+            Some(found) => repo.remove_ref(&found),
+            None => bail!("No local branch found"),
+        }
+    } else {
+        create_branch(&repo, &branch, parent, root, track, console)?;
+        if !json {
+            console.message(MessageType::Success, "Created branch")?;
+        }
+        branch
+    };
+
+    let (path, created) = match repo.worktree_for_branch(&branch)? {
+        Some(existing) => (existing.path, false),
+        None => {
+            let config_manager = ConfigManager::load().context("Failed to load configuration")?;
+            let worktree_dir = config_manager.get().general.worktree_dir.as_deref();
+            let path = worktree_path(&repo.repo_root(), worktree_dir, &branch);
+            repo.worktree_add(&path, &branch)?;
+            (path, true)
+        }
+    };
+
+    if json {
+        let output = serde_json::json!({
+            "branch": branch,
+            "worktree_path": path,
+            "created": created,
+        });
+        return print_json("work", output);
+    }
+
+    let location = path.display().to_string();
+    if created {
+        console.message(
+            MessageType::Success,
+            &format!(
+                "Created worktree for '{}' at {}",
+                branch.bright_blue(),
+                location.bold()
+            ),
+        )?;
+    } else {
+        console.message(
+            MessageType::Info,
+            &format!(
+                "'{}' is already checked out at {}",
+                branch.bright_blue(),
+                location.bold()
+            ),
+        )?;
+    }
+
     Ok(())
 }
+
+/// Worktrees default to a `<repo>-worktrees` directory next to the repository.
+fn worktree_path(repo_root: &Path, worktree_dir: Option<&str>, branch: &str) -> PathBuf {
+    let base = match worktree_dir.map(str::trim).filter(|dir| !dir.is_empty()) {
+        Some(dir) => {
+            let dir = PathBuf::from(dir);
+            if dir.is_absolute() {
+                dir
+            } else {
+                repo_root.join(dir)
+            }
+        }
+        None => {
+            let name = repo_root
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "repo".to_string());
+            repo_root
+                .parent()
+                .unwrap_or(repo_root)
+                .join(format!("{name}-worktrees"))
+        }
+    };
+
+    base.join(branch.replace('/', "-"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sage_git::testing::TestRepo;
+    use sage_graph::SageGraph;
+
+    #[test]
+    fn detect_parent_uses_default_branch_directly() -> Result<()> {
+        let repo = TestRepo::builder().with_initial_commit().build()?;
+        let graph = SageGraph::load(&repo)?;
+
+        let choice = detect_parent(&repo, &graph, "main")?;
+        assert_eq!(choice, ParentChoice::DefaultBranch("main".to_string()));
+        assert_eq!(choice.parent(), "main");
+        Ok(())
+    }
+
+    #[test]
+    fn detect_parent_extends_stack_from_member() -> Result<()> {
+        let repo = TestRepo::builder().with_initial_commit().build()?;
+        let mut graph = SageGraph::load(&repo)?;
+        repo.run_git(["branch", "feat/base"])?;
+        repo.run_git(["branch", "feat/child"])?;
+        graph.create_stack(
+            &repo,
+            "feat".to_owned(),
+            "feat/base".to_owned(),
+            "main".to_owned(),
+        )?;
+        graph.add_to_stack(&repo, "feat", "feat/base", "feat/child".to_owned())?;
+
+        let choice = detect_parent(&repo, &graph, "feat/child")?;
+        assert_eq!(
+            choice,
+            ParentChoice::Stack {
+                stack: "feat".to_string(),
+                parent: "feat/child".to_string(),
+                chain: vec![
+                    "main".to_string(),
+                    "feat/base".to_string(),
+                    "feat/child".to_string(),
+                ],
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn detect_parent_falls_back_to_current_branch() -> Result<()> {
+        let repo = TestRepo::builder().with_initial_commit().build()?;
+        let graph = SageGraph::load(&repo)?;
+        repo.run_git(["branch", "scratch"])?;
+
+        let choice = detect_parent(&repo, &graph, "scratch")?;
+        assert_eq!(choice, ParentChoice::Current("scratch".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn no_track_leaves_graph_unchanged() -> Result<()> {
+        let repo = TestRepo::builder().with_initial_commit().build()?;
+        let mut graph = SageGraph::load(&repo)?;
+        repo.run_git(["checkout", "-b", "feature"])?;
+
+        let changed = track_new_branch(&repo, &mut graph, "feature", "main".into(), None, false)?;
+        assert!(!changed);
+        assert!(!graph.is_tracked("feature"));
+
+        let changed = track_new_branch(&repo, &mut graph, "feature", "main".into(), None, true)?;
+        assert!(changed);
+        assert_eq!(
+            graph.get_info("feature").map(|info| info.parent.as_str()),
+            Some("main")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn create_branch_joins_the_current_stack() -> Result<()> {
+        let repo = TestRepo::builder().with_initial_commit().build()?;
+        let mut graph = SageGraph::load(&repo)?;
+        repo.run_git(["checkout", "-q", "-b", "feat/base"])?;
+        graph.create_stack(
+            &repo,
+            "feat".to_owned(),
+            "feat/base".to_owned(),
+            "main".to_owned(),
+        )?;
+        graph.save(&repo)?;
+
+        // Not a terminal, so the parent confirmation takes its default of "yes".
+        let console = sage_fmt::Console::new().with_writer(std::io::sink());
+        create_branch(&repo, "feat/next", None, false, Some(true), &console)?;
+        create_branch(
+            &repo,
+            "side",
+            Some("main".into()),
+            false,
+            Some(true),
+            &console,
+        )?;
+
+        assert_eq!(repo.get_current_branch()?, "feat/base");
+        let graph = load_graph(&repo, &console)?;
+        assert_eq!(
+            graph.stack_name_for_branch("feat/next").map(String::as_str),
+            Some("feat")
+        );
+        assert_eq!(
+            graph.get_info("side").map(|info| info.parent.as_str()),
+            Some("main")
+        );
+        assert!(graph.stack_name_for_branch("side").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn remove_branch_deletes_ref_and_untracks() -> Result<()> {
+        let repo = TestRepo::builder().with_initial_commit().build()?;
+        let mut graph = SageGraph::load(&repo)?;
+
+        repo.run_git(["checkout", "-b", "feature"])?;
+        repo.write("feature.txt", "feature\n")?;
+        repo.commit_all("feature work")?;
+        graph.add_loose_branch(&repo, "feature".to_owned(), "main".to_owned())?;
+        let tip = repo.last_commit("feature")?;
+
+        // Not a terminal, so the unmerged-branch confirmation falls back to "no".
+        let console = sage_fmt::Console::new().with_writer(std::io::sink());
+        assert!(remove_branch(&repo, &mut graph, "feature", false, &console).is_err());
+        assert!(repo.has_branch("feature".to_owned())?);
+        assert!(graph.is_tracked("feature"));
+
+        let removed = remove_branch(&repo, &mut graph, "feature", true, &console)?;
+        assert_eq!(removed, tip);
+        assert!(!repo.has_branch("feature".to_owned())?);
+        assert!(!graph.is_tracked("feature"));
+        assert_eq!(repo.get_current_branch()?, "main");
+
+        assert!(remove_branch(&repo, &mut graph, "main", true, &console).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn choose_branch_falls_back_to_default_branch() -> Result<()> {
+        let repo = TestRepo::with_feature_branch()?;
+        let console = sage_fmt::Console::new().with_writer(std::io::sink());
+
+        repo.run_git(["checkout", "-q", "feature"])?;
+        assert_eq!(choose_branch(&repo, &console)?.as_deref(), Some("main"));
+
+        // On the default branch itself there's nothing to fall back to.
+        repo.run_git(["checkout", "-q", "main"])?;
+        let err = choose_branch(&repo, &console).expect_err("no default to pick");
+        assert!(err.to_string().contains("pass a branch name"), "{err}");
+        Ok(())
+    }
+
+    #[test]
+    fn pick_candidates_excludes_current_branch() {
+        let branches = vec![
+            "refs/heads/main".to_string(),
+            "feature".to_string(),
+            "bugfix".to_string(),
+        ];
+
+        assert_eq!(
+            pick_candidates(branches, "main"),
+            vec!["bugfix".to_string(), "feature".to_string()]
+        );
+    }
+
+    #[test]
+    fn worktree_path_defaults_next_to_repo() {
+        let path = worktree_path(Path::new("/code/sage"), None, "feat/login");
+        assert_eq!(path, PathBuf::from("/code/sage-worktrees/feat-login"));
+    }
+
+    #[test]
+    fn worktree_path_respects_configured_dir() {
+        let absolute = worktree_path(Path::new("/code/sage"), Some("/tmp/wt"), "fix");
+        assert_eq!(absolute, PathBuf::from("/tmp/wt/fix"));
+
+        let relative = worktree_path(Path::new("/code/sage"), Some("../trees"), "fix");
+        assert_eq!(relative, PathBuf::from("/code/sage/../trees/fix"));
+    }
+
+    #[test]
+    fn dirty_tree_aborts_without_stashing() -> Result<()> {
+        let repo = TestRepo::builder().with_initial_commit().build()?;
+        repo.write("notes.txt", "draft\n")?;
+        repo.commit_all("add notes")?;
+        repo.write("notes.txt", "draft, edited\n")?;
+
+        ensure_clean_tree(&repo, false)?;
+        let error = ensure_clean_tree(&repo, true).expect_err("dirty tree");
+        assert!(error.to_string().contains("uncommitted changes"));
+
+        assert_eq!(repo.unstaged_files()?, vec!["notes.txt"]);
+        assert!(repo.git_stdout(["stash", "list"])?.is_empty());
+
+        repo.commit_all("finish notes")?;
+        ensure_clean_tree(&repo, true)?;
+        Ok(())
+    }
+}
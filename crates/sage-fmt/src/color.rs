@@ -0,0 +1,182 @@
+use crossterm::style::Color;
+
+use crate::supports_color;
+
+/// How many colors the terminal can display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorLevel {
+    None,
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+/// The 16 basic ANSI colors, indexed by their palette number, with typical RGB values.
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::DarkRed, (128, 0, 0)),
+    (Color::DarkGreen, (0, 128, 0)),
+    (Color::DarkYellow, (128, 128, 0)),
+    (Color::DarkBlue, (0, 0, 128)),
+    (Color::DarkMagenta, (128, 0, 128)),
+    (Color::DarkCyan, (0, 128, 128)),
+    (Color::Grey, (192, 192, 192)),
+    (Color::DarkGrey, (128, 128, 128)),
+    (Color::Red, (255, 0, 0)),
+    (Color::Green, (0, 255, 0)),
+    (Color::Yellow, (255, 255, 0)),
+    (Color::Blue, (0, 0, 255)),
+    (Color::Magenta, (255, 0, 255)),
+    (Color::Cyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+impl ColorLevel {
+    /// Detect from the environment, honouring `NO_COLOR`/`FORCE_COLOR` and whether stderr (where status output goes) is a TTY.
+    pub fn detect() -> Self {
+        if !supports_color() {
+            return Self::None;
+        }
+        match Self::from_env(
+            std::env::var("COLORTERM").ok().as_deref(),
+            std::env::var("TERM").ok().as_deref(),
+        ) {
+            // FORCE_COLOR can enable color without a usable $TERM.
+            Self::None => Self::Ansi16,
+            level => level,
+        }
+    }
+
+    pub(crate) fn from_env(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        if let Some(colorterm) = colorterm
+            && matches!(
+                colorterm.to_ascii_lowercase().as_str(),
+                "truecolor" | "24bit"
+            )
+        {
+            return Self::TrueColor;
+        }
+
+        match term.map(str::to_ascii_lowercase).as_deref() {
+            None | Some("") | Some("dumb") => Self::None,
+            Some(term) if term.ends_with("-direct") => Self::TrueColor,
+            Some(term) if term.contains("256color") => Self::Ansi256,
+            Some(_) => Self::Ansi16,
+        }
+    }
+
+    /// The closest color this level can display.
+    pub fn downgrade(self, color: Color) -> Color {
+        match (self, color) {
+            (Self::None | Self::TrueColor, color) => color,
+            (Self::Ansi256, Color::Rgb { r, g, b }) => Color::AnsiValue(rgb_to_ansi256(r, g, b)),
+            (Self::Ansi16, Color::Rgb { r, g, b }) => nearest_ansi16((r, g, b)),
+            (Self::Ansi16, Color::AnsiValue(value)) if value < 16 => ANSI16[value as usize].0,
+            (Self::Ansi16, Color::AnsiValue(value)) => nearest_ansi16(ansi256_to_rgb(value)),
+            (_, color) => color,
+        }
+    }
+}
+
+fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    if r == g && g == b {
+        // The 24-step grayscale ramp (232..=255) is finer than the cube's diagonal.
+        return match r {
+            0..=7 => 16,
+            249..=255 => 231,
+            _ => 232 + ((u16::from(r) - 8) * 24 / 247) as u8,
+        };
+    }
+
+    // Cube steps are 0, 95, 135, 175, 215, 255.
+    let scale = |value: u8| match value {
+        0..48 => 0,
+        48..115 => 1,
+        _ => (value - 35) / 40,
+    };
+    16 + 36 * scale(r) + 6 * scale(g) + scale(b)
+}
+
+fn ansi256_to_rgb(value: u8) -> (u8, u8, u8) {
+    match value {
+        0..=15 => ANSI16[value as usize].1,
+        16..=231 => {
+            let index = value - 16;
+            let level = |step: u8| if step == 0 { 0 } else { 55 + step * 40 };
+            (level(index / 36), level((index / 6) % 6), level(index % 6))
+        }
+        _ => {
+            let gray = 8 + (value - 232) * 10;
+            (gray, gray, gray)
+        }
+    }
+}
+
+fn nearest_ansi16((r, g, b): (u8, u8, u8)) -> Color {
+    let distance = |(cr, cg, cb): (u8, u8, u8)| {
+        let dr = i32::from(r) - i32::from(cr);
+        let dg = i32::from(g) - i32::from(cg);
+        let db = i32::from(b) - i32::from(cb);
+        dr * dr + dg * dg + db * db
+    };
+
+    ANSI16
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb))
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::Reset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_level_from_colorterm_and_term() {
+        let cases = [
+            (
+                Some("truecolor"),
+                Some("xterm-256color"),
+                ColorLevel::TrueColor,
+            ),
+            (Some("24bit"), None, ColorLevel::TrueColor),
+            (None, Some("xterm-direct"), ColorLevel::TrueColor),
+            (None, Some("xterm-256color"), ColorLevel::Ansi256),
+            (Some("yes"), Some("screen-256color"), ColorLevel::Ansi256),
+            (None, Some("xterm"), ColorLevel::Ansi16),
+            (None, Some("linux"), ColorLevel::Ansi16),
+            (None, Some("dumb"), ColorLevel::None),
+            (None, None, ColorLevel::None),
+        ];
+
+        for (colorterm, term, expected) in cases {
+            assert_eq!(
+                ColorLevel::from_env(colorterm, term),
+                expected,
+                "COLORTERM={colorterm:?} TERM={term:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn downgrades_rgb_to_supported_palette() {
+        let orange = Color::Rgb {
+            r: 255,
+            g: 135,
+            b: 0,
+        };
+        assert_eq!(ColorLevel::TrueColor.downgrade(orange), orange);
+        assert_eq!(ColorLevel::Ansi256.downgrade(orange), Color::AnsiValue(208));
+        assert_eq!(ColorLevel::Ansi16.downgrade(orange), Color::Yellow);
+
+        assert_eq!(
+            ColorLevel::Ansi16.downgrade(Color::AnsiValue(33)),
+            Color::Cyan
+        );
+        assert_eq!(
+            ColorLevel::Ansi16.downgrade(Color::AnsiValue(9)),
+            Color::Red
+        );
+        assert_eq!(ColorLevel::Ansi16.downgrade(Color::Cyan), Color::Cyan);
+    }
+}
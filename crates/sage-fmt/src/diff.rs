@@ -0,0 +1,99 @@
+use std::ops::Range;
+
+/// The role of a line in a unified diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DiffLine {
+    /// `diff --git`, `index`, `---`/`+++` and other lines before a file's first hunk.
+    Header,
+    Hunk,
+    Added,
+    Removed,
+    Context,
+}
+
+/// Classifies every line of `diff`, tracking whether we're inside a file header so a
+/// removed line that happens to start with `--` isn't mistaken for one.
+pub(crate) fn classify(diff: &str) -> Vec<(DiffLine, &str)> {
+    let mut in_header = false;
+    diff.lines()
+        .map(|line| {
+            if line.starts_with("diff ") {
+                in_header = true;
+                return (DiffLine::Header, line);
+            }
+            if line.starts_with("@@") {
+                in_header = false;
+                return (DiffLine::Hunk, line);
+            }
+            if in_header || line.starts_with('#') {
+                return (DiffLine::Header, line);
+            }
+            let kind = match line.as_bytes().first() {
+                Some(b'+') => DiffLine::Added,
+                Some(b'-') => DiffLine::Removed,
+                _ => DiffLine::Context,
+            };
+            (kind, line)
+        })
+        .collect()
+}
+
+/// The part of each line that differs once the common prefix and suffix are removed.
+///
+/// Both lines include their `-`/`+` marker, which is never part of the span.
+pub(crate) fn changed_spans(old: &str, new: &str) -> (Range<usize>, Range<usize>) {
+    let (old_body, new_body) = (&old[1..], &new[1..]);
+
+    let prefix = old_body
+        .char_indices()
+        .zip(new_body.chars())
+        .find(|((_, a), b)| a != b)
+        .map_or(old_body.len().min(new_body.len()), |((index, _), _)| index);
+
+    let suffix = old_body[prefix..]
+        .chars()
+        .rev()
+        .zip(new_body[prefix..].chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum::<usize>();
+
+    (
+        1 + prefix..1 + old_body.len() - suffix,
+        1 + prefix..1 + new_body.len() - suffix,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headers_are_tracked_until_the_first_hunk() {
+        let diff = "diff --git a/x b/x\n--- a/x\n+++ b/x\n@@ -1 +1 @@\n--- old\n+new\n ctx\n";
+        let kinds: Vec<DiffLine> = classify(diff).into_iter().map(|(kind, _)| kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                DiffLine::Header,
+                DiffLine::Header,
+                DiffLine::Header,
+                DiffLine::Hunk,
+                DiffLine::Removed,
+                DiffLine::Added,
+                DiffLine::Context,
+            ]
+        );
+    }
+
+    #[test]
+    fn changed_spans_skip_common_prefix_and_suffix() {
+        let (old, new) = changed_spans("-let total = 1;", "+let total = 42;");
+        assert_eq!(&"-let total = 1;"[old], "1");
+        assert_eq!(&"+let total = 42;"[new], "42");
+
+        let (old, new) = changed_spans("-héllo", "+hello");
+        assert_eq!(&"-héllo"[old], "é");
+        assert_eq!(&"+hello"[new], "e");
+    }
+}
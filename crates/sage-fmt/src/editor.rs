@@ -0,0 +1,88 @@
+use std::{env, fs, io::Write, path::PathBuf, process::Command};
+
+use anyhow::{Context, Result, anyhow, bail};
+
+const FALLBACK_EDITORS: &[&str] = &["nano", "vim", "vi"];
+
+/// Opens the user's editor on a temporary file and returns the edited contents.
+#[derive(Debug, Clone)]
+pub struct TextEditor {
+    suffix: String,
+    editor: Option<String>,
+}
+
+impl Default for TextEditor {
+    fn default() -> Self {
+        Self {
+            suffix: ".txt".to_string(),
+            editor: None,
+        }
+    }
+}
+
+impl TextEditor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// File suffix for the temporary file, so editors pick the right syntax.
+    pub fn suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.suffix = suffix.into();
+        self
+    }
+
+    /// Override the editor command instead of reading `$VISUAL`/`$EDITOR`.
+    pub fn editor(mut self, editor: impl Into<String>) -> Self {
+        self.editor = Some(editor.into());
+        self
+    }
+
+    pub fn edit(&self, initial: &str) -> Result<String> {
+        let mut file = tempfile::Builder::new()
+            .prefix("sage-")
+            .suffix(&self.suffix)
+            .tempfile()
+            .context("failed to create temporary file for editor")?;
+        file.write_all(initial.as_bytes())
+            .context("failed to write temporary file for editor")?;
+        file.flush()?;
+
+        let editor = self.resolve_editor()?;
+        let mut parts = editor.split_whitespace();
+        let program = parts
+            .next()
+            .ok_or_else(|| anyhow!("editor command is empty"))?;
+
+        let status = Command::new(program)
+            .args(parts)
+            .arg(file.path())
+            .status()
+            .with_context(|| format!("failed to launch editor '{editor}'"))?;
+
+        if !status.success() {
+            bail!("editor '{editor}' exited with {status}");
+        }
+
+        fs::read_to_string(file.path()).context("failed to read edited file")
+    }
+
+    fn resolve_editor(&self) -> Result<String> {
+        if let Some(editor) = &self.editor {
+            return Ok(editor.clone());
+        }
+
+        for var in ["VISUAL", "EDITOR"] {
+            if let Ok(value) = env::var(var)
+                && !value.trim().is_empty()
+            {
+                return Ok(value);
+            }
+        }
+
+        FALLBACK_EDITORS
+            .iter()
+            .find_map(|name| which::which(name).ok())
+            .map(|path: PathBuf| path.display().to_string())
+            .ok_or_else(|| anyhow!("no editor found; set $EDITOR"))
+    }
+}
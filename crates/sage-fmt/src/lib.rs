@@ -1,27 +1,47 @@
 use anyhow::Result;
 use crossterm::style::{Color, Stylize};
 use std::{
-    io::IsTerminal,
+    io::{BufRead, IsTerminal, Write},
     sync::{Arc, atomic::AtomicBool},
 };
 
+mod color;
+mod diff;
+mod editor;
+mod output;
+mod pager;
+mod select;
 mod symbols;
+mod table;
 mod theme;
+pub use color::ColorLevel;
+use diff::{DiffLine, changed_spans, classify};
+pub use editor::TextEditor;
+use output::Output;
+pub use pager::Pager;
+pub use select::Select;
 use symbols::{Symbols, ascii_mode as symbols_ascii_mode};
-pub use theme::Theme;
+pub use table::{Align, Table};
+pub use theme::{THEME_PRESETS, Theme};
 
 mod progress;
-use progress::PROGRESS_FRAMES;
-pub use progress::ProgressIndicator;
+pub use progress::{ProgressIndicator, SPINNER_STYLES, SpinnerStyle};
+
+mod spinner;
+pub use spinner::SpinnerGuard;
 
 #[allow(dead_code)]
 pub struct Console {
     theme: Theme,
     use_color: bool,
+    color_level: ColorLevel,
     is_ci: bool,
+    /// Status lines and prompts; stdout is left for command data.
+    output: Output,
     needs_clear: Arc<AtomicBool>,
     last_line_blank: Arc<AtomicBool>,
     symbols: Symbols,
+    spinner_style: SpinnerStyle,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -35,18 +55,65 @@ pub enum MessageType {
 #[allow(dead_code)]
 impl Console {
     pub fn new() -> Self {
+        let color_level = ColorLevel::detect();
         Self {
-            theme: Theme::default(),
-            use_color: supports_color(),
+            theme: Theme::default().downgrade(color_level),
+            use_color: color_level != ColorLevel::None,
+            color_level,
             is_ci: is_ci_environment(),
+            output: Output::stderr(),
             needs_clear: Arc::new(AtomicBool::new(false)),
             last_line_blank: Arc::new(AtomicBool::new(false)),
             symbols: Symbols::new(symbols_ascii_mode()),
+            spinner_style: SpinnerStyle::default(),
+        }
+    }
+
+    pub fn with_theme(theme: Theme) -> Self {
+        let console = Self::new();
+        Self {
+            theme: theme.downgrade(console.color_level),
+            ..console
         }
     }
 
+    /// Animation for [`Console::progress`] and [`Console::spinner`].
+    pub fn with_spinner_style(mut self, style: SpinnerStyle) -> Self {
+        self.spinner_style = style;
+        self
+    }
+
+    /// Suppress everything but errors.
+    pub fn quiet(mut self, quiet: bool) -> Self {
+        self.output.set_quiet(quiet);
+        self
+    }
+
+    pub fn is_quiet(&self) -> bool {
+        self.output.is_quiet()
+    }
+
+    /// Write status lines and prompts to `writer` instead of stderr, e.g. to capture them in tests
+    /// or embed them in another UI. The writer isn't a terminal, so output is uncolored and
+    /// spinners and prompts fall back to their non-terminal behavior.
+    pub fn with_writer(mut self, writer: impl Write + Send + 'static) -> Self {
+        let quiet = self.output.is_quiet();
+        self.output = Output::new(writer);
+        self.output.set_quiet(quiet);
+        self.use_color = false;
+        self.color_level = ColorLevel::None;
+        self
+    }
+
+    pub fn color_level(&self) -> ColorLevel {
+        self.color_level
+    }
+
     pub fn header(&self, command: &str) -> Result<()> {
-        println!("sage {}", self.style(command, self.theme.muted));
+        self.emit(
+            false,
+            &format!("sage {}", self.style(command, self.theme.muted)),
+        );
         Ok(())
     }
 
@@ -62,11 +129,151 @@ impl Console {
             MessageType::Info => (self.symbols.dot, self.theme.info),
         };
 
-        println!("  {} {}", self.style(symbol, color), text);
+        self.emit(
+            msg_type == MessageType::Error,
+            &format!("  {} {}", self.style(symbol, color), text),
+        );
         Ok(())
     }
 
-    fn style(&self, text: &str, color: Color) -> String {
+    /// Print multi-line output (e.g. from a hook) indented and dimmed under a message.
+    ///
+    /// Shown in quiet mode when `msg_type` is an error, like [`Console::message`].
+    pub fn details(&self, msg_type: MessageType, text: &str) {
+        for line in text.lines() {
+            self.emit(
+                msg_type == MessageType::Error,
+                &format!("    {}", self.style(line, self.theme.muted)),
+            );
+        }
+    }
+
+    /// Print diff lines, coloring additions, deletions and `@@` headers.
+    pub fn diff(&self, lines: &[String]) {
+        for line in lines {
+            let color = match line.chars().next() {
+                Some('+') => Some(self.theme.success),
+                Some('-') => Some(self.theme.error),
+                Some('@') => Some(self.theme.info),
+                _ => None,
+            };
+            let line = match color {
+                Some(color) => self.style(line, color),
+                None => line.clone(),
+            };
+            self.emit(false, &format!("    {line}"));
+        }
+    }
+
+    /// Colorize a whole unified diff for printing or paging: additions, deletions and `@@`
+    /// headers as in [`Console::diff`], with file headers dimmed.
+    ///
+    /// With `word_diff`, a removed line directly followed by one added line has the changed
+    /// part of each highlighted. Without color (or in CI) the diff comes back unchanged.
+    pub fn render_diff(&self, diff: &str, word_diff: bool) -> String {
+        if !self.use_color || self.is_ci {
+            return diff.to_string();
+        }
+
+        let lines = classify(diff);
+        let mut rendered = String::with_capacity(diff.len());
+        let mut index = 0;
+        while index < lines.len() {
+            let (kind, line) = lines[index];
+            let is_pair = |at: usize| {
+                matches!(lines.get(at), Some((DiffLine::Removed, _)))
+                    && matches!(lines.get(at + 1), Some((DiffLine::Added, _)))
+                    && !matches!(lines.get(at + 2), Some((DiffLine::Added, _)))
+                    && (at == 0 || lines[at - 1].0 != DiffLine::Removed)
+            };
+
+            if word_diff && is_pair(index) {
+                let new = lines[index + 1].1;
+                let (old_span, new_span) = changed_spans(line, new);
+                rendered.push_str(&self.highlight(line, old_span, self.theme.error));
+                rendered.push('\n');
+                rendered.push_str(&self.highlight(new, new_span, self.theme.success));
+                rendered.push('\n');
+                index += 2;
+                continue;
+            }
+
+            let styled = match kind {
+                DiffLine::Header => self.style(line, self.theme.muted),
+                DiffLine::Hunk => self.style(line, self.theme.info),
+                DiffLine::Added => self.style(line, self.theme.success),
+                DiffLine::Removed => self.style(line, self.theme.error),
+                DiffLine::Context => line.to_string(),
+            };
+            rendered.push_str(&styled);
+            rendered.push('\n');
+            index += 1;
+        }
+
+        if !diff.ends_with('\n') {
+            rendered.pop();
+        }
+        rendered
+    }
+
+    fn highlight(&self, line: &str, span: std::ops::Range<usize>, color: Color) -> String {
+        let emphasized = format!("{}", line[span.clone()].with(color).bold().underlined());
+        format!(
+            "{}{emphasized}{}",
+            self.style(&line[..span.start], color),
+            self.style(&line[span.end..], color)
+        )
+    }
+
+    /// Ask a yes/no question, returning `default` without prompting when not interactive.
+    pub fn confirm(&self, prompt: &str, default: bool) -> Result<bool> {
+        if !self.is_interactive() {
+            return Ok(default);
+        }
+
+        let hint = if default { "[Y/n]" } else { "[y/N]" };
+        self.output.write(&format!(
+            "  {} {} {} ",
+            self.style("?", self.theme.primary),
+            prompt,
+            self.style(hint, self.theme.muted)
+        ))?;
+
+        let mut answer = String::new();
+        std::io::stdin().lock().read_line(&mut answer)?;
+        Ok(parse_confirmation(&answer, default))
+    }
+
+    /// Ask for a line of text, returning `default` when the answer is empty or not interactive.
+    pub fn input(&self, prompt: &str, default: &str) -> Result<String> {
+        if !self.is_interactive() {
+            return Ok(default.to_string());
+        }
+
+        self.output.write(&format!(
+            "  {} {} {} ",
+            self.style("?", self.theme.primary),
+            prompt,
+            self.style(&format!("[{default}]"), self.theme.muted)
+        ))?;
+
+        let mut answer = String::new();
+        std::io::stdin().lock().read_line(&mut answer)?;
+        let answer = answer.trim();
+        Ok(if answer.is_empty() { default } else { answer }.to_string())
+    }
+
+    /// Pick one of several items; see [`Select`].
+    pub fn select(&self, prompt: impl Into<String>) -> Select<'_> {
+        Select::new(self, prompt.into())
+    }
+
+    /// Whether we can prompt the user: stdin and the output are terminals and we're not in CI.
+    pub fn is_interactive(&self) -> bool {
+        !self.is_ci && std::io::stdin().is_terminal() && self.output.is_terminal()
+    }
+
+    pub(crate) fn style(&self, text: &str, color: Color) -> String {
         if self.use_color && !self.is_ci {
             format!("{}", text.with(color))
         } else {
@@ -77,8 +284,11 @@ impl Console {
     pub fn progress(&self, message: impl Into<String>) -> ProgressIndicator {
         let message = message.into();
 
-        if self.is_ci || !std::io::stdout().is_terminal() {
-            println!("  {}", message);
+        if self.output.is_quiet() {
+            return ProgressIndicator::noop(self.needs_clear.clone());
+        }
+        if self.is_ci || !self.output.is_terminal() {
+            self.output.line(false, &format!("  {message}"));
             return ProgressIndicator::noop(self.needs_clear.clone());
         }
 
@@ -88,19 +298,85 @@ impl Console {
             message.clone()
         };
 
+        let frames = self.spinner_style.frames(symbols_ascii_mode()).iter();
         let frames: Vec<String> = if self.use_color {
-            PROGRESS_FRAMES
-                .iter()
+            frames
                 .map(|frame| self.style(frame, self.theme.info))
                 .collect()
         } else {
-            PROGRESS_FRAMES
-                .iter()
-                .map(|frame| (*frame).to_string())
-                .collect()
+            frames.map(|frame| (*frame).to_string()).collect()
         };
 
-        ProgressIndicator::spinner(rendered_message, frames, self.needs_clear.clone())
+        ProgressIndicator::spinner(
+            rendered_message,
+            frames,
+            self.output.clone(),
+            self.needs_clear.clone(),
+        )
+    }
+
+    /// Like [`Console::progress`], but resolves into a ✓/✗ line via the returned guard.
+    ///
+    /// In CI or when stderr isn't a terminal this prints one static line instead of animating.
+    pub fn spinner(&self, message: impl Into<String>) -> SpinnerGuard {
+        let message = message.into();
+        let check = self.style(self.symbols.check, self.theme.success);
+        let cross = self.style(self.symbols.cross, self.theme.error);
+
+        let progress = if self.output.is_quiet() {
+            ProgressIndicator::noop(self.needs_clear.clone())
+        } else if self.is_ci || !self.output.is_terminal() {
+            let _ = self.output.write(&self.static_line(&message));
+            ProgressIndicator::noop(self.needs_clear.clone())
+        } else {
+            self.progress(message)
+        };
+
+        SpinnerGuard::new(progress, check, cross, self.output.clone())
+    }
+
+    /// Print a ✓/✗ line for a finished step, appending the error when it failed.
+    pub fn step<T, E: std::fmt::Display>(
+        &self,
+        message: &str,
+        result: &std::result::Result<T, E>,
+    ) -> Result<()> {
+        match result {
+            Ok(_) => self.emit(
+                false,
+                &format!(
+                    "  {} {}",
+                    self.style(self.symbols.check, self.theme.success),
+                    message
+                ),
+            ),
+            Err(err) => self.emit(
+                true,
+                &format!(
+                    "  {} {}: {}",
+                    self.style(self.symbols.cross, self.theme.error),
+                    message,
+                    err
+                ),
+            ),
+        }
+        Ok(())
+    }
+
+    fn emit(&self, is_error: bool, line: &str) {
+        self.output.line(is_error, line);
+    }
+
+    pub(crate) fn output(&self) -> &Output {
+        &self.output
+    }
+
+    fn static_line(&self, message: &str) -> String {
+        format!(
+            "  {} {}\n",
+            self.style(self.symbols.dot, self.theme.info),
+            message
+        )
     }
 }
 
@@ -110,6 +386,14 @@ impl Default for Console {
     }
 }
 
+fn parse_confirmation(answer: &str, default: bool) -> bool {
+    match answer.trim().to_ascii_lowercase().as_str() {
+        "y" | "yes" => true,
+        "n" | "no" => false,
+        _ => default,
+    }
+}
+
 pub(crate) fn supports_color() -> bool {
     if std::env::var("NO_COLOR").is_ok() {
         return false;
@@ -121,7 +405,7 @@ pub(crate) fn supports_color() -> bool {
             && force_color.to_lowercase() != "false";
     }
 
-    if !std::io::stdout().is_terminal() {
+    if !std::io::stderr().is_terminal() {
         return false;
     }
 
@@ -136,18 +420,196 @@ pub(crate) fn supports_color() -> bool {
     }
 }
 
+/// Environment variables set by common CI providers.
+pub const CI_ENV_VARS: &[&str] = &[
+    "CI",
+    "GITHUB_ACTIONS",
+    "GITLAB_CI",
+    "CIRCLECI",
+    "JENKINS_URL",
+    "BUILDKITE",
+    "TRAVIS",
+    "APPVEYOR",
+    "AZURE_PIPELINES",
+    "TEAMCITY_VERSION",
+    "BAMBOO_BUILD_NUMBER",
+];
+
+/// Whether we're running under a known CI provider.
+pub fn is_ci() -> bool {
+    CI_ENV_VARS
+        .iter()
+        .any(|var| std::env::var_os(var).is_some())
+}
+
 fn is_ci_environment() -> bool {
-    std::env::var("CI").is_ok()
-    || std::env::var("GITHUB_ACTIONS").is_ok()
-    || std::env::var("GITLAB_CI").is_ok()
-    || std::env::var("CIRCLECI").is_ok()
-    || std::env::var("JENKINS_URL").is_ok()
-    || std::env::var("BUILDKITE").is_ok()
-    || std::env::var("TRAVIS").is_ok()
-    || std::env::var("APPVEYOR").is_ok()
-    || std::env::var("AZURE_PIPELINES").is_ok()
-    || std::env::var("TEAMCITY_VERSION").is_ok()
-    || std::env::var("BAMBOO_BUILD_NUMBER").is_ok()
-    // Also check if stdout is not a terminal as a fallback
-    || !std::io::stdout().is_terminal()
+    // Also check if stderr, where status output goes, is not a terminal as a fallback
+    is_ci() || !std::io::stderr().is_terminal()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ci_spinner_line_is_static_and_plain() {
+        let buffer = output::tests::SharedBuffer::default();
+        let console = Console {
+            theme: Theme::default(),
+            use_color: true,
+            color_level: ColorLevel::Ansi16,
+            is_ci: true,
+            output: Output::new(buffer.clone()),
+            needs_clear: Arc::new(AtomicBool::new(false)),
+            last_line_blank: Arc::new(AtomicBool::new(false)),
+            symbols: Symbols::new(false),
+            spinner_style: SpinnerStyle::default(),
+        };
+
+        let line = console.static_line("Fetching remote");
+        assert_eq!(line, "  · Fetching remote\n");
+        assert!(!line.contains('\x1b'));
+        assert!(!line.contains('\r'));
+
+        let guard = console.spinner("Fetching remote");
+        assert!(
+            !console
+                .needs_clear
+                .load(std::sync::atomic::Ordering::SeqCst)
+        );
+        guard.success("Fetched");
+
+        let written = buffer.contents();
+        assert_eq!(
+            written,
+            format!("  · Fetching remote\n  {} Fetched\n", console.symbols.check)
+        );
+        assert!(!written.contains('\x1b'), "{written:?}");
+        assert!(!written.contains('\r'), "{written:?}");
+    }
+
+    #[test]
+    fn status_output_goes_to_configured_writer() {
+        let buffer = output::tests::SharedBuffer::default();
+        let console = Console::new().with_writer(buffer.clone());
+
+        console.header("list").unwrap();
+        console.message(MessageType::Success, "Done").unwrap();
+        console.step("Fetched", &Ok::<(), String>(())).unwrap();
+        console.spinner("Pushing").fail("Push rejected");
+
+        let written = buffer.contents();
+        for expected in ["list", "Done", "Fetched", "Push rejected"] {
+            assert!(written.contains(expected), "missing {expected}: {written}");
+        }
+
+        let quiet_buffer = output::tests::SharedBuffer::default();
+        let quiet = Console::new().quiet(true).with_writer(quiet_buffer.clone());
+        quiet.header("list").unwrap();
+        quiet.message(MessageType::Success, "Done").unwrap();
+        quiet.spinner("Pushing").success("Pushed");
+        quiet.message(MessageType::Error, "Push rejected").unwrap();
+
+        let written = quiet_buffer.contents();
+        assert!(!written.contains("Done"), "quiet output leaked: {written}");
+        assert!(
+            !written.contains("Pushed"),
+            "quiet output leaked: {written}"
+        );
+        assert!(
+            written.contains("Push rejected"),
+            "missing error: {written}"
+        );
+    }
+
+    #[test]
+    fn captures_formatted_output_in_buffer() {
+        let buffer = output::tests::SharedBuffer::default();
+        let console = Console::new().with_writer(buffer.clone());
+        let (check, cross, dot) = (
+            console.symbols.check,
+            console.symbols.cross,
+            console.symbols.dot,
+        );
+
+        console.header("save").unwrap();
+        console.message(MessageType::Success, "Committed").unwrap();
+        console.details(MessageType::Info, "3 files changed\n1 insertion");
+        console.step("Push", &Err::<(), _>("rejected")).unwrap();
+        console.progress("Fetching").done();
+        console.spinner("Pushing").success("Pushed");
+
+        assert_eq!(
+            buffer.contents(),
+            format!(
+                "sage save\n  {check} Committed\n    3 files changed\n    1 insertion\n  \
+                 {cross} Push: rejected\n  Fetching\n  {dot} Pushing\n  {check} Pushed\n"
+            )
+        );
+    }
+
+    #[test]
+    fn custom_writer_is_never_colored() {
+        let buffer = output::tests::SharedBuffer::default();
+        let console = color_console(false).with_writer(buffer.clone());
+
+        console.message(MessageType::Success, "Committed").unwrap();
+        assert!(
+            !buffer.contents().contains('\x1b'),
+            "{:?}",
+            buffer.contents()
+        );
+        assert_eq!(console.color_level(), ColorLevel::None);
+    }
+
+    fn color_console(is_ci: bool) -> Console {
+        Console {
+            theme: Theme::default(),
+            use_color: true,
+            color_level: ColorLevel::Ansi16,
+            is_ci,
+            output: Output::stderr(),
+            needs_clear: Arc::new(AtomicBool::new(false)),
+            last_line_blank: Arc::new(AtomicBool::new(false)),
+            symbols: Symbols::new(false),
+            spinner_style: SpinnerStyle::default(),
+        }
+    }
+
+    #[test]
+    fn render_diff_colors_lines_by_kind() {
+        let diff =
+            "diff --git a/x b/x\n--- a/x\n+++ b/x\n@@ -1,2 +1,2 @@\n ctx\n-old value\n+new value\n";
+        let console = color_console(false);
+        let theme = Theme::default();
+
+        let rendered = console.render_diff(diff, false);
+        for (line, color) in [
+            ("--- a/x", theme.muted),
+            ("+++ b/x", theme.muted),
+            ("@@ -1,2 +1,2 @@", theme.info),
+            ("-old value", theme.error),
+            ("+new value", theme.success),
+        ] {
+            let styled = format!("{}", line.with(color));
+            assert!(rendered.contains(&styled), "missing {line:?}: {rendered:?}");
+        }
+        assert!(rendered.contains("\n ctx\n"));
+
+        let words = console.render_diff(diff, true);
+        let changed = format!("{}", "new".with(theme.success).bold().underlined());
+        assert!(words.contains(&changed), "{words:?}");
+        assert!(words.contains(&format!("{}", " value".with(theme.success))));
+
+        assert_eq!(color_console(true).render_diff(diff, true), diff);
+    }
+
+    #[test]
+    fn confirmation_answers() {
+        assert!(parse_confirmation("y\n", false));
+        assert!(parse_confirmation("YES", false));
+        assert!(!parse_confirmation("n", true));
+        assert!(parse_confirmation("\n", true));
+        assert!(!parse_confirmation("maybe", false));
+    }
 }
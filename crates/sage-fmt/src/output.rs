@@ -0,0 +1,104 @@
+use std::{
+    io::{self, IsTerminal, Write},
+    sync::{Arc, Mutex},
+};
+
+/// Destination for console status lines and prompts.
+///
+/// Defaults to stderr so stdout only ever carries command data (JSON, logs, listings).
+#[derive(Clone)]
+pub(crate) struct Output {
+    writer: Arc<Mutex<Box<dyn Write + Send>>>,
+    quiet: bool,
+    /// Custom writers are never treated as terminals, so nothing animates or prompts into them.
+    is_terminal: bool,
+}
+
+impl Output {
+    pub(crate) fn stderr() -> Self {
+        Self {
+            is_terminal: io::stderr().is_terminal(),
+            ..Self::new(io::stderr())
+        }
+    }
+
+    pub(crate) fn new(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            writer: Arc::new(Mutex::new(Box::new(writer))),
+            quiet: false,
+            is_terminal: false,
+        }
+    }
+
+    pub(crate) fn is_terminal(&self) -> bool {
+        self.is_terminal
+    }
+
+    pub(crate) fn set_quiet(&mut self, quiet: bool) {
+        self.quiet = quiet;
+    }
+
+    pub(crate) fn is_quiet(&self) -> bool {
+        self.quiet
+    }
+
+    /// Write a status line; quiet mode drops everything but errors.
+    pub(crate) fn line(&self, is_error: bool, line: &str) {
+        if is_visible(self.quiet, is_error) {
+            let _ = self.write(&format!("{line}\n"));
+        }
+    }
+
+    /// Write text as-is and flush, e.g. a prompt waiting for input. Not affected by quiet mode.
+    pub(crate) fn write(&self, text: &str) -> io::Result<()> {
+        let mut writer = self
+            .writer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        writer.write_all(text.as_bytes())?;
+        writer.flush()
+    }
+}
+
+fn is_visible(quiet: bool, is_error: bool) -> bool {
+    !quiet || is_error
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A writer tests can read back after handing a clone to [`Output`].
+    #[derive(Clone, Default)]
+    pub(crate) struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl SharedBuffer {
+        pub(crate) fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn quiet_mode_keeps_only_errors() {
+        let buffer = SharedBuffer::default();
+        let mut output = Output::new(buffer.clone());
+
+        output.line(false, "status");
+        output.set_quiet(true);
+        output.line(false, "hidden");
+        output.line(true, "failure");
+        output.write("prompt? ").unwrap();
+
+        assert_eq!(buffer.contents(), "status\nfailure\nprompt? ");
+    }
+}
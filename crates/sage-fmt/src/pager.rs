@@ -0,0 +1,180 @@
+use std::{
+    env,
+    io::{self, IsTerminal, Write},
+    process::{Child, Command, Stdio},
+};
+
+use anyhow::{Context, Result};
+
+const DEFAULT_PAGER: &str = "less -R";
+
+/// Sends output through `$PAGER` when stdout is a terminal, or straight to
+/// stdout otherwise.
+///
+/// Write errors caused by the pager exiting early (e.g. quitting `less`
+/// before the end) are swallowed, so callers can keep writing unconditionally.
+pub struct Pager {
+    child: Option<Child>,
+    closed: bool,
+}
+
+impl Pager {
+    pub fn start() -> Result<Self> {
+        let command = pager_command(io::stdout().is_terminal(), env::var("PAGER").ok());
+        Self::spawn(command)
+    }
+
+    /// A pager that always writes directly to stdout, e.g. for `--json` output.
+    pub fn disabled() -> Self {
+        Self {
+            child: None,
+            closed: false,
+        }
+    }
+
+    fn spawn(command: Option<String>) -> Result<Self> {
+        let Some(command) = command else {
+            return Ok(Self::disabled());
+        };
+
+        let mut parts = command.split_whitespace();
+        let Some(program) = parts.next() else {
+            return Ok(Self::disabled());
+        };
+
+        let mut cmd = Command::new(program);
+        cmd.args(parts).stdin(Stdio::piped());
+        if env::var_os("LESS").is_none() {
+            // Quit immediately when everything fits on one screen.
+            cmd.env("LESS", "FRX");
+        }
+
+        match cmd.spawn() {
+            Ok(child) => Ok(Self {
+                child: Some(child),
+                closed: false,
+            }),
+            // A missing pager binary shouldn't stop output from being shown.
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::disabled()),
+            Err(err) => Err(err).with_context(|| format!("failed to launch pager '{command}'")),
+        }
+    }
+
+    pub fn is_paging(&self) -> bool {
+        self.child.is_some()
+    }
+
+    /// Close the pager's input and wait for the user to quit it.
+    pub fn finish(mut self) -> Result<()> {
+        self.wait()
+    }
+
+    fn wait(&mut self) -> Result<()> {
+        if let Some(mut child) = self.child.take() {
+            drop(child.stdin.take());
+            child.wait().context("failed to wait for pager")?;
+        } else {
+            io::stdout().flush()?;
+        }
+        Ok(())
+    }
+}
+
+impl Write for Pager {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.closed {
+            return Ok(buf.len());
+        }
+
+        let result = match self.child.as_mut().and_then(|child| child.stdin.as_mut()) {
+            Some(stdin) => stdin.write(buf),
+            None => io::stdout().write(buf),
+        };
+
+        match result {
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => {
+                self.closed = true;
+                Ok(buf.len())
+            }
+            other => other,
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.closed {
+            return Ok(());
+        }
+
+        let result = match self.child.as_mut().and_then(|child| child.stdin.as_mut()) {
+            Some(stdin) => stdin.flush(),
+            None => io::stdout().flush(),
+        };
+
+        match result {
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => {
+                self.closed = true;
+                Ok(())
+            }
+            other => other,
+        }
+    }
+}
+
+impl Drop for Pager {
+    fn drop(&mut self) {
+        let _ = self.wait();
+    }
+}
+
+/// The pager command to run, or `None` to print directly.
+fn pager_command(stdout_is_terminal: bool, pager: Option<String>) -> Option<String> {
+    if !stdout_is_terminal {
+        return None;
+    }
+
+    match pager {
+        Some(pager) if pager.trim().is_empty() => None,
+        Some(pager) => Some(pager),
+        None => Some(DEFAULT_PAGER.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_pager_when_stdout_is_not_a_terminal() {
+        assert_eq!(pager_command(false, None), None);
+        assert_eq!(pager_command(false, Some("less".into())), None);
+        assert_eq!(pager_command(false, Some("  ".into())), None);
+
+        let pager = Pager::spawn(pager_command(false, None)).expect("pager");
+        assert!(!pager.is_paging());
+    }
+
+    #[test]
+    fn empty_pager_disables_paging() {
+        assert_eq!(pager_command(true, Some("  ".into())), None);
+    }
+
+    #[test]
+    fn defaults_to_less() {
+        assert_eq!(pager_command(true, None).as_deref(), Some(DEFAULT_PAGER));
+        assert_eq!(
+            pager_command(true, Some("more".into())).as_deref(),
+            Some("more")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn early_exit_does_not_fail_writes() {
+        let mut pager = Pager::spawn(Some("true".into())).expect("spawn");
+        assert!(pager.is_paging());
+        for _ in 0..1024 {
+            writeln!(pager, "{}", "x".repeat(1024)).expect("write ignored");
+        }
+        pager.finish().expect("finish");
+    }
+}
@@ -1,21 +1,79 @@
 use std::{
-    io::{self, Write},
     sync::{
-        Arc,
+        Arc, Mutex,
         atomic::{AtomicBool, Ordering},
     },
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-pub const PROGRESS_FRAMES: &[&str] = &["◡◡◡", "◠◡◡", "◡◠◡", "◡◡◠", "◡◠◡", "◠◡◡", "◡◡◡"];
+use anyhow::{Result, bail};
+
+use crate::output::Output;
+
 const PROGRESS_FRAME_INTERVAL: Duration = Duration::from_millis(120);
 
+pub const SPINNER_STYLES: &[&str] = &["wave", "dots", "line", "braille"];
+
+/// Animation used by [`ProgressIndicator`] spinners, selected via `general.spinner`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SpinnerStyle {
+    #[default]
+    Wave,
+    Dots,
+    Line,
+    Braille,
+}
+
+impl SpinnerStyle {
+    /// Look up a style by name. See [`SPINNER_STYLES`] for the valid names.
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "wave" => Ok(Self::Wave),
+            "dots" => Ok(Self::Dots),
+            "line" => Ok(Self::Line),
+            "braille" => Ok(Self::Braille),
+            other => bail!(
+                "Unknown spinner '{other}'. Valid spinners: {}",
+                SPINNER_STYLES.join(", ")
+            ),
+        }
+    }
+
+    /// Animation frames; `ascii` swaps in plain characters for terminals without Unicode.
+    pub fn frames(self, ascii: bool) -> &'static [&'static str] {
+        match (self, ascii) {
+            (Self::Wave, false) => &["◡◡◡", "◠◡◡", "◡◠◡", "◡◡◠", "◡◠◡", "◠◡◡", "◡◡◡"],
+            (Self::Wave, true) => &["___", "-__", "_-_", "__-", "_-_", "-__", "___"],
+            (Self::Dots, false) => &["·  ", "·· ", "···", " ··", "  ·", "   "],
+            (Self::Dots, true) => &[".  ", ".. ", "...", " ..", "  .", "   "],
+            (Self::Braille, false) => &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+            // Braille has no ASCII equivalent; the line spinner is the closest.
+            (Self::Line, _) | (Self::Braille, true) => &["-", "\\", "|", "/"],
+        }
+    }
+
+    /// The frame shown on the `index`th tick, wrapping around at the end.
+    pub fn frame(self, ascii: bool, index: usize) -> &'static str {
+        let frames = self.frames(ascii);
+        frames[index % frames.len()]
+    }
+}
+
+/// Byte counts reported through [`ProgressIndicator::set_bytes`].
+#[derive(Debug, Clone, Copy, Default)]
+struct ByteProgress {
+    done: u64,
+    total: u64,
+}
+
 pub struct ProgressIndicator {
     stop: Arc<AtomicBool>,
+    bytes: Arc<Mutex<Option<ByteProgress>>>,
     handle: Option<thread::JoinHandle<()>>,
+    /// Where the animation is drawn; `None` for no-op indicators.
+    output: Option<Output>,
     needs_clear: Arc<AtomicBool>,
-    use_spinner: bool,
     finished: bool,
 }
 
@@ -23,14 +81,20 @@ impl ProgressIndicator {
     pub(crate) fn spinner(
         message: String,
         frames: Vec<String>,
+        output: Output,
         needs_clear: Arc<AtomicBool>,
     ) -> Self {
         let stop = Arc::new(AtomicBool::new(false));
         needs_clear.store(true, Ordering::SeqCst);
 
+        let bytes = Arc::new(Mutex::new(None));
+        let started = Instant::now();
+
         let stop_for_thread = stop.clone();
+        let bytes_for_thread = bytes.clone();
         let message_for_thread = Arc::new(message);
         let frames_for_thread = Arc::new(frames);
+        let output_for_thread = output.clone();
 
         let handle = thread::spawn(move || {
             let mut index = 0usize;
@@ -38,11 +102,20 @@ impl ProgressIndicator {
                 let frame = &frames_for_thread[index % frames_for_thread.len()];
                 index = index.wrapping_add(1);
 
-                {
-                    let mut stdout = io::stdout().lock();
-                    let _ = write!(stdout, "\r  {} {}", frame, message_for_thread.as_str());
-                    let _ = stdout.flush();
-                }
+                let bytes = *bytes_for_thread
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+                let line = match bytes {
+                    Some(ByteProgress { done, total }) => format!(
+                        "\r  {} {} {}\x1B[K",
+                        frame,
+                        message_for_thread.as_str(),
+                        format_throughput(done, total, started.elapsed())
+                    ),
+                    None => format!("\r  {} {}", frame, message_for_thread.as_str()),
+                };
+                let _ = output_for_thread.write(&line);
 
                 thread::sleep(PROGRESS_FRAME_INTERVAL);
             }
@@ -50,9 +123,10 @@ impl ProgressIndicator {
 
         Self {
             stop,
+            bytes,
             handle: Some(handle),
+            output: Some(output),
             needs_clear,
-            use_spinner: true,
             finished: false,
         }
     }
@@ -60,13 +134,23 @@ impl ProgressIndicator {
     pub(crate) fn noop(needs_clear: Arc<AtomicBool>) -> Self {
         Self {
             stop: Arc::new(AtomicBool::new(true)),
+            bytes: Arc::new(Mutex::new(None)),
             handle: None,
+            output: None,
             needs_clear,
-            use_spinner: false,
             finished: true,
         }
     }
 
+    /// Report byte-oriented progress; the spinner line gains a percentage and transfer rate.
+    pub fn set_bytes(&self, done: u64, total: u64) {
+        let mut bytes = self
+            .bytes
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *bytes = Some(ByteProgress { done, total });
+    }
+
     pub fn done(mut self) {
         self.finish();
     }
@@ -82,10 +166,8 @@ impl ProgressIndicator {
             let _ = handle.join();
         }
 
-        if self.use_spinner {
-            let mut stdout = io::stdout().lock();
-            let _ = write!(stdout, "\r\x1B[2K\r");
-            let _ = stdout.flush();
+        if let Some(output) = &self.output {
+            let _ = output.write("\r\x1B[2K\r");
         }
 
         self.needs_clear.store(false, Ordering::SeqCst);
@@ -93,8 +175,96 @@ impl ProgressIndicator {
     }
 }
 
+/// Renders e.g. `42% · 1.2 MiB/s`; the percentage is omitted when the total is unknown.
+pub(crate) fn format_throughput(done: u64, total: u64, elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs_f64();
+    let rate = if seconds > 0.0 {
+        done as f64 / seconds
+    } else {
+        0.0
+    };
+    let rate = format!("{}/s", format_bytes(rate));
+
+    if total == 0 {
+        return rate;
+    }
+
+    let percent = (done.min(total) as f64 / total as f64 * 100.0).floor();
+    format!("{percent:.0}% · {rate}")
+}
+
+fn format_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{value:.0} {}", UNITS[unit])
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
 impl Drop for ProgressIndicator {
     fn drop(&mut self) {
         self.finish();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spinner_styles_start_on_expected_frame_and_wrap() {
+        let expected = [
+            ("wave", "◡◡◡", "___"),
+            ("dots", "·  ", ".  "),
+            ("line", "-", "-"),
+            ("braille", "⠋", "-"),
+        ];
+        assert_eq!(expected.len(), SPINNER_STYLES.len());
+
+        for (name, unicode, ascii) in expected {
+            let style = SpinnerStyle::from_name(name).expect("known style");
+            assert_eq!(style.frame(false, 0), unicode, "{name}");
+            assert_eq!(style.frame(true, 0), ascii, "{name}");
+
+            for ascii_mode in [false, true] {
+                let frames = style.frames(ascii_mode);
+                assert_eq!(style.frame(ascii_mode, frames.len()), frames[0]);
+                assert_eq!(style.frame(ascii_mode, frames.len() + 1), frames[1]);
+                if ascii_mode {
+                    assert!(frames.iter().all(|frame| frame.is_ascii()), "{name}");
+                }
+            }
+        }
+
+        assert_eq!(SpinnerStyle::default(), SpinnerStyle::Wave);
+        assert!(SpinnerStyle::from_name("Dots").is_ok());
+        assert!(SpinnerStyle::from_name("bounce").is_err());
+    }
+
+    #[test]
+    fn throughput_includes_percent_and_rate() {
+        let rendered = format_throughput(512 * 1024, 1024 * 1024, Duration::from_millis(500));
+        assert_eq!(rendered, "50% · 1.0 MiB/s");
+    }
+
+    #[test]
+    fn throughput_without_total_shows_rate_only() {
+        let rendered = format_throughput(300, 0, Duration::from_secs(1));
+        assert_eq!(rendered, "300 B/s");
+    }
+
+    #[test]
+    fn throughput_clamps_percent_and_handles_zero_elapsed() {
+        let rendered = format_throughput(2048, 1024, Duration::ZERO);
+        assert_eq!(rendered, "100% · 0 B/s");
+    }
+}
@@ -0,0 +1,272 @@
+use std::io::BufRead;
+
+use anyhow::{Result, bail};
+
+use crate::Console;
+
+type Preview<'a> = Box<dyn Fn(&str) -> Option<String> + 'a>;
+
+const DEFAULT_HINT: &str = "[number or filter]";
+
+/// Line-based picker: lists numbered items and reads a number or a filter query.
+pub struct Select<'a> {
+    console: &'a Console,
+    prompt: String,
+    items: Vec<String>,
+    fuzzy: bool,
+    preview: Option<Preview<'a>>,
+    /// Replaces the default hint after the prompt when set.
+    footer: Option<Vec<String>>,
+    /// Chosen on an empty answer, and without prompting when there's no terminal.
+    default: Option<usize>,
+}
+
+impl<'a> Select<'a> {
+    pub(crate) fn new(console: &'a Console, prompt: String) -> Self {
+        Self {
+            console,
+            prompt,
+            items: Vec::new(),
+            fuzzy: false,
+            preview: None,
+            footer: None,
+            default: None,
+        }
+    }
+
+    pub fn items<I, S>(mut self, items: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.items = items.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Match queries as in-order subsequences instead of plain substrings.
+    pub fn fuzzy_search(mut self, fuzzy: bool) -> Self {
+        self.fuzzy = fuzzy;
+        self
+    }
+
+    /// Extra dimmed text shown next to each item.
+    pub fn with_preview(mut self, preview: impl Fn(&str) -> Option<String> + 'a) -> Self {
+        self.preview = Some(Box::new(preview));
+        self
+    }
+
+    /// Dimmed lines shown under the items instead of the default `[number or filter]` hint.
+    pub fn with_footer(mut self, lines: Vec<String>) -> Self {
+        self.footer = Some(lines);
+        self
+    }
+
+    /// Item returned when the user just presses Enter, or straight away when there's no
+    /// terminal to prompt on. Ignored when out of range.
+    pub fn default_index(mut self, index: usize) -> Self {
+        self.default = Some(index);
+        self
+    }
+
+    /// Index of the first item matching `predicate`, without prompting. Lets callers make
+    /// the choice programmatically, e.g. in CI or for `--json` output.
+    pub fn select_by(&self, predicate: impl Fn(&str) -> bool) -> Option<usize> {
+        self.items.iter().position(|item| predicate(item))
+    }
+
+    /// Index of the chosen item, or `None` if the user entered nothing and there is no
+    /// default. Without an interactive terminal the default is returned if set.
+    pub fn interact(self) -> Result<Option<usize>> {
+        let default = self.default.filter(|&index| index < self.items.len());
+        if !self.console.is_interactive() {
+            if default.is_some() {
+                return Ok(default);
+            }
+            bail!("cannot prompt for a selection without an interactive terminal");
+        }
+        if self.items.is_empty() {
+            return Ok(None);
+        }
+
+        let mut visible: Vec<usize> = (0..self.items.len()).collect();
+        loop {
+            self.render(&visible)?;
+
+            let mut answer = String::new();
+            if std::io::stdin().lock().read_line(&mut answer)? == 0 {
+                return Ok(None);
+            }
+            let answer = answer.trim();
+            if answer.is_empty() {
+                return Ok(default);
+            }
+
+            if let Ok(number) = answer.parse::<usize>()
+                && (1..=visible.len()).contains(&number)
+            {
+                return Ok(Some(visible[number - 1]));
+            }
+
+            let matches = filter_items(&self.items, answer, self.fuzzy);
+            match matches.as_slice() {
+                [] => self.console.output().write(&format!(
+                    "  {}\n",
+                    self.console.style(
+                        &format!("No matches for '{answer}'"),
+                        self.console.theme.muted
+                    )
+                ))?,
+                [only] => return Ok(Some(*only)),
+                _ => visible = matches,
+            }
+        }
+    }
+
+    fn render(&self, visible: &[usize]) -> Result<()> {
+        let width = visible
+            .iter()
+            .map(|&index| self.items[index].chars().count())
+            .max()
+            .unwrap_or(0);
+
+        for (number, &index) in visible.iter().enumerate() {
+            let item = &self.items[index];
+            let preview = self
+                .preview
+                .as_ref()
+                .and_then(|preview| preview(item))
+                .map(|text| format!("  {}", self.console.style(&text, self.console.theme.muted)))
+                .unwrap_or_default();
+            self.console
+                .output()
+                .write(&format!("  {:>3}) {item:<width$}{preview}\n", number + 1))?;
+        }
+
+        let hint = match &self.footer {
+            Some(lines) => {
+                for line in lines {
+                    self.console.output().write(&format!(
+                        "  {}\n",
+                        self.console.style(line, self.console.theme.muted)
+                    ))?;
+                }
+                String::new()
+            }
+            None => format!(
+                " {}",
+                self.console.style(DEFAULT_HINT, self.console.theme.muted)
+            ),
+        };
+
+        self.console.output().write(&format!(
+            "  {} {}{hint} ",
+            self.console.style("?", self.console.theme.primary),
+            self.prompt,
+        ))?;
+        Ok(())
+    }
+}
+
+/// Indices of `items` matching `query`, case-insensitively.
+pub(crate) fn filter_items(items: &[String], query: &str, fuzzy: bool) -> Vec<usize> {
+    let query = query.to_lowercase();
+    items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| {
+            let item = item.to_lowercase();
+            if fuzzy {
+                is_subsequence(&item, &query)
+            } else {
+                item.contains(&query)
+            }
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+fn is_subsequence(haystack: &str, needle: &str) -> bool {
+    let mut haystack = haystack.chars();
+    needle
+        .chars()
+        .all(|wanted| haystack.any(|found| found == wanted))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::tests::SharedBuffer;
+
+    /// Renders into a buffer; a custom writer is uncolored whatever the test's terminal is.
+    fn rendered(select: impl FnOnce(Select<'_>) -> Select<'_>) -> String {
+        let buffer = SharedBuffer::default();
+        let console = Console::new().with_writer(buffer.clone());
+        select(console.select("Branch").items(["main", "feature"]))
+            .render(&[0, 1])
+            .expect("render");
+        buffer.contents()
+    }
+
+    #[test]
+    fn custom_footer_replaces_default_hint() {
+        let default = rendered(|select| select);
+        assert!(default.contains(DEFAULT_HINT), "{default}");
+
+        let custom = rendered(|select| {
+            select.with_footer(vec!["d) delete".to_string(), "Enter) cancel".to_string()])
+        });
+        assert!(!custom.contains('\x1b'), "{custom:?}");
+        assert!(custom.contains("d) delete\n"), "{custom}");
+        assert!(custom.contains("Enter) cancel\n"), "{custom}");
+        assert!(!custom.contains(DEFAULT_HINT), "{custom}");
+        assert!(custom.trim_end().ends_with("Branch"), "{custom}");
+    }
+
+    #[test]
+    fn select_by_returns_first_match_without_prompting() {
+        let console = Console::new().with_writer(SharedBuffer::default());
+        let select = console
+            .select("Branch")
+            .items(["main", "feat/login", "feat/logout"]);
+
+        assert_eq!(select.select_by(|item| item.starts_with("feat/")), Some(1));
+        assert_eq!(select.select_by(|item| item == "main"), Some(0));
+        assert_eq!(select.select_by(|item| item == "release"), None);
+    }
+
+    #[test]
+    fn default_index_is_used_without_a_terminal() {
+        let console = Console::new().with_writer(SharedBuffer::default());
+        let branches = ["main", "feature"];
+
+        let chosen = console
+            .select("Branch")
+            .items(branches)
+            .default_index(1)
+            .interact()
+            .expect("default");
+        assert_eq!(chosen, Some(1));
+
+        // Out of range defaults are ignored, so this still needs a terminal.
+        let out_of_range = console
+            .select("Branch")
+            .items(branches)
+            .default_index(5)
+            .interact();
+        assert!(out_of_range.is_err());
+        assert!(console.select("Branch").items(branches).interact().is_err());
+    }
+
+    #[test]
+    fn filters_by_substring_or_subsequence() {
+        let items: Vec<String> = ["feature/login", "fix/logout", "main"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        assert_eq!(filter_items(&items, "LOG", false), vec![0, 1]);
+        assert_eq!(filter_items(&items, "flgn", false), Vec::<usize>::new());
+        assert_eq!(filter_items(&items, "flgn", true), vec![0]);
+        assert_eq!(filter_items(&items, "fxlo", true), vec![1]);
+    }
+}
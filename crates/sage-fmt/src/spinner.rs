@@ -0,0 +1,43 @@
+use crate::{ProgressIndicator, output::Output};
+
+/// A running spinner that resolves into a single ✓/✗ line.
+///
+/// Dropping the guard without resolving it just clears the spinner.
+pub struct SpinnerGuard {
+    progress: ProgressIndicator,
+    check: String,
+    cross: String,
+    output: Output,
+}
+
+impl SpinnerGuard {
+    pub(crate) fn new(
+        progress: ProgressIndicator,
+        check: String,
+        cross: String,
+        output: Output,
+    ) -> Self {
+        Self {
+            progress,
+            check,
+            cross,
+            output,
+        }
+    }
+
+    pub fn set_bytes(&self, done: u64, total: u64) {
+        self.progress.set_bytes(done, total);
+    }
+
+    pub fn success(self, message: &str) {
+        self.progress.done();
+        self.output
+            .line(false, &format!("  {} {}", self.check, message));
+    }
+
+    pub fn fail(self, message: &str) {
+        self.progress.done();
+        self.output
+            .line(true, &format!("  {} {}", self.cross, message));
+    }
+}
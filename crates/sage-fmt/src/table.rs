@@ -0,0 +1,170 @@
+/// How a column's cells are padded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+}
+
+const GAP: &str = "  ";
+const ELLIPSIS: char = '…';
+
+/// Aligned multi-column output.
+///
+/// Widths are measured on the visible text, so cells that already carry color codes
+/// still line up. Cells wider than [`Table::max_width`] are cut short with `…`.
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    align: Vec<Align>,
+    max_width: Option<usize>,
+}
+
+impl Table {
+    /// A table with a header row; pass an empty slice for none.
+    pub fn new(headers: &[&str]) -> Self {
+        Self {
+            headers: headers.iter().map(|header| header.to_string()).collect(),
+            ..Self::default()
+        }
+    }
+
+    pub fn align(mut self, column: usize, align: Align) -> Self {
+        if self.align.len() <= column {
+            self.align.resize(column + 1, Align::Left);
+        }
+        self.align[column] = align;
+        self
+    }
+
+    /// Cap every cell at `width` visible characters.
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    pub fn row<I, S>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.push_row(cells);
+        self
+    }
+
+    pub fn push_row<I, S>(&mut self, cells: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+    }
+
+    /// One line per row, each ending in a newline. Trailing padding is trimmed.
+    pub fn render(&self) -> String {
+        let rows: Vec<Vec<String>> = (!self.headers.is_empty())
+            .then_some(&self.headers)
+            .into_iter()
+            .chain(&self.rows)
+            .map(|row| row.iter().map(|cell| self.fit(cell)).collect())
+            .collect();
+
+        let widths = column_widths(&rows);
+        let mut out = String::new();
+        for row in &rows {
+            let mut line = String::new();
+            for (column, width) in widths.iter().enumerate() {
+                if column > 0 {
+                    line.push_str(GAP);
+                }
+                let cell = row.get(column).map_or("", String::as_str);
+                let padding = " ".repeat(width - visible_len(cell));
+                match self.align.get(column).copied().unwrap_or_default() {
+                    Align::Left => {
+                        line.push_str(cell);
+                        line.push_str(&padding);
+                    }
+                    Align::Right => {
+                        line.push_str(&padding);
+                        line.push_str(cell);
+                    }
+                }
+            }
+            out.push_str(line.trim_end_matches(' '));
+            out.push('\n');
+        }
+        out
+    }
+
+    fn fit(&self, cell: &str) -> String {
+        match self.max_width {
+            Some(width) if visible_len(cell) > width => truncate(cell, width),
+            _ => cell.to_string(),
+        }
+    }
+}
+
+/// Printed width of `text`, ignoring ANSI escape sequences.
+pub(crate) fn visible_len(text: &str) -> usize {
+    strip_ansi_escapes::strip_str(text).chars().count()
+}
+
+fn column_widths(rows: &[Vec<String>]) -> Vec<usize> {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    (0..columns)
+        .map(|column| {
+            rows.iter()
+                .filter_map(|row| row.get(column))
+                .map(|cell| visible_len(cell))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect()
+}
+
+/// Shorten to `width` visible characters, ending in `…`. Styling is dropped.
+fn truncate(cell: &str, width: usize) -> String {
+    if width == 0 {
+        return String::new();
+    }
+    let plain = strip_ansi_escapes::strip_str(cell);
+    let mut truncated: String = plain.chars().take(width - 1).collect();
+    truncated.push(ELLIPSIS);
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn widths_ignore_color_codes() {
+        let colored = "\x1b[32mmain\x1b[0m";
+        assert_eq!(visible_len(colored), 4);
+
+        let table = Table::new(&["Branch", "Ahead"])
+            .align(1, Align::Right)
+            .row([colored, "3"])
+            .row(["feature/login", "12"]);
+
+        let rendered = strip_ansi_escapes::strip_str(table.render());
+        assert_eq!(
+            rendered,
+            "Branch         Ahead\n\
+             main               3\n\
+             feature/login     12\n"
+        );
+    }
+
+    #[test]
+    fn truncates_wide_cells_and_pads_short_rows() {
+        let table = Table::new(&[])
+            .max_width(6)
+            .row(["abcdefghij", "x"])
+            .row(["ab"]);
+
+        assert_eq!(table.render(), "abcde…  x\nab\n");
+        assert_eq!(truncate("\x1b[1mbold text\x1b[0m", 4), "bol…");
+    }
+}
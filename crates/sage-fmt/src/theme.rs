@@ -1,5 +1,10 @@
+use anyhow::{Result, bail};
 use crossterm::style::Color;
 
+use crate::ColorLevel;
+
+pub const THEME_PRESETS: &[&str] = &["default", "high-contrast", "deuteranopia"];
+
 #[derive(Debug, Clone)]
 pub struct Theme {
     pub primary: Color,
@@ -22,3 +27,78 @@ impl Default for Theme {
         }
     }
 }
+
+impl Theme {
+    /// Replace colors the terminal can't show with their nearest supported equivalent.
+    pub fn downgrade(self, level: ColorLevel) -> Self {
+        Self {
+            primary: level.downgrade(self.primary),
+            success: level.downgrade(self.success),
+            error: level.downgrade(self.error),
+            warning: level.downgrade(self.warning),
+            info: level.downgrade(self.info),
+            muted: level.downgrade(self.muted),
+        }
+    }
+
+    /// Look up a named preset. See [`THEME_PRESETS`] for the valid names.
+    pub fn preset(name: &str) -> Result<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "default" => Ok(Self::default()),
+            "high-contrast" => Ok(Self {
+                primary: Color::White,
+                success: Color::Green,
+                error: Color::Red,
+                warning: Color::Yellow,
+                info: Color::Cyan,
+                muted: Color::Grey,
+            }),
+            // Avoids red/green; blue and orange stay distinguishable for red-green color blindness.
+            "deuteranopia" => Ok(Self {
+                primary: Color::Cyan,
+                success: Color::AnsiValue(33),
+                error: Color::AnsiValue(208),
+                warning: Color::Yellow,
+                info: Color::AnsiValue(75),
+                muted: Color::DarkGrey,
+            }),
+            other => bail!(
+                "Unknown theme '{other}'. Valid themes: {}",
+                THEME_PRESETS.join(", ")
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_have_distinct_success_and_error() {
+        for name in THEME_PRESETS {
+            let theme = Theme::preset(name).expect("preset exists");
+            assert_ne!(theme.success, theme.error, "preset {name}");
+        }
+    }
+
+    #[test]
+    fn deuteranopia_avoids_red_and_green() {
+        let theme = Theme::preset("deuteranopia").unwrap();
+        for color in [theme.success, theme.error] {
+            assert!(!matches!(
+                color,
+                Color::Red | Color::DarkRed | Color::Green | Color::DarkGreen
+            ));
+        }
+    }
+
+    #[test]
+    fn unknown_preset_lists_valid_names() {
+        let err = Theme::preset("neon").unwrap_err().to_string();
+        assert!(err.contains("neon"));
+        for name in THEME_PRESETS {
+            assert!(err.contains(name), "missing {name} in: {err}");
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::{collections::HashMap, path::Path};
+
+use anyhow::{Context, Result, bail};
+
+use super::Repo;
+
+const UNCOMMITTED_ID: &str = "0000000000000000000000000000000000000000";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlameCommit {
+    Committed(String),
+    /// The line only exists in the working tree.
+    Uncommitted,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameLine {
+    pub commit: BlameCommit,
+    pub author: String,
+    /// Author time in seconds since the Unix epoch.
+    pub timestamp: i64,
+    /// 1-based line number in the current file.
+    pub line_no: usize,
+    pub content: String,
+}
+
+impl Repo {
+    /// Blame `path` (relative to the repo root), optionally restricted to an
+    /// inclusive, 1-based `(start, end)` line range.
+    pub fn blame(
+        &self,
+        path: impl AsRef<Path>,
+        range: Option<(usize, usize)>,
+    ) -> Result<Vec<BlameLine>> {
+        let mut command = self.git()?.args(["blame", "--porcelain"]);
+        if let Some((start, end)) = range {
+            if start == 0 || end < start {
+                bail!("invalid blame range {start},{end}");
+            }
+            command = command.arg("-L").arg(format!("{start},{end}"));
+        }
+
+        let output = command
+            .arg("--")
+            .arg(path.as_ref())
+            .run_with_output()
+            .with_context(|| format!("failed to blame {}", path.as_ref().display()))?;
+
+        parse_porcelain(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+fn parse_porcelain(output: &str) -> Result<Vec<BlameLine>> {
+    // Porcelain output only includes commit metadata the first time a commit appears.
+    let mut authors: HashMap<String, (String, i64)> = HashMap::new();
+    let mut lines = Vec::new();
+
+    let mut commit = String::new();
+    let mut line_no = 0;
+
+    for line in output.lines() {
+        if let Some(content) = line.strip_prefix('\t') {
+            let (author, timestamp) = authors.get(&commit).cloned().unwrap_or_default();
+            lines.push(BlameLine {
+                commit: if commit == UNCOMMITTED_ID {
+                    BlameCommit::Uncommitted
+                } else {
+                    BlameCommit::Committed(commit.clone())
+                },
+                author,
+                timestamp,
+                line_no,
+                content: content.to_string(),
+            });
+            continue;
+        }
+
+        if let Some(author) = line.strip_prefix("author ") {
+            authors.entry(commit.clone()).or_default().0 = author.to_string();
+        } else if let Some(time) = line.strip_prefix("author-time ") {
+            authors.entry(commit.clone()).or_default().1 = time
+                .parse()
+                .with_context(|| format!("invalid author-time '{time}'"))?;
+        } else if let Some(header) = parse_header(line) {
+            (commit, line_no) = header;
+        }
+    }
+
+    Ok(lines)
+}
+
+/// `<sha> <orig line> <final line> [<group size>]`
+fn parse_header(line: &str) -> Option<(String, usize)> {
+    let mut parts = line.split(' ');
+    let sha = parts.next()?;
+    if sha.len() != UNCOMMITTED_ID.len() || !sha.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let _orig_line = parts.next()?;
+    let final_line = parts.next()?.parse().ok()?;
+    Some((sha.to_string(), final_line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestRepo;
+
+    #[test]
+    fn blame_attributes_lines_to_authors() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        repo.write("notes.txt", "first\nsecond\n").expect("write");
+        repo.commit_all("add notes").expect("commit");
+
+        repo.write("notes.txt", "first\nsecond\nthird\n")
+            .expect("write");
+        repo.git()
+            .env("GIT_AUTHOR_NAME", "Other Author")
+            .args(["commit", "-am", "append line"])
+            .run()
+            .expect("commit as other author");
+
+        repo.write("notes.txt", "first\nsecond\nthird\nfourth\n")
+            .expect("write");
+
+        let lines = repo.blame("notes.txt", None).expect("blame");
+        let summary: Vec<(usize, &str, &str)> = lines
+            .iter()
+            .map(|line| (line.line_no, line.author.as_str(), line.content.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1, "Test User", "first"),
+                (2, "Test User", "second"),
+                (3, "Other Author", "third"),
+                (4, "Not Committed Yet", "fourth"),
+            ]
+        );
+
+        assert_eq!(lines[0].commit, lines[1].commit);
+        assert_ne!(lines[1].commit, lines[2].commit);
+        assert!(matches!(lines[2].commit, BlameCommit::Committed(_)));
+        assert_eq!(lines[3].commit, BlameCommit::Uncommitted);
+        assert!(lines[0].timestamp > 0);
+
+        let ranged = repo.blame("notes.txt", Some((2, 3))).expect("ranged blame");
+        let contents: Vec<&str> = ranged.iter().map(|line| line.content.as_str()).collect();
+        assert_eq!(contents, vec!["second", "third"]);
+        assert_eq!(ranged[0].line_no, 2);
+    }
+}
@@ -1,6 +1,7 @@
 use std::fs::File;
 
 use anyhow::{Result, anyhow, bail};
+use chrono::{DateTime, Utc};
 use gix::{
     bstr::{BStr, ByteSlice},
     interrupt,
@@ -15,26 +16,64 @@ use gix_worktree_state::checkout;
 
 use super::Repo;
 
-impl Repo {
-    pub fn fetch(&self) -> Result<()> {
-        if !self.has_remote()? {
-            return Ok(());
-        }
+/// How [`Repo::switch_branch_with`] gets to the target.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SwitchOptions {
+    /// Create the branch at the current commit first; fails if it already exists.
+    pub create: bool,
+    /// Check out the revision without a branch, leaving HEAD detached.
+    pub detach: bool,
+}
 
-        self.git()?
-            .arg("fetch")
-            .arg("--no-progress")
-            .arg("--all")
-            .arg("--prune")
-            .run()
+/// How [`Repo::push_with`] handles a remote branch the local one doesn't contain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushMode {
+    /// Let the remote reject a non-fast-forward update.
+    Plain,
+    /// Overwrite the remote branch only if it still matches our remote-tracking ref.
+    ForceWithLease,
+    Force,
+}
+
+/// What `HEAD` points at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeadState {
+    /// On `branch`, which has no commits yet (e.g. right after `git init`).
+    Unborn {
+        branch: String,
+    },
+    /// Not on a branch; `commit` is the checked out commit id.
+    Detached {
+        commit: String,
+    },
+    Branch {
+        name: String,
+    },
+}
+
+impl Repo {
+    pub fn head_state(&self) -> Result<HeadState> {
+        Ok(match self.repo.head()?.kind {
+            gix::head::Kind::Unborn(name) => HeadState::Unborn {
+                branch: name.shorten().to_string(),
+            },
+            gix::head::Kind::Detached { target, .. } => HeadState::Detached {
+                commit: target.to_string(),
+            },
+            gix::head::Kind::Symbolic(reference) => HeadState::Branch {
+                name: reference.name.shorten().to_string(),
+            },
+        })
     }
 
+    /// The checked out branch, including an unborn one; fails on a detached HEAD.
     pub fn get_current_branch(&self) -> Result<String> {
-        let head_name = self.repo.head_name()?;
-
-        match head_name {
-            Some(name) => Ok(name.shorten().to_string()),
-            _ => bail!("Detached head"),
+        match self.head_state()? {
+            HeadState::Unborn { branch } => Ok(branch),
+            HeadState::Branch { name } => Ok(name),
+            HeadState::Detached { commit } => {
+                bail!("Detached head at {}", &commit[..commit.len().min(8)])
+            }
         }
     }
 
@@ -73,16 +112,39 @@ impl Repo {
     }
 
     pub fn switch_branch(&self, name: &str) -> Result<()> {
+        self.switch_branch_with(name, &SwitchOptions::default())
+    }
+
+    /// Switch to `name`, optionally creating it first or detaching onto it.
+    pub fn switch_branch_with(&self, name: &str, options: &SwitchOptions) -> Result<()> {
+        if options.create && options.detach {
+            bail!("Cannot create a branch and detach HEAD at the same time");
+        }
+        if options.create {
+            if self.has_branch(name.to_owned())? {
+                bail!("Branch '{}' already exists", self.remove_ref(name));
+            }
+            self.create_branch(name)?;
+        }
+
         // Prefer gix for performance on clean trees. If there are local changes
         // or untracked files, defer to native `git switch` to preserve user data.
-        let has_local_changes = self.is_dirty()? || !self.untracked_files()?.is_empty();
+        let has_local_changes = self.is_dirty()?;
         if has_local_changes {
-            let branch_name = self.remove_ref(name);
-            return self.git()?.arg("switch").arg(branch_name).run();
+            let command = self.git()?.arg("switch");
+            let command = if options.detach {
+                command.arg("--detach").arg(name)
+            } else {
+                command.arg(self.remove_ref(name))
+            };
+            return command.run();
         }
 
-        let branch_ref = self.as_ref(name);
-        let branch_full = FullName::try_from(branch_ref.as_str())?;
+        let new_head = if options.detach {
+            Target::Object(self.resolve_commit(name)?)
+        } else {
+            Target::Symbolic(FullName::try_from(self.as_ref(name).as_str())?)
+        };
 
         let edits = [RefEdit {
             change: Change::Update {
@@ -92,7 +154,7 @@ impl Repo {
                     message: "switch branch".into(),
                 },
                 expected: PreviousValue::Any,
-                new: Target::Symbolic(branch_full),
+                new: new_head,
             },
             name: FullName::try_from("HEAD")?,
             deref: false,
@@ -129,6 +191,15 @@ impl Repo {
         Ok(())
     }
 
+    /// Delete a local branch. Without `force`, git refuses if it isn't merged.
+    pub fn delete_branch(&self, name: &str, force: bool) -> Result<()> {
+        self.git()?
+            .arg("branch")
+            .arg(if force { "-D" } else { "-d" })
+            .arg(self.remove_ref(name))
+            .run()
+    }
+
     pub fn list_branches(&self) -> Result<Vec<String>> {
         let mut branches = vec![];
         self.repo
@@ -177,6 +248,44 @@ impl Repo {
         Ok(branches.contains(&self.as_ref(&name)))
     }
 
+    /// Short names of local branches under the path `prefix`, e.g. `feat/auth/`, sorted.
+    ///
+    /// The prefix matches whole path segments, so `feat/auth` finds `feat/auth/login` but
+    /// not `feat/authz`. An empty prefix returns every branch.
+    pub fn find_branches_by_prefix(&self, prefix: &str) -> Result<Vec<String>> {
+        let prefix = prefix.trim_end_matches('/');
+        let mut branches: Vec<String> = self
+            .list_branches()?
+            .iter()
+            .map(|branch| self.remove_ref(branch))
+            .filter(|branch| {
+                prefix.is_empty()
+                    || branch
+                        .strip_prefix(prefix)
+                        .is_some_and(|rest| rest.starts_with('/'))
+            })
+            .collect();
+        branches.sort();
+        Ok(branches)
+    }
+
+    /// Committer date of the commit `name` points at. Accepts short or full ref names.
+    pub fn branch_last_activity(&self, name: &str) -> Result<DateTime<Utc>> {
+        let full_name = if name.starts_with("refs/") {
+            name.to_string()
+        } else {
+            self.as_ref(name)
+        };
+        let commit = self
+            .repo
+            .find_reference(full_name.as_str())
+            .map_err(|_| anyhow!("Branch '{name}' not found"))?
+            .peel_to_commit()?;
+        let seconds = commit.committer()?.time()?.seconds;
+        DateTime::from_timestamp(seconds, 0)
+            .ok_or_else(|| anyhow!("Branch '{name}' has an invalid commit time"))
+    }
+
     pub fn set_upstream_named(
         &mut self,
         branch: &str,
@@ -213,6 +322,15 @@ impl Repo {
     }
 
     pub fn push(&self, force: bool) -> Result<()> {
+        self.push_with(if force {
+            PushMode::Force
+        } else {
+            PushMode::ForceWithLease
+        })
+    }
+
+    /// Push the current branch to its upstream.
+    pub fn push_with(&self, mode: PushMode) -> Result<()> {
         // Get upstream from config using string_by (correct method for subsections)
         let config = self.repo.config_snapshot();
         let current_branch = self.get_current_branch()?;
@@ -226,16 +344,14 @@ impl Repo {
             .ok_or_else(|| anyhow!("No merge ref configured for branch {}", current_branch))?
             .to_string();
 
-        let force_flag = if force {
-            "--force"
-        } else {
-            "--force-with-lease"
-        };
-
         // Run external git push (since gix lacks native push)
-        self.git()?
-            .arg("push")
-            .arg(force_flag)
+        let mut command = self.git()?.arg("push");
+        match mode {
+            PushMode::Plain => {}
+            PushMode::ForceWithLease => command = command.arg("--force-with-lease"),
+            PushMode::Force => command = command.arg("--force"),
+        }
+        command
             .arg("--no-progress")
             .arg(remote_name)
             .arg(format!(
@@ -268,7 +384,7 @@ impl Repo {
 
     pub fn get_default_branch(&self) -> Result<String> {
         if !self.has_remote()? {
-            return Ok(String::from("main"));
+            return self.default_branch_from_local();
         }
         let remote_name = self.remote_name()?.unwrap();
         let head_ref_name = format!("refs/remotes/{}/{}", remote_name, "HEAD");
@@ -288,6 +404,56 @@ impl Repo {
         }
     }
 
+    /// Point `refs/remotes/<remote>/HEAD` at `name`, like `git remote set-head`, so
+    /// [`Repo::get_default_branch`] follows a default branch that changed upstream.
+    pub fn set_default_branch(&self, name: &str) -> Result<()> {
+        let Some(remote_name) = self.remote_name()? else {
+            bail!("No remote to set the default branch for");
+        };
+        let target = format!("refs/remotes/{remote_name}/{name}");
+        if self.repo.try_find_reference(target.as_str())?.is_none() {
+            bail!("Branch '{name}' not found on '{remote_name}'; fetch it first");
+        }
+
+        let edits = [RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: "set default branch".into(),
+                },
+                expected: PreviousValue::Any,
+                new: Target::Symbolic(FullName::try_from(target.as_str())?),
+            },
+            name: FullName::try_from(format!("refs/remotes/{remote_name}/HEAD"))?,
+            deref: false,
+        }];
+        self.repo.edit_references(edits)?;
+        Ok(())
+    }
+
+    /// Best guess at the primary branch of a repository without a remote.
+    ///
+    /// Prefers `init.defaultBranch` when that branch exists, then the first existing of
+    /// `main`, `master` and `trunk`, then `init.defaultBranch` even if it's unborn, then `main`.
+    pub fn default_branch_from_local(&self) -> Result<String> {
+        let configured = self
+            .get_config("init", None, "defaultBranch")?
+            .filter(|name| !name.trim().is_empty());
+
+        if let Some(name) = &configured
+            && self.has_branch(name.clone())?
+        {
+            return Ok(name.clone());
+        }
+        for name in ["main", "master", "trunk"] {
+            if self.has_branch(name.to_owned())? {
+                return Ok(name.to_owned());
+            }
+        }
+        Ok(configured.unwrap_or_else(|| String::from("main")))
+    }
+
     pub fn pull(&self) -> Result<()> {
         if !self.has_remote()? {
             return Ok(());
@@ -315,9 +481,69 @@ impl Repo {
     }
 }
 
+/// Reject names git wouldn't accept for a local branch.
+pub fn validate_branch_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.starts_with('-') || name == "HEAD" {
+        bail!("Invalid branch name '{name}'");
+    }
+    FullName::try_from(format!("refs/heads/{name}"))
+        .map_err(|err| anyhow!("Invalid branch name '{name}': {err}"))?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::testing::TestRepo;
+    use super::{HeadState, SwitchOptions, validate_branch_name};
+    use crate::{Repo, testing::TestRepo};
+
+    #[test]
+    fn head_state_covers_unborn_branch_and_detached() {
+        let repo = TestRepo::builder().build().expect("temp repo");
+        assert_eq!(
+            repo.head_state().expect("head state"),
+            HeadState::Unborn {
+                branch: "main".to_string()
+            }
+        );
+        assert_eq!(repo.get_current_branch().expect("branch"), "main");
+
+        repo.write("file.txt", "content\n").expect("write");
+        repo.commit_all("first").expect("commit");
+        assert_eq!(
+            repo.head_state().expect("head state"),
+            HeadState::Branch {
+                name: "main".to_string()
+            }
+        );
+
+        let head = repo.last_commit("HEAD").expect("head commit");
+        repo.run_git(["checkout", "--detach"]).expect("detach");
+        assert_eq!(
+            repo.head_state().expect("head state"),
+            HeadState::Detached { commit: head.id }
+        );
+        let err = repo.get_current_branch().expect_err("detached");
+        assert!(err.to_string().contains(&head.short_id), "{err}");
+    }
+
+    #[test]
+    fn validates_branch_names() {
+        for name in ["feat/123-login", "fix/ABC-9-crash", "main"] {
+            assert!(validate_branch_name(name).is_ok(), "{name}");
+        }
+        for name in [
+            "",
+            "-x",
+            "HEAD",
+            "feat//x",
+            "feat/x.lock",
+            "a b",
+            "x..y",
+            "x/",
+        ] {
+            assert!(validate_branch_name(name).is_err(), "{name:?}");
+        }
+    }
 
     #[test]
     fn create_branch_adds_reference() {
@@ -349,6 +575,88 @@ mod tests {
         assert_eq!(current, "feature");
     }
 
+    #[test]
+    fn switch_with_create_and_detach() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+
+        let create = SwitchOptions {
+            create: true,
+            ..Default::default()
+        };
+        repo.switch_branch_with("feature", &create)
+            .expect("create and switch");
+        assert_eq!(repo.get_current_branch().expect("branch"), "feature");
+        assert!(repo.switch_branch_with("feature", &create).is_err());
+
+        let head = repo.last_commit("HEAD").expect("head");
+        let detach = SwitchOptions {
+            detach: true,
+            ..Default::default()
+        };
+        repo.switch_branch_with("main", &detach).expect("detach");
+        assert_eq!(
+            repo.head_state().expect("head state"),
+            HeadState::Detached { commit: head.id }
+        );
+    }
+
+    #[test]
+    fn switch_with_local_changes_falls_back_to_git() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        repo.write("wip.txt", "work in progress\n").expect("write");
+
+        repo.switch_branch_with(
+            "feature",
+            &SwitchOptions {
+                create: true,
+                ..Default::default()
+            },
+        )
+        .expect("create and switch with local changes");
+
+        assert_eq!(repo.get_current_branch().expect("branch"), "feature");
+        assert_eq!(
+            std::fs::read_to_string(repo.path().join("wip.txt")).expect("read"),
+            "work in progress\n"
+        );
+    }
+
+    #[test]
+    fn default_branch_falls_back_to_local_master() {
+        let repo = TestRepo::builder()
+            .initial_branch("master")
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        assert_eq!(repo.get_default_branch().expect("default"), "master");
+
+        repo.run_git(["branch", "main"]).expect("create main");
+        assert_eq!(repo.get_default_branch().expect("default"), "main");
+    }
+
+    #[test]
+    fn default_branch_prefers_init_default_branch() {
+        let repo = TestRepo::builder()
+            .initial_branch("trunk")
+            .build()
+            .expect("temp repo");
+        repo.run_git(["config", "init.defaultBranch", "trunk"])
+            .expect("config");
+        let reopened = crate::Repo::discover(repo.path()).expect("reopen");
+        // Unborn, so no branch exists yet; the configured name still wins over `main`.
+        assert_eq!(reopened.get_default_branch().expect("default"), "trunk");
+
+        repo.commit_allow_empty("initial").expect("commit");
+        repo.run_git(["branch", "master"]).expect("create master");
+        assert_eq!(reopened.get_default_branch().expect("default"), "trunk");
+    }
+
     #[test]
     fn has_branch_accepts_short_names() {
         let repo = TestRepo::builder()
@@ -367,6 +675,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn find_branches_by_prefix_matches_whole_segments() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        for name in [
+            "feat/auth/login",
+            "feat/auth/logout",
+            "feat/authz",
+            "feat/billing/invoices",
+        ] {
+            repo.create_branch(name).expect("create branch");
+        }
+
+        let expected = vec!["feat/auth/login", "feat/auth/logout"];
+        assert_eq!(
+            repo.find_branches_by_prefix("feat/auth/")
+                .expect("by prefix"),
+            expected
+        );
+        assert_eq!(
+            repo.find_branches_by_prefix("feat/auth")
+                .expect("without slash"),
+            expected
+        );
+        assert_eq!(
+            repo.find_branches_by_prefix("feat/billing")
+                .expect("other prefix"),
+            vec!["feat/billing/invoices"]
+        );
+        assert!(
+            repo.find_branches_by_prefix("fix/")
+                .expect("no matches")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn set_default_branch_updates_remote_head() {
+        let origin = TestRepo::builder()
+            .initial_branch("main")
+            .with_initial_commit()
+            .build()
+            .expect("origin repo");
+        origin.create_branch("develop").expect("create develop");
+
+        let local = TestRepo::new().expect("local repo");
+        local
+            .run_git([
+                "remote",
+                "add",
+                "origin",
+                &origin.path().display().to_string(),
+            ])
+            .expect("add remote");
+        local.run_git(["fetch", "-q", "origin"]).expect("fetch");
+        // Reopen so the remote added by git is visible.
+        let repo = Repo::discover(local.path()).expect("reopen");
+
+        repo.set_default_branch("main").expect("set main");
+        assert_eq!(repo.get_default_branch().expect("default"), "main");
+
+        repo.set_default_branch("develop").expect("set develop");
+        assert_eq!(repo.get_default_branch().expect("default"), "develop");
+
+        assert!(repo.set_default_branch("missing").is_err());
+    }
+
     #[test]
     fn ref_helpers_strip_prefixes() {
         let repo = TestRepo::new().expect("temp repo");
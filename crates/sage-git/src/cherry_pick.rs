@@ -0,0 +1,171 @@
+use std::path::PathBuf;
+
+use anyhow::{Result, bail};
+
+use super::Repo;
+
+#[derive(Debug, Clone, Default)]
+pub struct CherryPickOptions {
+    /// Append "(cherry picked from commit ...)" to the message (`-x`).
+    pub record_origin: bool,
+    /// Keep a commit whose changes are already in HEAD instead of reporting it as empty.
+    pub allow_empty: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CherryPickOutcome {
+    /// The commit was applied on top of HEAD as `new_commit`.
+    Applied { new_commit: String },
+    /// The cherry-pick stopped on conflicts in these files and is still in progress.
+    Conflicts { files: Vec<PathBuf> },
+    /// The commit's changes were already in HEAD; nothing changed.
+    Empty,
+}
+
+impl Repo {
+    /// Apply `commit` on top of the currently checked out branch.
+    ///
+    /// On conflicts the cherry-pick is left in progress so the caller can decide whether
+    /// to resolve it or run `git cherry-pick --abort`. An empty cherry-pick is skipped.
+    /// Fails without touching anything when a cherry-pick is already in progress.
+    pub fn cherry_pick(
+        &self,
+        commit: &str,
+        options: &CherryPickOptions,
+    ) -> Result<CherryPickOutcome> {
+        if self.cherry_pick_in_progress() {
+            bail!(
+                "A cherry-pick is already in progress; finish it with `git cherry-pick --continue` or `--abort` first"
+            );
+        }
+
+        let before = self.resolve_commit("HEAD")?;
+
+        let mut command = self.git()?.arg("cherry-pick");
+        if options.record_origin {
+            command = command.arg("-x");
+        }
+        if options.allow_empty {
+            command = command.arg("--allow-empty");
+        }
+        let output = command.arg(commit).allow_failure().run_with_output()?;
+
+        if !output.status.success() {
+            let files = self.conflicted_files()?;
+            if !files.is_empty() {
+                return Ok(CherryPickOutcome::Conflicts { files });
+            }
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            // git stops with nothing to commit when the changes are already applied.
+            if stderr.contains("cherry-pick is now empty") {
+                self.git()?.args(["cherry-pick", "--skip"]).run()?;
+                return Ok(CherryPickOutcome::Empty);
+            }
+            bail!("Failed to cherry-pick '{commit}': {}", stderr.trim());
+        }
+
+        let after = self.resolve_commit("HEAD")?;
+        if after == before {
+            Ok(CherryPickOutcome::Empty)
+        } else {
+            Ok(CherryPickOutcome::Applied {
+                new_commit: after.to_string(),
+            })
+        }
+    }
+
+    fn cherry_pick_in_progress(&self) -> bool {
+        let git_dir = self.git_dir();
+        git_dir.join("CHERRY_PICK_HEAD").exists() || git_dir.join("sequencer").exists()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestRepo;
+
+    #[test]
+    fn clean_cherry_pick_applies_commit() {
+        let repo = TestRepo::with_feature_branch().expect("temp repo");
+        let feature = repo.last_commit("feature").expect("feature tip");
+
+        let outcome = repo
+            .cherry_pick(
+                "feature",
+                &CherryPickOptions {
+                    record_origin: true,
+                    ..Default::default()
+                },
+            )
+            .expect("cherry-pick");
+        let CherryPickOutcome::Applied { new_commit } = outcome else {
+            panic!("expected an applied commit, got {outcome:?}");
+        };
+
+        let head = repo.last_commit("HEAD").expect("head");
+        assert_eq!(head.id, new_commit);
+        assert_ne!(head.id, feature.id);
+        assert_eq!(head.summary, "feature work");
+        assert!(head.message.contains(&feature.id), "{}", head.message);
+
+        let outcome = repo
+            .cherry_pick("feature", &CherryPickOptions::default())
+            .expect("cherry-pick again");
+        assert_eq!(outcome, CherryPickOutcome::Empty);
+        assert_eq!(repo.last_commit("HEAD").expect("head").id, new_commit);
+        assert!(!repo.git_dir().join("CHERRY_PICK_HEAD").exists());
+    }
+
+    #[test]
+    fn conflicting_cherry_pick_reports_files() {
+        let repo = TestRepo::with_feature_branch().expect("temp repo");
+        repo.write("feature.txt", "main\n").expect("write");
+        repo.commit_all("main adds feature.txt").expect("commit");
+
+        let outcome = repo
+            .cherry_pick("feature", &CherryPickOptions::default())
+            .expect("cherry-pick");
+        assert_eq!(
+            outcome,
+            CherryPickOutcome::Conflicts {
+                files: vec![PathBuf::from("feature.txt")]
+            }
+        );
+
+        repo.run_git(["cherry-pick", "--abort"]).expect("abort");
+    }
+
+    #[test]
+    fn refuses_to_start_while_a_pick_is_in_progress() {
+        let repo = TestRepo::with_feature_branch().expect("temp repo");
+        repo.write("feature.txt", "main\n").expect("write");
+        repo.commit_all("main adds feature.txt").expect("commit");
+        repo.run_git(["checkout", "-b", "other"])
+            .expect("create other");
+        repo.write("other.txt", "other\n").expect("write");
+        repo.commit_all("other work").expect("commit");
+        repo.run_git(["checkout", "main"]).expect("checkout main");
+
+        let outcome = repo
+            .cherry_pick("feature", &CherryPickOptions::default())
+            .expect("cherry-pick");
+        assert!(matches!(outcome, CherryPickOutcome::Conflicts { .. }));
+
+        // The user resolves the conflict but hasn't continued yet.
+        repo.write("feature.txt", "resolved\n").expect("write");
+        repo.run_git(["add", "feature.txt"]).expect("add");
+
+        let error = repo
+            .cherry_pick("other", &CherryPickOptions::default())
+            .expect_err("pick in progress");
+        assert!(error.to_string().contains("already in progress"), "{error}");
+
+        assert!(repo.git_dir().join("CHERRY_PICK_HEAD").exists());
+        assert_eq!(repo.staged_changes().expect("staged"), vec!["feature.txt"]);
+        assert_eq!(
+            std::fs::read_to_string(repo.path().join("feature.txt")).expect("read"),
+            "resolved\n"
+        );
+    }
+}
@@ -1,9 +1,12 @@
+use std::fmt;
+use std::fs;
 use std::process::Stdio;
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use chrono::{DateTime, Utc};
+use gix::bstr::ByteSlice;
 use gix::revision::walk::Sorting;
-use gix::{Id, traverse::commit::simple::CommitTimeOrder};
+use gix::{Id, ObjectId, traverse::commit::simple::CommitTimeOrder};
 
 use super::Repo;
 
@@ -15,16 +18,288 @@ pub struct Commit {
     pub author: String,
 }
 
+/// Commit metadata for display and prompts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitInfo {
+    pub id: String,
+    /// First 8 characters of `id`.
+    pub short_id: String,
+    /// First line of the message.
+    pub summary: String,
+    pub message: String,
+    pub author: String,
+    /// Author time in seconds since the Unix epoch.
+    pub timestamp: i64,
+}
+
+/// Whether a commit is signed and whether `git verify-commit` accepts the signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureStatus {
+    Good,
+    /// Signed, but the signature is invalid or the key isn't trusted.
+    Bad,
+    None,
+}
+
+/// Who made a commit, when, and whether it is signed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureInfo {
+    pub author: String,
+    pub committer: String,
+    /// Author time in seconds since the Unix epoch.
+    pub authored_at: i64,
+    /// Committer time in seconds since the Unix epoch.
+    pub committed_at: i64,
+    pub signature_status: SignatureStatus,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CommitOptions {
+    pub allow_empty: bool,
+    pub amend: bool,
+    /// Keep the existing message when amending; the message is ignored.
+    pub no_edit: bool,
+    /// Skip the pre-commit and commit-msg hooks.
+    pub no_verify: bool,
+    /// Create a `fixup!` commit for this revision; the message is ignored.
+    pub fixup: Option<String>,
+    /// Sign with the key from `user.signingkey`, in the format set by `gpg.format`.
+    pub sign: bool,
+}
+
+/// The git hook that rejected a commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitHook {
+    PreCommit,
+    CommitMsg,
+}
+
+impl CommitHook {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::PreCommit => "pre-commit",
+            Self::CommitMsg => "commit-msg",
+        }
+    }
+}
+
+/// Returned (inside the `anyhow::Error`) by [`Repo::create_commit`] when a hook fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookFailure {
+    pub hook: CommitHook,
+    /// Everything the hook printed.
+    pub output: String,
+}
+
+impl fmt::Display for HookFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The {} hook rejected the commit", self.hook.name())
+    }
+}
+
+impl std::error::Error for HookFailure {}
+
 impl Repo {
-    pub fn create_commit(&self, message: &str, allow_empty: bool, amend: bool) -> Result<()> {
+    /// Create a commit from the index.
+    ///
+    /// When a hook rejects it, the error downcasts to [`HookFailure`].
+    pub fn create_commit(&self, message: &str, options: &CommitOptions) -> Result<()> {
         let mut command = self.git()?.arg("commit");
-        if allow_empty {
+        if options.allow_empty {
             command = command.arg("--allow-empty");
         }
-        if amend {
+        if options.amend {
             command = command.arg("--amend");
         }
-        command.arg("-m").arg(message).stdout(Stdio::null()).run()
+        if options.no_verify {
+            command = command.arg("--no-verify");
+        }
+        if options.sign {
+            command = command.arg("-S");
+        }
+        match &options.fixup {
+            Some(target) => command = command.arg("--fixup").arg(target),
+            None if options.no_edit => command = command.arg("--no-edit"),
+            None => command = command.arg("-m").arg(message),
+        }
+
+        // git's trace2 events record each hook it runs and how it exited, so a failure
+        // is only blamed on a hook when that hook is what actually failed.
+        let trace = self.git_dir().join("sage").join("commit-trace.json");
+        if let Some(parent) = trace.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let _ = fs::remove_file(&trace);
+        let output = command
+            .env("GIT_TRACE2_EVENT", &trace)
+            .allow_failure()
+            .run_with_output();
+        let events = fs::read_to_string(&trace).unwrap_or_default();
+        let _ = fs::remove_file(&trace);
+        let output = output?;
+        if output.status.success() {
+            return Ok(());
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if let Some(hook) = failed_hook(&events) {
+            return Err(HookFailure {
+                hook,
+                output: stderr.trim_end().to_string(),
+            }
+            .into());
+        }
+
+        let details = if stderr.trim().is_empty() {
+            stdout.trim()
+        } else {
+            stderr.trim()
+        };
+        bail!("Failed to create commit: {details}")
+    }
+
+    /// The configured `user.signingkey`, if any.
+    pub fn signing_key(&self) -> Option<String> {
+        self.repo
+            .config_snapshot()
+            .string("user.signingkey")
+            .map(|key| key.to_string())
+            .filter(|key| !key.trim().is_empty())
+    }
+
+    /// Whether `rev` carries a signature that `git verify-commit` accepts.
+    pub fn verify_commit_signature(&self, rev: &str) -> Result<bool> {
+        let status = self
+            .git()?
+            .args(["verify-commit", rev])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .allow_failure()
+            .run_with_status()?;
+        Ok(status.success())
+    }
+
+    /// Author, committer and signature status of `rev`.
+    ///
+    /// Only signed commits are passed to `git verify-commit`.
+    pub fn commit_signature_info(&self, rev: &str) -> Result<SignatureInfo> {
+        let id = self.resolve_commit(rev)?;
+        let commit = self.repo.find_commit(id)?;
+        let author = commit.author()?;
+        let committer = commit.committer()?;
+
+        let signature_status = if commit.signature()?.is_none() {
+            SignatureStatus::None
+        } else if self.verify_commit_signature(&id.to_string())? {
+            SignatureStatus::Good
+        } else {
+            SignatureStatus::Bad
+        };
+
+        Ok(SignatureInfo {
+            author: author.name.to_str_lossy().into_owned(),
+            committer: committer.name.to_str_lossy().into_owned(),
+            authored_at: author.time()?.seconds,
+            committed_at: committer.time()?.seconds,
+            signature_status,
+        })
+    }
+
+    /// Change the last commit's message without touching the index or working tree.
+    ///
+    /// With no `message` the commit is re-created as-is (`--no-edit`).
+    pub fn reword_last_commit(&self, message: Option<&str>, no_verify: bool) -> Result<()> {
+        // `--only` with no pathspec commits HEAD's tree, ignoring anything staged.
+        let mut command = self
+            .git()?
+            .args(["commit", "--amend", "--only", "--allow-empty"]);
+        if no_verify {
+            command = command.arg("--no-verify");
+        }
+        match message {
+            Some(message) => command = command.arg("-m").arg(message),
+            None => command = command.arg("--no-edit"),
+        }
+        command.stdout(Stdio::null()).run()
+    }
+
+    /// Commits on `head` since it diverged from `base`, oldest first.
+    ///
+    /// Empty when `head` is already contained in `base`.
+    pub fn commits_between(&self, base: &str, head: &str) -> Result<Vec<CommitInfo>> {
+        let base_id = self.resolve_commit(base)?;
+        let head_id = self.resolve_commit(head)?;
+
+        let merge_base = match self.repo.merge_base(base_id, head_id) {
+            Ok(id) => Some(id.detach()),
+            Err(gix::repository::merge_base::Error::NotFound { .. }) => None,
+            Err(err) => return Err(err.into()),
+        };
+
+        let walk = self
+            .repo
+            .rev_walk([head_id])
+            .with_hidden(merge_base)
+            // Parent order rather than commit time, which ties for commits made within a second.
+            .sorting(Sorting::BreadthFirst)
+            .all()?;
+
+        let mut commits = Vec::new();
+        for info in walk {
+            commits.push(self.commit_info(info?.id)?);
+        }
+        commits.reverse();
+        Ok(commits)
+    }
+
+    /// History reachable from HEAD, newest first; empty on an unborn branch.
+    pub fn recent_commits(&self, limit: Option<usize>) -> Result<Vec<CommitInfo>> {
+        let Some(head) = self.repo.head()?.id() else {
+            return Ok(Vec::new());
+        };
+
+        let walk = self
+            .repo
+            .rev_walk([head.detach()])
+            .sorting(Sorting::ByCommitTime(CommitTimeOrder::NewestFirst))
+            .all()?;
+
+        let mut commits = Vec::new();
+        for info in walk.take(limit.unwrap_or(usize::MAX)) {
+            commits.push(self.commit_info(info?.id)?);
+        }
+        Ok(commits)
+    }
+
+    /// The commit HEAD points at, or `None` on an unborn branch.
+    pub fn current_commit_info(&self) -> Result<Option<CommitInfo>> {
+        match self.repo.head()?.id() {
+            Some(id) => Ok(Some(self.commit_info(id.detach())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// The commit `rev` (a branch, tag or hash) points at.
+    pub fn last_commit(&self, rev: &str) -> Result<CommitInfo> {
+        self.commit_info(self.resolve_commit(rev)?)
+    }
+
+    pub(crate) fn commit_info(&self, id: ObjectId) -> Result<CommitInfo> {
+        let commit = self.repo.find_commit(id)?;
+        let message = commit.message_raw()?.to_str_lossy().trim_end().to_string();
+        let summary = message.lines().next().unwrap_or_default().to_string();
+        let author = commit.author()?;
+
+        let id = id.to_hex().to_string();
+        Ok(CommitInfo {
+            short_id: id[..8].to_string(),
+            id,
+            summary,
+            message,
+            author: author.name.to_str_lossy().into_owned(),
+            timestamp: author.time()?.seconds,
+        })
     }
 
     pub fn get_current_commit(&self) -> Result<Id<'_>> {
@@ -85,3 +360,431 @@ impl Repo {
         Ok(commits)
     }
 }
+
+/// The pre-commit or commit-msg hook that exited non-zero, according to a git trace2
+/// event log. Events from git processes the hooks themselves run are ignored.
+fn failed_hook(events: &str) -> Option<CommitHook> {
+    let top_sid = events.lines().find_map(|line| json_field(line, "sid"))?;
+
+    let mut hooks = Vec::new();
+    for line in events.lines() {
+        if json_field(line, "sid") != Some(top_sid) {
+            continue;
+        }
+        let child_id = json_field(line, "child_id");
+        match json_field(line, "event") {
+            Some("child_start") if json_field(line, "child_class") == Some("hook") => {
+                let hook = match json_field(line, "hook_name") {
+                    Some("pre-commit") => CommitHook::PreCommit,
+                    Some("commit-msg") => CommitHook::CommitMsg,
+                    _ => continue,
+                };
+                hooks.push((child_id, hook));
+            }
+            Some("child_exit") if json_field(line, "code") != Some("0") => {
+                if let Some((_, hook)) = hooks.iter().find(|(id, _)| *id == child_id) {
+                    return Some(*hook);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The raw value of `"key":` in a single-line JSON object: a string's contents or a number.
+fn json_field<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let start = line.find(&format!("\"{key}\":"))? + key.len() + 3;
+    let rest = &line[start..];
+    match rest.strip_prefix('"') {
+        Some(string) => string.split('"').next(),
+        None => rest.split([',', '}']).next(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CommitHook, CommitOptions, HookFailure, SignatureStatus, failed_hook};
+    use crate::testing::TestRepo;
+
+    #[test]
+    fn commits_between_stops_at_merge_base() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        repo.write("base.txt", "base\n").expect("write");
+        repo.commit_all("base work").expect("commit");
+
+        repo.run_git(["checkout", "-b", "feature"])
+            .expect("create feature");
+        repo.write("one.txt", "one\n").expect("write");
+        repo.commit_all("feat: first\n\nWith a body")
+            .expect("commit");
+        repo.write("two.txt", "two\n").expect("write");
+        repo.commit_all("feat: second").expect("commit");
+
+        repo.run_git(["checkout", "main"]).expect("checkout main");
+        repo.write("main.txt", "main\n").expect("write");
+        repo.commit_all("main moved on").expect("commit");
+
+        let commits = repo.commits_between("main", "feature").expect("commits");
+        let summaries: Vec<&str> = commits.iter().map(|c| c.summary.as_str()).collect();
+        assert_eq!(summaries, vec!["feat: first", "feat: second"]);
+        assert_eq!(commits[0].message, "feat: first\n\nWith a body");
+        assert_eq!(commits[0].author, "Test User");
+        assert_eq!(commits[0].short_id, commits[0].id[..8]);
+
+        let reversed = repo.commits_between("feature", "main").expect("commits");
+        assert_eq!(reversed.len(), 1);
+        assert_eq!(reversed[0].summary, "main moved on");
+
+        repo.run_git(["branch", "merged", "main~1"])
+            .expect("branch");
+        let contained = repo.commits_between("main", "merged").expect("commits");
+        assert!(contained.is_empty());
+    }
+
+    #[test]
+    fn current_commit_info_is_none_when_unborn() {
+        let repo = TestRepo::builder().build().expect("temp repo");
+        assert_eq!(repo.current_commit_info().expect("info"), None);
+    }
+
+    #[test]
+    fn current_commit_info_reads_head() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+
+        let info = repo
+            .current_commit_info()
+            .expect("info")
+            .expect("head commit");
+        assert_eq!(info.summary, "Initial commit");
+        assert_eq!(
+            info.id,
+            repo.get_current_commit().expect("head").to_string()
+        );
+    }
+
+    #[test]
+    fn fixup_commit_targets_summary() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        repo.write("feature.txt", "one\n").expect("write");
+        repo.commit_all("feat: add feature").expect("commit");
+
+        repo.write("feature.txt", "two\n").expect("write");
+        repo.run_git(["add", "-A"]).expect("stage");
+        repo.create_commit(
+            "ignored",
+            &CommitOptions {
+                fixup: Some("HEAD".to_string()),
+                ..Default::default()
+            },
+        )
+        .expect("fixup commit");
+
+        let info = repo
+            .current_commit_info()
+            .expect("info")
+            .expect("head commit");
+        assert_eq!(info.summary, "fixup! feat: add feature");
+    }
+
+    #[test]
+    fn amend_no_edit_keeps_message_and_adds_changes() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        repo.write("feature.txt", "one\n").expect("write");
+        repo.commit_all("feat: add feature").expect("commit");
+
+        repo.write("extra.txt", "extra\n").expect("write");
+        repo.stage_all().expect("stage");
+        repo.create_commit(
+            "",
+            &CommitOptions {
+                amend: true,
+                no_edit: true,
+                ..Default::default()
+            },
+        )
+        .expect("amend");
+
+        let commits = repo.get_commits(Some(2)).expect("commits");
+        assert_eq!(commits[0].message.trim(), "feat: add feature");
+        assert_ne!(commits[1].message.trim(), "feat: add feature");
+        assert!(repo.staged_changes().expect("staged").is_empty());
+        let tree = repo
+            .git()
+            .args(["ls-tree", "--name-only", "HEAD"])
+            .output()
+            .expect("ls-tree");
+        assert!(String::from_utf8_lossy(&tree.stdout).contains("extra.txt"));
+    }
+
+    #[test]
+    fn signature_info_reports_unsigned_commit() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        let head = repo.last_commit("HEAD").expect("head");
+
+        let info = repo.commit_signature_info("HEAD").expect("signature info");
+        assert_eq!(info.author, head.author);
+        assert_eq!(info.committer, "Test User");
+        assert_eq!(info.authored_at, head.timestamp);
+        assert!(info.committed_at >= info.authored_at);
+        assert_eq!(info.signature_status, SignatureStatus::None);
+    }
+
+    #[test]
+    fn signs_commits_with_ssh_key() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        assert_eq!(repo.signing_key(), None);
+
+        let keys = tempfile::tempdir().expect("key dir");
+        let key = keys.path().join("id_ed25519");
+        let generated = std::process::Command::new("ssh-keygen")
+            .args(["-q", "-t", "ed25519", "-N", "", "-C", "test", "-f"])
+            .arg(&key)
+            .status();
+        if !matches!(generated, Ok(status) if status.success()) {
+            eprintln!("skipping: ssh-keygen is not available");
+            return;
+        }
+
+        let public_key = key.with_extension("pub");
+        let allowed = keys.path().join("allowed_signers");
+        let public = std::fs::read_to_string(&public_key).expect("read public key");
+        std::fs::write(&allowed, format!("test@example.com {public}")).expect("allowed signers");
+
+        repo.run_git(["config", "gpg.format", "ssh"])
+            .expect("gpg.format");
+        repo.run_git(["config", "user.signingkey", public_key.to_str().unwrap()])
+            .expect("signingkey");
+        repo.run_git([
+            "config",
+            "gpg.ssh.allowedSignersFile",
+            allowed.to_str().unwrap(),
+        ])
+        .expect("allowed signers");
+
+        let reopened = crate::Repo::discover(repo.path()).expect("reopen");
+        assert_eq!(reopened.signing_key().as_deref(), public_key.to_str());
+        assert!(!repo.verify_commit_signature("HEAD").expect("verify"));
+
+        repo.write("signed.txt", "signed\n").expect("write");
+        repo.run_git(["add", "-A"]).expect("stage");
+        repo.create_commit(
+            "signed work",
+            &CommitOptions {
+                sign: true,
+                ..Default::default()
+            },
+        )
+        .expect("signed commit");
+        assert!(repo.verify_commit_signature("HEAD").expect("verify"));
+
+        let info = repo.commit_signature_info("HEAD").expect("signature info");
+        assert_eq!(info.signature_status, SignatureStatus::Good);
+        let info = repo
+            .commit_signature_info("HEAD~1")
+            .expect("signature info");
+        assert_eq!(info.signature_status, SignatureStatus::None);
+
+        repo.run_git(["config", "--unset", "gpg.ssh.allowedSignersFile"])
+            .expect("unset allowed signers");
+        let info = repo.commit_signature_info("HEAD").expect("signature info");
+        assert_eq!(info.signature_status, SignatureStatus::Bad);
+    }
+
+    fn install_failing_pre_commit(repo: &TestRepo) {
+        install_failing_hook(repo, "pre-commit", "lint failed");
+    }
+
+    fn install_failing_hook(repo: &TestRepo, name: &str, message: &str) {
+        let hook = repo
+            .write(
+                format!(".git/hooks/{name}"),
+                format!("#!/bin/sh\necho '{message}'\nexit 1\n"),
+            )
+            .expect("write hook");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755))
+                .expect("make hook executable");
+        }
+    }
+
+    #[test]
+    fn failing_hooks_are_reported_by_name() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        let before = repo.current_commit_info().expect("info");
+        install_failing_pre_commit(&repo);
+        repo.write("file.txt", "content\n").expect("write file");
+        repo.stage_all().expect("stage");
+
+        let err = repo
+            .create_commit("feat: add file", &CommitOptions::default())
+            .expect_err("hook blocks commit");
+        let failure = err.downcast_ref::<HookFailure>().expect("hook failure");
+        assert_eq!(failure.hook, CommitHook::PreCommit);
+        assert_eq!(failure.output, "lint failed");
+        assert_eq!(repo.current_commit_info().expect("info"), before);
+
+        std::fs::remove_file(repo.path().join(".git/hooks/pre-commit")).expect("remove hook");
+        install_failing_hook(&repo, "commit-msg", "use conventional commits");
+        let err = repo
+            .create_commit("add file", &CommitOptions::default())
+            .expect_err("hook blocks commit");
+        let failure = err.downcast_ref::<HookFailure>().expect("hook failure");
+        assert_eq!(failure.hook, CommitHook::CommitMsg);
+        assert_eq!(failure.output, "use conventional commits");
+        assert_eq!(repo.current_commit_info().expect("info"), before);
+    }
+
+    #[test]
+    fn failures_after_a_passing_hook_are_not_blamed_on_it() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        let hook = repo
+            .write(".git/hooks/commit-msg", "#!/bin/sh\nexit 0\n")
+            .expect("write hook");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755))
+                .expect("make hook executable");
+        }
+        repo.run_git(["config", "user.signingkey", "ABCDEF"])
+            .expect("signing key");
+        repo.run_git(["config", "gpg.program", "false"])
+            .expect("gpg program");
+        repo.write("file.txt", "content\n").expect("write file");
+        repo.stage_all().expect("stage");
+
+        let err = repo
+            .create_commit(
+                "feat: add file",
+                &CommitOptions {
+                    sign: true,
+                    ..Default::default()
+                },
+            )
+            .expect_err("signing fails");
+        assert!(err.downcast_ref::<HookFailure>().is_none(), "{err}");
+        assert!(err.to_string().contains("Failed to create commit"), "{err}");
+    }
+
+    #[test]
+    fn failed_hook_reads_trace_events() {
+        let events = [
+            r#"{"event":"version","sid":"top","evt":"3"}"#,
+            r#"{"event":"child_start","sid":"top","child_id":0,"child_class":"hook","hook_name":"pre-commit"}"#,
+            r#"{"event":"child_exit","sid":"top","child_id":0,"pid":1,"code":0}"#,
+            r#"{"event":"child_start","sid":"top","child_id":1,"child_class":"hook","hook_name":"commit-msg"}"#,
+            r#"{"event":"child_exit","sid":"top/inner","child_id":1,"pid":2,"code":128}"#,
+            r#"{"event":"child_exit","sid":"top","child_id":1,"pid":3,"code":1}"#,
+        ]
+        .join("\n");
+        assert_eq!(failed_hook(&events), Some(CommitHook::CommitMsg));
+
+        let passing = events.replace(r#""code":1}"#, r#""code":0}"#);
+        assert_eq!(failed_hook(&passing), None);
+        assert_eq!(failed_hook(""), None);
+    }
+
+    #[test]
+    fn no_verify_skips_failing_pre_commit_hook() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        install_failing_pre_commit(&repo);
+        repo.write("file.txt", "content\n").expect("write file");
+        repo.stage_all().expect("stage");
+
+        let hooked = repo.create_commit("feat: add file", &CommitOptions::default());
+        assert!(hooked.is_err(), "failing hook should block the commit");
+
+        let options = CommitOptions {
+            no_verify: true,
+            ..Default::default()
+        };
+        repo.create_commit("feat: add file", &options)
+            .expect("commit bypassing hooks");
+        repo.create_commit(
+            "chore: empty",
+            &CommitOptions {
+                allow_empty: true,
+                no_verify: true,
+                ..Default::default()
+            },
+        )
+        .expect("empty commit bypassing hooks");
+
+        let commits = repo.get_commits(Some(2)).expect("commits");
+        assert_eq!(commits[0].message.trim(), "chore: empty");
+        assert_eq!(commits[1].message.trim(), "feat: add file");
+    }
+
+    #[test]
+    fn reword_leaves_index_and_worktree_alone() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        repo.write("file.txt", "one\n").expect("write file");
+        repo.commit_all("feat: add fiel").expect("commit");
+
+        repo.write("file.txt", "two\n").expect("modify file");
+        repo.write("staged.txt", "staged\n")
+            .expect("write staged file");
+        repo.run_git(["add", "staged.txt"]).expect("stage");
+
+        repo.reword_last_commit(Some("feat: add file"), false)
+            .expect("reword");
+
+        let commits = repo.get_commits(Some(2)).expect("commits");
+        assert_eq!(commits[0].message.trim(), "feat: add file");
+        assert_eq!(commits[1].message.trim(), "Initial commit");
+
+        let status = repo
+            .git()
+            .args(["status", "--porcelain"])
+            .output()
+            .expect("status");
+        let status = String::from_utf8_lossy(&status.stdout);
+        assert!(status.contains(" M file.txt"), "status: {status}");
+        assert!(status.contains("A  staged.txt"), "status: {status}");
+
+        let committed = repo
+            .git()
+            .args(["show", "HEAD:file.txt"])
+            .output()
+            .expect("show");
+        assert_eq!(String::from_utf8_lossy(&committed.stdout), "one\n");
+
+        repo.reword_last_commit(None, false)
+            .expect("reword no-edit");
+        let commits = repo.get_commits(Some(1)).expect("commits");
+        assert_eq!(commits[0].message.trim(), "feat: add file");
+    }
+}
@@ -1,10 +1,231 @@
-use anyhow::Result;
-use gix::config::SnapshotMut;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use gix::bstr::{BStr, ByteSlice};
 
 use super::Repo;
 
+/// Which git config file [`Repo::set_config`] writes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigScope {
+    /// The repository's own `.git/config`, shared by all of its worktrees.
+    Local,
+    /// The user's global config; see [`global_config_path`].
+    Global,
+}
+
 impl Repo {
-    pub fn get_config(&mut self) -> Result<SnapshotMut<'_>> {
-        Ok(self.repo.config_snapshot_mut())
+    /// Read `section[.subsection].key` from the effective config.
+    ///
+    /// Subsections may contain dots, e.g. `get_config("branch", Some("feat/v1.2"), "remote")`.
+    pub fn get_config(
+        &self,
+        section: &str,
+        subsection: Option<&str>,
+        key: &str,
+    ) -> Result<Option<String>> {
+        let config = self.repo.config_snapshot();
+        let subsection: Option<&BStr> = subsection.map(|name| name.as_bytes().as_bstr());
+        Ok(config
+            .string_by(section, subsection, key)
+            .map(|value| value.to_str_lossy().into_owned()))
+    }
+
+    /// Write `section[.subsection].key = value` and reload the repository's config.
+    pub fn set_config(
+        &mut self,
+        section: &str,
+        subsection: Option<&str>,
+        key: &str,
+        value: &str,
+        scope: ConfigScope,
+    ) -> Result<()> {
+        self.set_config_with_env(section, subsection, key, value, scope, |var| {
+            std::env::var(var).ok()
+        })
+    }
+
+    /// [`Repo::set_config`] with the environment used to find the global file passed in.
+    fn set_config_with_env(
+        &mut self,
+        section: &str,
+        subsection: Option<&str>,
+        key: &str,
+        value: &str,
+        scope: ConfigScope,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<()> {
+        let file = match scope {
+            // Linked worktrees have their own git dir, but git reads the common one's config.
+            ConfigScope::Local => self.repo.common_dir().join("config"),
+            ConfigScope::Global => global_config_path(env)?,
+        };
+        self.write_config(&file, &config_key(section, subsection, key), value)?;
+
+        // gix snapshots config when the repository is opened.
+        *self = Self::discover(self.repo_root())?;
+        Ok(())
+    }
+
+    fn write_config(&self, file: &Path, key: &str, value: &str) -> Result<()> {
+        self.git()?
+            .args(["config", "--file"])
+            .arg(file)
+            .args([key, value])
+            .run()
+    }
+}
+
+/// `section.subsection.key`; git treats everything between the first and last dot as the subsection.
+fn config_key(section: &str, subsection: Option<&str>, key: &str) -> String {
+    match subsection {
+        Some(subsection) => format!("{section}.{subsection}.{key}"),
+        None => format!("{section}.{key}"),
+    }
+}
+
+/// The file `git config --global` writes to.
+///
+/// `$GIT_CONFIG_GLOBAL` wins; otherwise `~/.gitconfig`, unless only the XDG config file exists.
+pub fn global_config_path(env: impl Fn(&str) -> Option<String>) -> Result<PathBuf> {
+    if let Some(path) = env("GIT_CONFIG_GLOBAL").filter(|path| !path.is_empty()) {
+        return Ok(PathBuf::from(path));
+    }
+
+    let home = env("HOME")
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
+        .ok_or_else(|| anyhow!("Cannot locate the global git config: $HOME is not set"))?;
+    let dotfile = home.join(".gitconfig");
+
+    let xdg = env("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".config"))
+        .join("git")
+        .join("config");
+
+    if !dotfile.exists() && xdg.exists() {
+        Ok(xdg)
+    } else {
+        Ok(dotfile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestRepo;
+
+    #[test]
+    fn set_local_config_is_visible_to_get_config() {
+        let test_repo = TestRepo::builder().build().expect("temp repo");
+        let mut repo = Repo::discover(test_repo.path()).expect("open repo");
+        assert_eq!(
+            repo.get_config("user", None, "name").expect("read"),
+            Some("Test User".to_string())
+        );
+
+        repo.set_config("user", None, "name", "Renamed", ConfigScope::Local)
+            .expect("write");
+        assert_eq!(
+            repo.get_config("user", None, "name").expect("read"),
+            Some("Renamed".to_string())
+        );
+
+        repo.set_config(
+            "branch",
+            Some("feat/v1.2"),
+            "remote",
+            "upstream",
+            ConfigScope::Local,
+        )
+        .expect("write dotted subsection");
+        assert_eq!(
+            repo.get_config("branch", Some("feat/v1.2"), "remote")
+                .expect("read"),
+            Some("upstream".to_string())
+        );
+        assert_eq!(
+            repo.get_config("branch", Some("feat/v1"), "remote")
+                .expect("read"),
+            None
+        );
+    }
+
+    #[test]
+    fn local_config_from_a_linked_worktree_reaches_the_main_repo() {
+        let main = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        let parent = tempfile::tempdir().expect("worktree parent");
+        let worktree_path = parent.path().join("wt");
+        main.run_git([
+            "worktree",
+            "add",
+            "-q",
+            "-b",
+            "wt",
+            worktree_path.to_str().unwrap(),
+        ])
+        .expect("add worktree");
+
+        let mut worktree = Repo::discover(&worktree_path).expect("open worktree");
+        worktree
+            .set_config("sage", None, "shared", "yes", ConfigScope::Local)
+            .expect("write");
+
+        assert_eq!(
+            worktree.get_config("sage", None, "shared").expect("read"),
+            Some("yes".to_string())
+        );
+        let main = Repo::discover(main.path()).expect("reopen main");
+        assert_eq!(
+            main.get_config("sage", None, "shared").expect("read"),
+            Some("yes".to_string())
+        );
+    }
+
+    #[test]
+    fn global_scope_resolves_and_writes_global_file() {
+        let repo = TestRepo::builder().build().expect("temp repo");
+        let home = tempfile::tempdir().expect("home");
+        let explicit = home.path().join("custom.gitconfig");
+        let home_str = home.path().to_str().unwrap().to_string();
+
+        let env_home = |var: &str| (var == "HOME").then(|| home_str.clone());
+        assert_eq!(
+            global_config_path(env_home).expect("path"),
+            home.path().join(".gitconfig")
+        );
+
+        let xdg = home.path().join(".config/git/config");
+        std::fs::create_dir_all(xdg.parent().unwrap()).expect("xdg dir");
+        std::fs::write(&xdg, "").expect("xdg file");
+        assert_eq!(global_config_path(env_home).expect("path"), xdg);
+
+        let explicit_str = explicit.to_str().unwrap().to_string();
+        let env_explicit = |var: &str| match var {
+            "GIT_CONFIG_GLOBAL" => Some(explicit_str.clone()),
+            "HOME" => Some(home_str.clone()),
+            _ => None,
+        };
+        let path = global_config_path(env_explicit).expect("path");
+        assert_eq!(path, explicit);
+
+        let mut repo = Repo::discover(repo.path()).expect("open repo");
+        repo.set_config_with_env(
+            "sage",
+            None,
+            "scope",
+            "global",
+            ConfigScope::Global,
+            env_explicit,
+        )
+        .expect("write");
+        let written = std::fs::read_to_string(&explicit).expect("read global file");
+        assert!(written.contains("[sage]"), "{written}");
+        assert!(written.contains("scope = global"), "{written}");
     }
 }
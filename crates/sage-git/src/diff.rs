@@ -11,6 +11,85 @@ use gix::status::tree_index::TrackRenames;
 
 use crate::Repo;
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStat {
+    pub path: String,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+/// Per-file line counts for the staged changes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiffStat {
+    pub files: Vec<FileStat>,
+}
+
+impl DiffStat {
+    pub fn additions(&self) -> usize {
+        self.files.iter().map(|file| file.additions).sum()
+    }
+
+    pub fn deletions(&self) -> usize {
+        self.files.iter().map(|file| file.deletions).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// One-line summary, e.g. `3 files changed, +10 -2`.
+    pub fn summary(&self) -> String {
+        let count = self.files.len();
+        let noun = if count == 1 { "file" } else { "files" };
+        format!(
+            "{count} {noun} changed, +{} -{}",
+            self.additions(),
+            self.deletions()
+        )
+    }
+
+    fn parse_numstat(output: &str) -> Self {
+        let files = output
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(3, '\t');
+                let additions = parts.next()?;
+                let deletions = parts.next()?;
+                let path = parts.next()?;
+                // Binary files report `-` for both counts.
+                Some(FileStat {
+                    path: path.to_string(),
+                    additions: additions.parse().unwrap_or(0),
+                    deletions: deletions.parse().unwrap_or(0),
+                })
+            })
+            .collect();
+        Self { files }
+    }
+}
+
+/// Which changes [`Repo::diff_text`] and [`Repo::diff_stat_of`] look at.
+#[derive(Debug, Clone, Default)]
+pub struct DiffOptions {
+    /// Compare the index with HEAD instead of the working tree with the index.
+    pub staged: bool,
+    /// Limit the diff to these paths; empty means everything.
+    pub paths: Vec<String>,
+}
+
+impl DiffOptions {
+    fn args<'a>(&'a self, extra: &[&'a str]) -> Vec<&'a str> {
+        let mut args = vec!["diff", "--no-color", "--no-ext-diff"];
+        if self.staged {
+            args.push("--cached");
+        }
+        args.extend_from_slice(extra);
+        args.push("--");
+        args.extend(self.paths.iter().map(String::as_str));
+        args
+    }
+}
+
 struct Side {
     path: BString,
     mode: IndexMode,
@@ -25,6 +104,29 @@ struct ChangeData {
 }
 
 impl Repo {
+    pub fn diff_stat(&self) -> Result<DiffStat> {
+        self.diff_stat_of(&DiffOptions {
+            staged: true,
+            ..DiffOptions::default()
+        })
+    }
+
+    pub fn diff_stat_of(&self, options: &DiffOptions) -> Result<DiffStat> {
+        let output = self
+            .git()?
+            .args(options.args(&["--numstat"]))
+            .run_with_output()?;
+        Ok(DiffStat::parse_numstat(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+
+    /// The unified diff git would show for `options`, for displaying to the user.
+    pub fn diff_text(&self, options: &DiffOptions) -> Result<String> {
+        let output = self.git()?.args(options.args(&[])).run_with_output()?;
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
     pub fn diff_ai(&self) -> Result<String> {
         let index = match self.repo.open_index() {
             Ok(index) => index,
@@ -350,3 +452,80 @@ fn marker_name(side: Option<&Side>, prefix: &str) -> String {
 fn format_path(path: &BStr) -> String {
     path.to_str_lossy().into_owned()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{DiffOptions, DiffStat};
+    use crate::testing::TestRepo;
+
+    #[test]
+    fn diff_stat_counts_staged_lines() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        repo.write("a.txt", "one\ntwo\n").expect("write a");
+        repo.commit_all("add a").expect("commit a");
+
+        repo.write("a.txt", "one\nthree\nfour\n").expect("modify a");
+        repo.write("b.txt", "new\n").expect("write b");
+        repo.write("unstaged.txt", "ignored\n")
+            .expect("write unstaged");
+        repo.run_git(["add", "a.txt", "b.txt"]).expect("stage");
+
+        let stat = repo.diff_stat().expect("diff stat");
+        assert_eq!(stat.files.len(), 2);
+        assert_eq!(stat.additions(), 3);
+        assert_eq!(stat.deletions(), 1);
+        assert_eq!(stat.summary(), "2 files changed, +3 -1");
+    }
+
+    #[test]
+    fn staged_diff_excludes_unstaged_changes() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        repo.write("staged.txt", "one\n").expect("write staged");
+        repo.write("unstaged.txt", "one\n").expect("write unstaged");
+        repo.commit_all("add files").expect("commit");
+
+        repo.write("staged.txt", "two\n").expect("modify staged");
+        repo.write("unstaged.txt", "two\n")
+            .expect("modify unstaged");
+        repo.run_git(["add", "staged.txt"]).expect("stage");
+
+        let staged = DiffOptions {
+            staged: true,
+            ..DiffOptions::default()
+        };
+        let text = repo.diff_text(&staged).expect("staged diff");
+        assert!(text.contains("+++ b/staged.txt"), "{text}");
+        assert!(!text.contains("unstaged.txt"), "{text}");
+
+        let text = repo
+            .diff_text(&DiffOptions::default())
+            .expect("working diff");
+        assert!(text.contains("+++ b/unstaged.txt"), "{text}");
+        assert!(!text.contains("b/staged.txt"), "{text}");
+
+        let limited = DiffOptions {
+            staged: true,
+            paths: vec!["unstaged.txt".to_owned()],
+        };
+        assert!(
+            repo.diff_stat_of(&limited)
+                .expect("limited stat")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn numstat_treats_binary_counts_as_zero() {
+        let stat = DiffStat::parse_numstat("-\t-\timage.png\n4\t0\tsrc/lib.rs\n");
+        assert_eq!(stat.files[0].path, "image.png");
+        assert_eq!(stat.files[0].additions, 0);
+        assert_eq!(stat.additions(), 4);
+        assert_eq!(stat.summary(), "2 files changed, +4 -0");
+    }
+}
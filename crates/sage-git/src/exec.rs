@@ -1,7 +1,7 @@
 use std::{
     ffi::OsString,
     path::PathBuf,
-    process::{Command, ExitStatus, Output, Stdio},
+    process::{Child, Command, ExitStatus, Output, Stdio},
 };
 
 use anyhow::{Result, anyhow, bail};
@@ -113,6 +113,14 @@ impl GitCommand {
         Ok(output)
     }
 
+    /// Start the command without waiting for it; the caller owns the child's pipes.
+    pub fn spawn(mut self) -> Result<Child> {
+        let command_line = self.command_line();
+        self.prepare_command()
+            .spawn()
+            .map_err(|err| anyhow!("failed to spawn {command_line}: {err}"))
+    }
+
     fn command_line(&self) -> String {
         let mut parts = Vec::with_capacity(self.args.len() + 3);
         parts.push("git".to_string());
@@ -0,0 +1,263 @@
+use std::io::Write;
+use std::process::Stdio;
+
+use anyhow::{Context, Result, bail};
+
+use super::Repo;
+
+/// One `@@` section of a file's diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    /// The `@@ -a,b +c,d @@` line.
+    pub header: String,
+    /// Context, `+` and `-` lines, without trailing newlines.
+    pub lines: Vec<String>,
+}
+
+impl Hunk {
+    pub fn additions(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|line| line.starts_with('+'))
+            .count()
+    }
+
+    pub fn deletions(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|line| line.starts_with('-'))
+            .count()
+    }
+}
+
+/// A file's unstaged diff, split into hunks that can be staged on their own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDiff {
+    pub path: String,
+    /// `diff --git`, mode and `---`/`+++` lines preceding the first hunk.
+    header: Vec<String>,
+    pub hunks: Vec<Hunk>,
+}
+
+impl FileDiff {
+    /// A patch containing only the hunks at `indices`, suitable for `git apply`.
+    pub fn patch(&self, indices: &[usize]) -> String {
+        let mut patch = String::new();
+        let hunks = indices.iter().filter_map(|&index| self.hunks.get(index));
+        for line in self
+            .header
+            .iter()
+            .chain(hunks.flat_map(|hunk| std::iter::once(&hunk.header).chain(hunk.lines.iter())))
+        {
+            patch.push_str(line);
+            patch.push('\n');
+        }
+        patch
+    }
+}
+
+/// A hunk to stage, addressed by file path and its index in [`FileDiff::hunks`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HunkSelection {
+    pub path: String,
+    pub hunk: usize,
+}
+
+impl Repo {
+    /// Unstaged changes to tracked files, split per file and hunk.
+    pub fn unstaged_hunks(&self) -> Result<Vec<FileDiff>> {
+        let output = self
+            .git()?
+            .args([
+                "diff",
+                "--no-color",
+                "--no-ext-diff",
+                "--src-prefix=a/",
+                "--dst-prefix=b/",
+            ])
+            .run_with_output()?;
+        Ok(parse_diff(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Stage just the selected hunks of the current unstaged diff.
+    ///
+    /// Selecting every hunk of a file stages the whole file.
+    pub fn stage_hunks(&self, selections: &[HunkSelection]) -> Result<()> {
+        let mut patch = String::new();
+        for diff in self.unstaged_hunks()? {
+            let indices: Vec<usize> = selections
+                .iter()
+                .filter(|selection| selection.path == diff.path)
+                .map(|selection| selection.hunk)
+                .collect();
+            if !indices.is_empty() {
+                patch.push_str(&diff.patch(&indices));
+            }
+        }
+        if patch.is_empty() {
+            return Ok(());
+        }
+
+        let mut child = self
+            .git()?
+            .args(["apply", "--cached", "--recount", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        child
+            .stdin
+            .take()
+            .context("git apply has no stdin")?
+            .write_all(patch.as_bytes())?;
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("Failed to stage selected hunks: {}", stderr.trim());
+        }
+        Ok(())
+    }
+}
+
+/// Split `git diff` output into files and hunks.
+fn parse_diff(diff: &str) -> Vec<FileDiff> {
+    let mut files: Vec<FileDiff> = Vec::new();
+
+    for line in diff.lines() {
+        if let Some(paths) = line.strip_prefix("diff --git ") {
+            files.push(FileDiff {
+                path: paths
+                    .split_once(" b/")
+                    .map_or(paths, |(_, path)| path)
+                    .to_string(),
+                header: vec![line.to_string()],
+                hunks: Vec::new(),
+            });
+            continue;
+        }
+
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+
+        if line.starts_with("@@") {
+            file.hunks.push(Hunk {
+                header: line.to_string(),
+                lines: Vec::new(),
+            });
+        } else if let Some(hunk) = file.hunks.last_mut() {
+            hunk.lines.push(line.to_string());
+        } else {
+            if let Some(path) = line.strip_prefix("+++ b/") {
+                file.path = path.to_string();
+            }
+            file.header.push(line.to_string());
+        }
+    }
+
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestRepo;
+
+    const SAMPLE: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+-use old;
++use new;
+ 
+ fn main() {}
+@@ -20,2 +20,3 @@ fn helper() {
+     body();
++    more();
+ }
+diff --git a/notes.txt b/notes.txt
+deleted file mode 100644
+index 3333333..0000000
+--- a/notes.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-last line
+\\ No newline at end of file
+";
+
+    #[test]
+    fn parses_files_and_hunks() {
+        let files = parse_diff(SAMPLE);
+        assert_eq!(files.len(), 2);
+
+        let lib = &files[0];
+        assert_eq!(lib.path, "src/lib.rs");
+        assert_eq!(lib.hunks.len(), 2);
+        assert_eq!(lib.hunks[0].header, "@@ -1,3 +1,3 @@");
+        assert_eq!((lib.hunks[0].additions(), lib.hunks[0].deletions()), (1, 1));
+        assert_eq!(
+            lib.hunks[1].lines,
+            vec!["     body();", "+    more();", " }"]
+        );
+
+        let notes = &files[1];
+        assert_eq!(notes.path, "notes.txt");
+        assert_eq!(notes.hunks.len(), 1);
+        assert_eq!(
+            notes.hunks[0].lines.last().map(String::as_str),
+            Some("\\ No newline at end of file")
+        );
+    }
+
+    #[test]
+    fn patch_keeps_header_and_selected_hunks() {
+        let files = parse_diff(SAMPLE);
+        let patch = files[0].patch(&[1]);
+
+        assert!(patch.starts_with("diff --git a/src/lib.rs b/src/lib.rs\n"));
+        assert!(patch.contains("+++ b/src/lib.rs\n@@ -20,2 +20,3 @@"));
+        assert!(!patch.contains("+use new;"));
+        assert!(patch.ends_with(" }\n"));
+    }
+
+    #[test]
+    fn stages_only_selected_hunk() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        let original: String = (1..=20).map(|n| format!("line {n}\n")).collect();
+        repo.write("file.txt", &original).expect("write");
+        repo.commit_all("add file").expect("commit");
+
+        let changed = original
+            .replace("line 1\n", "first\n")
+            .replace("line 20\n", "last\n");
+        repo.write("file.txt", &changed).expect("write");
+
+        let files = repo.unstaged_hunks().expect("hunks");
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].hunks.len(), 2);
+
+        repo.stage_hunks(&[HunkSelection {
+            path: "file.txt".to_string(),
+            hunk: 1,
+        }])
+        .expect("stage");
+
+        let staged = repo
+            .git()
+            .args(["diff", "--cached"])
+            .output()
+            .expect("staged diff");
+        let staged = String::from_utf8_lossy(&staged.stdout);
+        assert!(staged.contains("+last"), "{staged}");
+        assert!(!staged.contains("+first"), "{staged}");
+
+        let remaining = repo.unstaged_hunks().expect("hunks");
+        assert_eq!(remaining[0].hunks.len(), 1);
+        assert!(remaining[0].hunks[0].lines.contains(&"+first".to_string()));
+    }
+}
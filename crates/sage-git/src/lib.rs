@@ -3,17 +3,48 @@ use std::path::{Path, PathBuf};
 use anyhow::Result;
 use gix::{Repository, bstr::ByteSlice};
 
+mod blame;
 mod branch;
+mod cherry_pick;
 mod commit;
 mod config;
 mod diff;
 mod exec;
+mod fetch;
+mod hunk;
+mod merge;
+mod rebase;
+mod reflog;
+mod remote;
 mod stage;
+mod stash;
 mod status;
+mod submodule;
+mod tag;
+mod worktree;
 
 #[cfg(any(test, feature = "testing"))]
 pub mod testing;
 
+pub use blame::{BlameCommit, BlameLine};
+pub use branch::{HeadState, PushMode, SwitchOptions, validate_branch_name};
+pub use cherry_pick::{CherryPickOptions, CherryPickOutcome};
+pub use commit::{
+    CommitHook, CommitInfo, CommitOptions, HookFailure, SignatureInfo, SignatureStatus,
+};
+pub use config::{ConfigScope, global_config_path};
+pub use diff::{DiffOptions, DiffStat, FileStat};
+pub use fetch::{FetchPhase, FetchProgress};
+pub use hunk::{FileDiff, Hunk, HunkSelection};
+pub use merge::{MergeOptions, MergeOutcome};
+pub use rebase::RebaseOutcome;
+pub use reflog::ReflogEntry;
+pub use remote::RemoteUrl;
+pub use stash::{StashId, StashOptions};
+pub use submodule::SubmoduleInfo;
+pub use tag::TagInfo;
+pub use worktree::WorktreeInfo;
+
 #[derive(Debug)]
 pub struct Repo {
     /// Repo
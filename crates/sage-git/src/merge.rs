@@ -0,0 +1,144 @@
+use std::path::PathBuf;
+
+use anyhow::{Result, bail};
+
+use super::Repo;
+
+#[derive(Debug, Clone, Default)]
+pub struct MergeOptions {
+    /// Always create a merge commit, even when a fast-forward is possible.
+    pub no_ff: bool,
+    /// Refuse to merge unless it can fast-forward.
+    pub ff_only: bool,
+    /// Message for the merge commit; git's default message otherwise.
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeOutcome {
+    /// HEAD moved forward to `commit` without a merge commit.
+    FastForward { commit: String },
+    /// A merge commit `commit` was created.
+    Merged { commit: String },
+    /// The merge stopped on conflicts in these files and is still in progress.
+    Conflicts { files: Vec<PathBuf> },
+    /// `branch` was already contained in HEAD; nothing changed.
+    UpToDate,
+}
+
+impl Repo {
+    /// Merge `branch` into the currently checked out branch.
+    ///
+    /// On conflicts the merge is left in progress so the caller can decide whether to
+    /// resolve it or run `git merge --abort`.
+    pub fn merge(&self, branch: &str, options: &MergeOptions) -> Result<MergeOutcome> {
+        if options.no_ff && options.ff_only {
+            bail!("Cannot combine no-ff and ff-only merges");
+        }
+
+        let theirs = self.resolve_commit(branch)?;
+        if self.is_ancestor(&theirs.to_string(), "HEAD")? {
+            return Ok(MergeOutcome::UpToDate);
+        }
+
+        let mut command = self.git()?.args(["merge", "--no-edit"]);
+        if options.no_ff {
+            command = command.arg("--no-ff");
+        }
+        if options.ff_only {
+            command = command.arg("--ff-only");
+        }
+        if let Some(message) = &options.message {
+            command = command.args(["-m", message]);
+        }
+        let output = command.arg(branch).allow_failure().run_with_output()?;
+
+        if !output.status.success() {
+            let files = self.conflicted_files()?;
+            if files.is_empty() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                bail!("Failed to merge '{branch}': {}", stderr.trim());
+            }
+            return Ok(MergeOutcome::Conflicts { files });
+        }
+
+        let after = self.resolve_commit("HEAD")?;
+        let commit = after.to_string();
+        if after == theirs {
+            Ok(MergeOutcome::FastForward { commit })
+        } else {
+            Ok(MergeOutcome::Merged { commit })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestRepo;
+
+    #[test]
+    fn fast_forward_merge_moves_head() {
+        let repo = TestRepo::with_feature_branch().expect("temp repo");
+        let feature = repo.last_commit("feature").expect("feature tip");
+
+        let outcome = repo
+            .merge("feature", &MergeOptions::default())
+            .expect("merge");
+        assert_eq!(
+            outcome,
+            MergeOutcome::FastForward {
+                commit: feature.id.clone()
+            }
+        );
+
+        let outcome = repo
+            .merge("feature", &MergeOptions::default())
+            .expect("merge again");
+        assert_eq!(outcome, MergeOutcome::UpToDate);
+    }
+
+    #[test]
+    fn diverged_merge_creates_merge_commit() {
+        let repo = TestRepo::with_feature_branch().expect("temp repo");
+        repo.write("main.txt", "main\n").expect("write");
+        repo.commit_all("main moves on").expect("commit");
+
+        let outcome = repo
+            .merge(
+                "feature",
+                &MergeOptions {
+                    message: Some("Merge feature".to_string()),
+                    ..Default::default()
+                },
+            )
+            .expect("merge");
+        let MergeOutcome::Merged { commit } = outcome else {
+            panic!("expected a merge commit, got {outcome:?}");
+        };
+
+        let head = repo.last_commit("HEAD").expect("head");
+        assert_eq!(head.id, commit);
+        assert_eq!(head.summary, "Merge feature");
+        assert!(repo.is_ancestor("feature", "HEAD").expect("ancestry"));
+    }
+
+    #[test]
+    fn conflicting_merge_reports_files() {
+        let repo = TestRepo::with_feature_branch().expect("temp repo");
+        repo.write("feature.txt", "main\n").expect("write");
+        repo.commit_all("main adds feature.txt").expect("commit");
+
+        let outcome = repo
+            .merge("feature", &MergeOptions::default())
+            .expect("merge");
+        assert_eq!(
+            outcome,
+            MergeOutcome::Conflicts {
+                files: vec![PathBuf::from("feature.txt")]
+            }
+        );
+
+        repo.run_git(["merge", "--abort"]).expect("abort");
+    }
+}
@@ -0,0 +1,88 @@
+use std::path::PathBuf;
+
+use anyhow::{Result, bail};
+
+use super::Repo;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RebaseOutcome {
+    /// The branch was rewritten onto the new base.
+    Completed,
+    /// The rebase stopped on conflicts in these files and is still in progress.
+    Conflicts { files: Vec<PathBuf> },
+    /// The branch already sat on the new base; nothing was rewritten.
+    UpToDate,
+}
+
+impl Repo {
+    /// Replay the commits in `upstream..branch` on top of `onto` (`git rebase --onto`).
+    ///
+    /// Leaves `branch` checked out. On conflicts the rebase is left in progress so the
+    /// caller can decide whether to continue or abort.
+    pub fn rebase_onto(&self, branch: &str, upstream: &str, onto: &str) -> Result<RebaseOutcome> {
+        let branch = self.remove_ref(branch);
+        let branch_ref = self.as_ref(&branch);
+        let before = self.resolve_commit(&branch_ref)?;
+
+        let output = self
+            .git()?
+            .args(["rebase", "--onto", onto, upstream, &branch])
+            .allow_failure()
+            .run_with_output()?;
+
+        if !output.status.success() {
+            let files = self.conflicted_files()?;
+            if files.is_empty() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                bail!(
+                    "Failed to rebase '{branch}' onto '{onto}': {}",
+                    stderr.trim()
+                );
+            }
+            return Ok(RebaseOutcome::Conflicts { files });
+        }
+
+        if self.resolve_commit(&branch_ref)? == before {
+            Ok(RebaseOutcome::UpToDate)
+        } else {
+            Ok(RebaseOutcome::Completed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestRepo;
+
+    #[test]
+    fn clean_rebase_completes_then_is_up_to_date() {
+        let repo = TestRepo::with_feature_branch().expect("temp repo");
+        repo.write("main.txt", "main\n").expect("write");
+        repo.commit_all("main moves on").expect("commit");
+
+        let outcome = repo.rebase_onto("feature", "main", "main").expect("rebase");
+        assert_eq!(outcome, RebaseOutcome::Completed);
+        assert!(repo.is_ancestor("main", "feature").expect("ancestry"));
+
+        let outcome = repo.rebase_onto("feature", "main", "main").expect("rebase");
+        assert_eq!(outcome, RebaseOutcome::UpToDate);
+    }
+
+    #[test]
+    fn conflicting_rebase_reports_files() {
+        let repo = TestRepo::with_feature_branch().expect("temp repo");
+        repo.write("feature.txt", "main\n").expect("write");
+        repo.commit_all("main adds feature.txt").expect("commit");
+
+        let outcome = repo.rebase_onto("feature", "main", "main").expect("rebase");
+        assert_eq!(
+            outcome,
+            RebaseOutcome::Conflicts {
+                files: vec![PathBuf::from("feature.txt")]
+            }
+        );
+
+        repo.run_git(["rebase", "--abort"]).expect("abort");
+    }
+}
@@ -0,0 +1,77 @@
+use anyhow::Result;
+use gix::bstr::ByteSlice;
+
+use super::Repo;
+
+/// One reflog line: the ref moved from `old_id` to `new_id`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReflogEntry {
+    /// All zeros when the ref was created.
+    pub old_id: String,
+    pub new_id: String,
+    pub message: String,
+    /// Seconds since the Unix epoch.
+    pub timestamp: i64,
+}
+
+impl Repo {
+    /// Reflog of `refname` (e.g. `HEAD` or a branch name), newest first.
+    ///
+    /// Refs without a reflog, including ones that no longer exist, yield an empty list.
+    pub fn reflog(&self, refname: &str) -> Result<Vec<ReflogEntry>> {
+        let name = if refname == "HEAD" {
+            refname.to_string()
+        } else {
+            self.as_ref(refname)
+        };
+        let Some(reference) = self.repo.try_find_reference(name.as_str())? else {
+            return Ok(Vec::new());
+        };
+
+        let mut platform = reference.log_iter();
+        let Some(lines) = platform.rev()? else {
+            return Ok(Vec::new());
+        };
+
+        lines
+            .map(|line| {
+                let line = line?;
+                Ok(ReflogEntry {
+                    old_id: line.previous_oid.to_string(),
+                    new_id: line.new_oid.to_string(),
+                    message: line.message.to_str_lossy().into_owned(),
+                    timestamp: line.signature.time.seconds,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::TestRepo;
+
+    #[test]
+    fn reflog_lists_entries_newest_first() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        repo.write("one.txt", "one\n").expect("write");
+        repo.commit_all("first change").expect("commit");
+        repo.write("two.txt", "two\n").expect("write");
+        repo.commit_all("second change").expect("commit");
+
+        let head = repo.last_commit("HEAD").expect("head");
+        let entries = repo.reflog("main").expect("reflog");
+        assert_eq!(entries.len(), 3, "{entries:?}");
+        assert_eq!(entries[0].new_id, head.id);
+        assert!(entries[0].message.contains("second change"), "{entries:?}");
+        assert!(entries[1].message.contains("first change"), "{entries:?}");
+        assert_eq!(entries[0].old_id, entries[1].new_id);
+        assert!(entries[0].timestamp >= entries[1].timestamp);
+
+        assert_eq!(repo.reflog("HEAD").expect("head reflog")[0].new_id, head.id);
+        assert!(repo.reflog("missing").expect("no reflog").is_empty());
+    }
+}
@@ -0,0 +1,156 @@
+use anyhow::Result;
+use gix::bstr::{BStr, ByteSlice};
+
+use super::Repo;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteUrl {
+    /// `ssh`, `https`, `http` or `git`; scp-like `git@host:path` URLs report `ssh`.
+    pub scheme: String,
+    /// Lower-cased host without user or port.
+    pub host: String,
+    /// Everything before the repository name; includes subgroups, e.g. `group/sub`.
+    pub owner: String,
+    /// Repository name without the `.git` suffix.
+    pub repo: String,
+}
+
+impl RemoteUrl {
+    pub fn parse(url: &str) -> Option<Self> {
+        let url = url.trim();
+        let (scheme, host, path) = if let Some((scheme, rest)) = url.split_once("://") {
+            let (authority, path) = rest.split_once('/')?;
+            let host = authority.rsplit('@').next()?;
+            // Drop any port; ssh ports aren't part of the web host.
+            let host = host.split(':').next()?;
+            (scheme.to_ascii_lowercase(), host, path)
+        } else {
+            let (user_host, path) = url.split_once(':')?;
+            ("ssh".to_string(), user_host.rsplit('@').next()?, path)
+        };
+
+        if scheme == "file" || host.is_empty() {
+            return None;
+        }
+
+        let path = path.trim_matches('/');
+        let path = path.strip_suffix(".git").unwrap_or(path);
+        let (owner, repo) = path.rsplit_once('/')?;
+        if owner.is_empty() || repo.is_empty() || owner.split('/').any(str::is_empty) {
+            return None;
+        }
+
+        Some(Self {
+            scheme,
+            host: host.to_ascii_lowercase(),
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        })
+    }
+
+    /// `owner/repo`, including any subgroups.
+    pub fn path(&self) -> String {
+        format!("{}/{}", self.owner, self.repo)
+    }
+}
+
+impl Repo {
+    /// Parsed URL of `remote`; `None` if the remote doesn't exist or isn't a hosted URL.
+    pub fn remote_url(&self, remote: &str) -> Result<Option<RemoteUrl>> {
+        let config = self.repo.config_snapshot();
+        let subsection: Option<&BStr> = Some(remote.as_bytes().as_bstr());
+        Ok(config
+            .string_by("remote", subsection, "url")
+            .and_then(|url| RemoteUrl::parse(&url.to_str_lossy())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestRepo;
+
+    #[test]
+    fn parses_remote_urls() {
+        let cases = [
+            (
+                "git@github.com:sage-scm/sage.git",
+                ("ssh", "github.com", "sage-scm", "sage"),
+            ),
+            (
+                "ssh://git@github.com/sage-scm/sage.git",
+                ("ssh", "github.com", "sage-scm", "sage"),
+            ),
+            (
+                "https://github.com/sage-scm/sage",
+                ("https", "github.com", "sage-scm", "sage"),
+            ),
+            (
+                "https://GitHub.com/sage-scm/sage.git/",
+                ("https", "github.com", "sage-scm", "sage"),
+            ),
+            (
+                "ssh://git@git.example.com:2222/team/project.git",
+                ("ssh", "git.example.com", "team", "project"),
+            ),
+            (
+                "https://user@git.example.com/team/project",
+                ("https", "git.example.com", "team", "project"),
+            ),
+            (
+                "git@gitlab.com:group/sub/project.git",
+                ("ssh", "gitlab.com", "group/sub", "project"),
+            ),
+            (
+                "https://gitlab.com/group/sub/deeper/project.git",
+                ("https", "gitlab.com", "group/sub/deeper", "project"),
+            ),
+        ];
+
+        for (url, (scheme, host, owner, repo)) in cases {
+            let parsed = RemoteUrl::parse(url).unwrap_or_else(|| panic!("failed to parse {url}"));
+            assert_eq!(
+                (
+                    parsed.scheme.as_str(),
+                    parsed.host.as_str(),
+                    parsed.owner.as_str(),
+                    parsed.repo.as_str()
+                ),
+                (scheme, host, owner, repo),
+                "url: {url}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_local_and_incomplete_urls() {
+        for url in [
+            "/srv/git/project.git",
+            "file:///srv/git/project.git",
+            "https://github.com/sage",
+            "git@github.com:",
+        ] {
+            assert_eq!(RemoteUrl::parse(url), None, "url: {url}");
+        }
+    }
+
+    #[test]
+    fn missing_remote_is_none() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        repo.run_git([
+            "remote",
+            "add",
+            "origin",
+            "git@github.com:sage-scm/sage.git",
+        ])
+        .expect("add remote");
+        let repo = Repo::discover(repo.path()).expect("reopen repo");
+
+        assert_eq!(repo.remote_url("upstream").expect("lookup"), None);
+        let origin = repo.remote_url("origin").expect("lookup").expect("origin");
+        assert_eq!(origin.path(), "sage-scm/sage");
+    }
+}
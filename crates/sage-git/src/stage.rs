@@ -1,6 +1,9 @@
+use std::io::Read;
 use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Context, Result, anyhow, bail};
+use gix::bstr::{BStr, BString};
+use gix::index::entry::{Flags, Mode, Stage, Stat};
 
 use crate::Repo;
 
@@ -14,31 +17,182 @@ impl Repo {
         self.git()?.arg("add").arg("--all").run()
     }
 
+    /// Stage every change like [`Repo::stage_all`], skipping paths that match one of
+    /// the gitignore-style `patterns`. Returns the paths that were left unstaged.
+    pub fn stage_all_except(&self, patterns: &[String]) -> Result<Vec<String>> {
+        let patterns = PathPatterns::parse(patterns)?;
+        let mut changed = self.untracked_files()?;
+        changed.extend(self.unstaged_files()?);
+        changed.sort();
+        changed.dedup();
+
+        let (excluded, included): (Vec<String>, Vec<String>) =
+            changed.into_iter().partition(|path| patterns.matches(path));
+        if !included.is_empty() {
+            let root = self.repo_root();
+            self.stage_paths(included.iter().map(|path| root.join(path)))?;
+        }
+        Ok(excluded)
+    }
+
+    /// Stage `paths` like `git add`, writing blobs and index entries directly.
+    ///
+    /// Deleted files are removed from the index. Directories are handed to `git add`,
+    /// which walks them while honoring `.gitignore`.
     pub fn stage_paths<I, P>(&self, paths: I) -> Result<()>
     where
         I: IntoIterator<Item = P>,
         P: AsRef<Path>,
     {
-        let mut command = self.git()?.arg("add");
-        let mut has_paths = false;
-
+        let mut files = Vec::new();
+        let mut dirs = Vec::new();
         for path in paths {
             match self.normalize_path(path.as_ref())? {
-                NormalizedPath::Root => {
-                    return self.stage_all();
+                NormalizedPath::Root => return self.stage_all(),
+                NormalizedPath::Path(rela) if self.repo_root().join(&rela).is_dir() => {
+                    dirs.push(rela)
                 }
-                NormalizedPath::Path(rela) => {
-                    command = command.arg(rela);
-                    has_paths = true;
+                NormalizedPath::Path(rela) => files.push(rela),
+            }
+        }
+
+        if !files.is_empty() {
+            let mut index = self.index_for_update()?;
+            let (mut pipeline, _) = self.repo.filter_pipeline(None)?;
+            let mut excludes = self.repo.excludes(&index, None, Default::default())?;
+            let file_mode = self
+                .repo
+                .config_snapshot()
+                .boolean("core.fileMode")
+                .unwrap_or(true);
+
+            for rela in &files {
+                let path = index_path(rela);
+                let absolute = self.repo_root().join(rela);
+                let metadata = match gix::index::fs::Metadata::from_path_no_follow(&absolute) {
+                    Ok(metadata) => metadata,
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                        let before = index.entries().len();
+                        remove_path(&mut index, path.as_ref());
+                        if index.entries().len() == before {
+                            bail!("Path '{}' did not match any files", rela.display());
+                        }
+                        continue;
+                    }
+                    Err(err) => return Err(err.into()),
+                };
+
+                let tracked = index.entry_by_path(path.as_ref());
+                if tracked.is_none() && excludes.at_path(rela, Some(Mode::FILE))?.is_excluded() {
+                    bail!("Path '{}' is ignored by .gitignore", rela.display());
                 }
+
+                let (mode, data) = if metadata.is_symlink() {
+                    let target = std::fs::read_link(&absolute)?;
+                    (Mode::SYMLINK, gix::path::into_bstr(target).into_owned())
+                } else {
+                    let mut data = Vec::new();
+                    pipeline
+                        .convert_to_git(std::fs::File::open(&absolute)?, rela, &index)?
+                        .read_to_end(&mut data)?;
+                    let mode = match tracked {
+                        Some(entry) if !file_mode => entry.mode,
+                        _ if file_mode && metadata.is_executable() => Mode::FILE_EXECUTABLE,
+                        _ => Mode::FILE,
+                    };
+                    (mode, data.into())
+                };
+
+                let id = self.repo.write_blob(&data)?.detach();
+                upsert_entry(
+                    &mut index,
+                    path.as_ref(),
+                    id,
+                    mode,
+                    Stat::from_fs(&metadata)?,
+                );
             }
+            write_index(&mut index)?;
         }
 
-        if !has_paths {
-            return Ok(());
+        if !dirs.is_empty() {
+            self.git()?.arg("add").arg("--").args(dirs).run()?;
         }
+        Ok(())
+    }
+
+    /// Reset `paths` in the index to HEAD without touching the working tree,
+    /// like `git restore --staged`. Directories are handed to `git reset`.
+    pub fn unstage_paths<I, P>(&self, paths: I) -> Result<()>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let head_tree = match self.repo.head()?.id() {
+            Some(id) => Some(self.repo.find_commit(id)?.tree()?),
+            None => None,
+        };
 
-        command.run()
+        let mut files = Vec::new();
+        let mut dirs = Vec::new();
+        for path in paths {
+            let rela = match self.normalize_path(path.as_ref())? {
+                NormalizedPath::Root => PathBuf::from("."),
+                NormalizedPath::Path(rela) => rela,
+            };
+            let head_entry = match &head_tree {
+                Some(tree) => tree.lookup_entry_by_path(&rela)?,
+                None => None,
+            };
+            let is_dir = self.repo_root().join(&rela).is_dir()
+                || head_entry
+                    .as_ref()
+                    .is_some_and(|entry| entry.mode().is_tree());
+            if is_dir {
+                dirs.push(rela);
+            } else {
+                files.push((
+                    rela,
+                    head_entry.map(|entry| (entry.object_id(), entry.mode())),
+                ));
+            }
+        }
+
+        if !files.is_empty() {
+            let mut index = self.index_for_update()?;
+            for (rela, head_entry) in &files {
+                let path = index_path(rela);
+                match head_entry {
+                    // A zeroed stat makes git re-hash the file on the next status.
+                    Some((id, mode)) => upsert_entry(
+                        &mut index,
+                        path.as_ref(),
+                        *id,
+                        (*mode).into(),
+                        Stat::default(),
+                    ),
+                    None => remove_path(&mut index, path.as_ref()),
+                }
+            }
+            write_index(&mut index)?;
+        }
+
+        if !dirs.is_empty() {
+            self.git()?.args(["reset", "-q", "--"]).args(dirs).run()?;
+        }
+        Ok(())
+    }
+
+    /// The index to modify, or an empty one when the repository has none yet.
+    fn index_for_update(&self) -> Result<gix::index::File> {
+        let path = self.repo.index_path();
+        if path.exists() {
+            return Ok(self.repo.open_index()?);
+        }
+        Ok(gix::index::File::from_state(
+            gix::index::State::new(self.repo.object_hash()),
+            path,
+        ))
     }
 
     fn normalize_path(&self, path: &Path) -> Result<NormalizedPath> {
@@ -67,3 +221,259 @@ impl Repo {
         Ok(NormalizedPath::Path(relative.to_path_buf()))
     }
 }
+
+/// `rela` as stored in the index: forward slashes on every platform.
+fn index_path(rela: &Path) -> BString {
+    gix::path::to_unix_separators_on_windows(gix::path::into_bstr(rela)).into_owned()
+}
+
+/// Replace all entries for `path` with a single unconflicted one.
+///
+/// Entries below `path` go too, since a file can't also be a directory.
+fn upsert_entry(
+    index: &mut gix::index::File,
+    path: &BStr,
+    id: gix::ObjectId,
+    mode: Mode,
+    stat: Stat,
+) {
+    let mut prefix = BString::from(path);
+    prefix.push(b'/');
+    index.remove_entries(|_, entry_path, entry| {
+        (entry_path == path && entry.stage() != Stage::Unconflicted)
+            || entry_path.starts_with(prefix.as_slice())
+    });
+
+    match index.entry_mut_by_path_and_stage(path, Stage::Unconflicted) {
+        Some(entry) => {
+            entry.id = id;
+            entry.mode = mode;
+            entry.stat = stat;
+        }
+        None => {
+            index.dangerously_push_entry(stat, id, Flags::empty(), mode, path);
+            index.sort_entries();
+        }
+    }
+}
+
+/// Drop every entry for `path`, in all stages.
+fn remove_path(index: &mut gix::index::File, path: &BStr) {
+    index.remove_entries(|_, entry_path, _| entry_path == path);
+}
+
+fn write_index(index: &mut gix::index::File) -> Result<()> {
+    // The cached trees no longer match the entries; git rebuilds them on the next commit.
+    index.remove_tree();
+    index
+        .write(gix::index::write::Options::default())
+        .context("Failed to write the index")
+}
+
+/// A list of gitignore-style patterns, where the last matching one decides.
+struct PathPatterns(Vec<gix::glob::Pattern>);
+
+impl PathPatterns {
+    fn parse(patterns: &[String]) -> Result<Self> {
+        patterns
+            .iter()
+            .map(|text| {
+                gix::glob::Pattern::from_bytes(text.as_bytes())
+                    .ok_or_else(|| anyhow!("invalid path pattern '{text}'"))
+            })
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+
+    /// Whether the repo-relative `path` is excluded, either itself or through one of its
+    /// parent directories, as with `target/` in a `.gitignore`.
+    fn matches(&self, path: &str) -> bool {
+        let mut excluded = false;
+        for pattern in &self.0 {
+            let hit = path
+                .match_indices('/')
+                .map(|(end, _)| (&path[..end], true))
+                .chain(std::iter::once((path, false)))
+                .any(|(candidate, is_dir)| {
+                    pattern.matches_repo_relative_path(
+                        candidate.into(),
+                        candidate.rfind('/').map(|slash| slash + 1),
+                        Some(is_dir),
+                        gix::glob::pattern::Case::Sensitive,
+                        gix::glob::wildmatch::Mode::NO_MATCH_SLASH_LITERAL,
+                    )
+                });
+            if hit {
+                excluded = !pattern.is_negative();
+            }
+        }
+        excluded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::testing::TestRepo;
+
+    /// Paths are resolved against the process's current directory, so pass them absolute.
+    fn paths(repo: &TestRepo, names: &[&str]) -> Vec<PathBuf> {
+        names.iter().map(|name| repo.path().join(name)).collect()
+    }
+
+    fn repo_with_files() -> TestRepo {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        repo.write("a.txt", "a\n").expect("write");
+        repo.write("b.txt", "b\n").expect("write");
+        repo.commit_all("add files").expect("commit");
+        repo
+    }
+
+    #[test]
+    fn stage_paths_stages_only_the_given_files() {
+        let repo = repo_with_files();
+        repo.write("a.txt", "a changed\n").expect("write");
+        std::fs::remove_file(repo.path().join("b.txt")).expect("delete");
+        repo.write("nested/c.txt", "c\n").expect("write");
+        repo.write("d.txt", "d\n").expect("write");
+        let script = repo.write("run.sh", "#!/bin/sh\n").expect("write");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))
+                .expect("chmod");
+        }
+
+        repo.stage_paths(paths(&repo, &["a.txt", "b.txt", "nested/c.txt", "run.sh"]))
+            .expect("stage");
+
+        assert_eq!(
+            repo.git_stdout(["diff", "--cached", "--name-status"])
+                .expect("run git"),
+            "M\ta.txt\nD\tb.txt\nA\tnested/c.txt\nA\trun.sh\n"
+        );
+        assert_eq!(
+            repo.git_stdout(["show", ":a.txt"]).expect("run git"),
+            "a changed\n"
+        );
+        #[cfg(unix)]
+        assert!(
+            repo.git_stdout(["ls-files", "-s", "run.sh"])
+                .expect("run git")
+                .starts_with("100755")
+        );
+        // Fresh stat info: nothing left to stage for these files.
+        assert_eq!(
+            repo.git_stdout(["diff", "--name-only"]).expect("run git"),
+            "",
+            "worktree should match the index"
+        );
+
+        repo.run_git(["commit", "-q", "-m", "partial"])
+            .expect("commit");
+        assert_eq!(
+            repo.git_stdout(["show", "--name-status", "--format=", "HEAD"])
+                .expect("run git"),
+            "M\ta.txt\nD\tb.txt\nA\tnested/c.txt\nA\trun.sh\n"
+        );
+        assert_eq!(
+            repo.git_stdout(["status", "--porcelain"]).expect("run git"),
+            "?? d.txt\n"
+        );
+    }
+
+    #[test]
+    fn stage_paths_rejects_ignored_and_unknown_paths() {
+        let repo = repo_with_files();
+        repo.write(".gitignore", "*.log\n").expect("write");
+        repo.write("debug.log", "noise\n").expect("write");
+
+        assert!(repo.stage_paths(paths(&repo, &["debug.log"])).is_err());
+        assert!(repo.stage_paths(paths(&repo, &["missing.txt"])).is_err());
+        assert_eq!(
+            repo.git_stdout(["diff", "--cached", "--name-only"])
+                .expect("run git"),
+            ""
+        );
+    }
+
+    #[test]
+    fn stage_paths_hands_directories_to_git() {
+        let repo = repo_with_files();
+        repo.write("dir/one.txt", "1\n").expect("write");
+        repo.write("dir/two.log", "2\n").expect("write");
+        repo.write(".gitignore", "*.log\n").expect("write");
+
+        repo.stage_paths(paths(&repo, &["dir"])).expect("stage dir");
+        assert_eq!(
+            repo.git_stdout(["diff", "--cached", "--name-only"])
+                .expect("run git"),
+            "dir/one.txt\n"
+        );
+    }
+
+    #[test]
+    fn unstage_paths_restores_head_entries() {
+        let repo = repo_with_files();
+        repo.write("a.txt", "a changed\n").expect("write");
+        std::fs::remove_file(repo.path().join("b.txt")).expect("delete");
+        repo.write("c.txt", "c\n").expect("write");
+        repo.stage_paths(paths(&repo, &["a.txt", "b.txt", "c.txt"]))
+            .expect("stage");
+
+        repo.unstage_paths(paths(&repo, &["a.txt", "b.txt", "c.txt"]))
+            .expect("unstage");
+
+        assert_eq!(
+            repo.git_stdout(["diff", "--cached", "--name-only"])
+                .expect("run git"),
+            ""
+        );
+        assert_eq!(
+            repo.git_stdout(["status", "--porcelain"]).expect("run git"),
+            " M a.txt\n D b.txt\n?? c.txt\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(repo.path().join("a.txt")).expect("read"),
+            "a changed\n"
+        );
+    }
+
+    #[test]
+    fn path_patterns_follow_gitignore_rules() {
+        let patterns = super::PathPatterns::parse(&[
+            "*.log".to_string(),
+            "!keep.log".to_string(),
+            "target/".to_string(),
+            "/docs/*.md".to_string(),
+        ])
+        .unwrap();
+
+        assert!(patterns.matches("debug.log"));
+        assert!(patterns.matches("nested/trace.log"));
+        assert!(!patterns.matches("keep.log"));
+        assert!(patterns.matches("target/debug/sg"));
+        assert!(patterns.matches("crates/x/target/out"));
+        assert!(!patterns.matches("target"));
+        assert!(patterns.matches("docs/README.md"));
+        assert!(!patterns.matches("src/docs/README.md"));
+        assert!(!patterns.matches("src/main.rs"));
+    }
+
+    #[test]
+    fn stage_all_except_leaves_excluded_paths_unstaged() {
+        let repo = TestRepo::builder().with_initial_commit().build().unwrap();
+        repo.write("src/lib.rs", "pub fn f() {}\n").unwrap();
+        repo.write("debug.log", "noise\n").unwrap();
+
+        let excluded = repo.stage_all_except(&["*.log".to_string()]).unwrap();
+
+        assert_eq!(excluded, vec!["debug.log"]);
+        assert_eq!(repo.staged_changes().unwrap(), vec!["src/lib.rs"]);
+        assert_eq!(repo.untracked_files().unwrap(), vec!["debug.log"]);
+    }
+}
@@ -0,0 +1,179 @@
+use std::fmt;
+
+use anyhow::{Context, Result, bail};
+
+use super::Repo;
+
+const STASH_REF: &str = "refs/stash";
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StashOptions {
+    /// Leave staged changes in the index and working tree after stashing.
+    pub keep_index: bool,
+    /// Stash untracked files too, removing them from the working tree.
+    pub include_untracked: bool,
+}
+
+/// The commit a stash entry was recorded as. Unlike `stash@{0}`, it keeps naming the
+/// same entry after other stashes are pushed or dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StashId(pub String);
+
+impl fmt::Display for StashId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Repo {
+    /// Stash local changes like `git stash push`, returning the new entry.
+    ///
+    /// Fails when there is nothing to stash rather than returning an older entry.
+    pub fn stash_push(&self, message: Option<&str>, options: StashOptions) -> Result<StashId> {
+        let before = self.stash_tip()?;
+
+        let mut command = self.git()?.args(["stash", "push", "--quiet"]);
+        if options.keep_index {
+            command = command.arg("--keep-index");
+        }
+        if options.include_untracked {
+            command = command.arg("--include-untracked");
+        }
+        if let Some(message) = message {
+            command = command.args(["--message", message]);
+        }
+        command.run()?;
+
+        match self.stash_tip()? {
+            Some(id) if Some(&id) != before.as_ref() => Ok(StashId(id)),
+            _ => bail!("No local changes to stash"),
+        }
+    }
+
+    /// Apply and drop the entry `id`, wherever it now sits in the stash list.
+    ///
+    /// If it doesn't apply cleanly git keeps the entry, so nothing is lost; the error
+    /// says so and the conflicts are left in the working tree.
+    pub fn stash_pop(&self, id: &StashId) -> Result<()> {
+        let output = self
+            .git()?
+            .args(["stash", "list", "--format=%H"])
+            .run_with_output()?;
+        let entries = String::from_utf8_lossy(&output.stdout);
+        let Some(index) = entries.lines().position(|entry| entry == id.0) else {
+            bail!("Stash {id} no longer exists");
+        };
+
+        self.git()?
+            .args(["stash", "pop", "--quiet", &format!("stash@{{{index}}}")])
+            .run()
+            .with_context(|| format!("Stash {id} did not apply cleanly and was kept"))
+    }
+
+    fn stash_tip(&self) -> Result<Option<String>> {
+        Ok(self
+            .repo
+            .try_find_reference(STASH_REF)?
+            .and_then(|mut reference| reference.peel_to_id_in_place().ok())
+            .map(|id| id.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestRepo;
+
+    #[test]
+    fn stash_push_captures_untracked_files() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        repo.write("tracked.txt", "one\n").expect("write");
+        repo.commit_all("add tracked").expect("commit");
+
+        repo.write("tracked.txt", "two\n").expect("write");
+        repo.write("scratch.txt", "notes\n").expect("write");
+
+        let id = repo
+            .stash_push(
+                Some("before sync"),
+                StashOptions {
+                    include_untracked: true,
+                    ..Default::default()
+                },
+            )
+            .expect("stash");
+
+        assert_eq!(
+            repo.git_stdout(["rev-parse", "stash@{0}"])
+                .expect("run git")
+                .trim(),
+            id.0
+        );
+        assert!(
+            repo.git_stdout(["stash", "list"])
+                .expect("run git")
+                .contains("before sync")
+        );
+        // Untracked files live in the stash commit's third parent.
+        let untracked = repo
+            .git_stdout(["ls-tree", "--name-only", &format!("{id}^3")])
+            .expect("run git");
+        assert_eq!(untracked.trim(), "scratch.txt");
+
+        assert!(!repo.path().join("scratch.txt").exists());
+        assert!(repo.unstaged_files().expect("status").is_empty());
+
+        let error = repo
+            .stash_push(None, StashOptions::default())
+            .expect_err("nothing left to stash");
+        assert!(error.to_string().contains("No local changes"));
+    }
+
+    #[test]
+    fn stash_pop_leaves_other_entries_alone() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        repo.write("a.txt", "a\n").expect("write");
+        repo.write("b.txt", "b\n").expect("write");
+        repo.commit_all("add files").expect("commit");
+
+        repo.write("a.txt", "user change\n").expect("write");
+        repo.run_git(["stash", "push", "--quiet", "-m", "user stash"])
+            .expect("user stash");
+
+        repo.write("b.txt", "ours\n").expect("write");
+        let ours = repo
+            .stash_push(Some("autostash"), StashOptions::default())
+            .expect("stash");
+
+        // Someone else stashes on top, so ours is no longer stash@{0}.
+        repo.write("a.txt", "later change\n").expect("write");
+        repo.run_git(["stash", "push", "--quiet", "-m", "later stash"])
+            .expect("later stash");
+
+        repo.stash_pop(&ours).expect("pop");
+
+        let remaining = repo
+            .git_stdout(["stash", "list", "--format=%s"])
+            .expect("run git");
+        assert!(remaining.contains("user stash"));
+        assert!(remaining.contains("later stash"));
+        assert!(!remaining.contains("autostash"));
+        assert_eq!(
+            std::fs::read_to_string(repo.path().join("b.txt")).expect("read"),
+            "ours\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(repo.path().join("a.txt")).expect("read"),
+            "a\n"
+        );
+
+        let error = repo.stash_pop(&ours).expect_err("already popped");
+        assert!(error.to_string().contains("no longer exists"));
+    }
+}
@@ -1,6 +1,8 @@
 use std::convert::TryFrom;
+use std::path::PathBuf;
 
 use anyhow::{Context, Result};
+use gix::bstr::{BStr, ByteSlice};
 use gix::status::Item as StatusItem;
 use gix::{ObjectId, diff::index::Change as IndexChange, progress::Discard, status as gix_status};
 
@@ -9,12 +11,44 @@ use gix_status::plumbing::index_as_worktree_with_renames::Summary;
 use super::Repo;
 
 impl Repo {
+    /// Any staged, unstaged or untracked (non-ignored) change.
     pub fn is_dirty(&self) -> Result<bool> {
-        Ok(self.repo.is_dirty()?)
+        Ok(!self.is_clean()?)
     }
 
+    /// Stops at the first change found instead of collecting the full status.
     pub fn is_clean(&self) -> Result<bool> {
-        Ok(!self.is_dirty()?)
+        let mut iter = self
+            .repo
+            .status(Discard)?
+            .untracked_files(gix_status::UntrackedFiles::Files)
+            .into_iter(Vec::new())?;
+
+        match iter.next() {
+            Some(item) => {
+                item?;
+                Ok(false)
+            }
+            None => Ok(true),
+        }
+    }
+
+    /// Paths with unresolved merge conflicts, i.e. index entries at stage 1, 2 or 3.
+    pub fn conflicted_files(&self) -> Result<Vec<PathBuf>> {
+        let index = self.repo.open_index()?;
+        let mut files: Vec<PathBuf> = index
+            .entries()
+            .iter()
+            .filter(|entry| entry.stage_raw() != 0)
+            .map(|entry| PathBuf::from(entry.path(&index).to_str_lossy().into_owned()))
+            .collect();
+
+        files.dedup();
+        Ok(files)
+    }
+
+    pub fn has_conflicts(&self) -> Result<bool> {
+        Ok(!self.conflicted_files()?.is_empty())
     }
 
     pub fn unstaged_files(&self) -> Result<Vec<String>> {
@@ -107,6 +141,79 @@ impl Repo {
         Ok((ahead, behind))
     }
 
+    /// Commits `branch` is ahead/behind its upstream, or `None` when it has no upstream.
+    pub fn upstream_ahead_behind(&self, branch: &str) -> Result<Option<(usize, usize)>> {
+        let Some(upstream) = self.upstream_ref(branch)? else {
+            return Ok(None);
+        };
+
+        self.ahead_behind(&self.as_ref(branch), &upstream).map(Some)
+    }
+
+    /// Commits only reachable from `from` (ahead) and only reachable from `to` (behind).
+    ///
+    /// Both sides can be any revision, e.g. a branch and its stack parent.
+    pub fn ahead_behind(&self, from: &str, to: &str) -> Result<(usize, usize)> {
+        let from_id = self.resolve_commit(from)?;
+        let to_id = self.resolve_commit(to)?;
+
+        let ahead = self.unique_commit_count(from_id, to_id)?;
+        let behind = self.unique_commit_count(to_id, from_id)?;
+        Ok((ahead, behind))
+    }
+
+    fn upstream_ref(&self, branch: &str) -> Result<Option<String>> {
+        let branch = self.remove_ref(branch);
+        let config = self.repo.config_snapshot();
+        let subsection: Option<&BStr> = Some(branch.as_bytes().as_bstr());
+
+        let configured = config
+            .string_by("branch", subsection, "remote")
+            .zip(config.string_by("branch", subsection, "merge"))
+            .map(|(remote, merge)| {
+                let merge = merge.to_string();
+                let merge = merge.strip_prefix("refs/heads/").unwrap_or(&merge);
+                format!("refs/remotes/{remote}/{merge}")
+            });
+
+        let candidate = match configured {
+            Some(candidate) => candidate,
+            None => match self.remote_name()? {
+                Some(remote) => format!("refs/remotes/{remote}/{branch}"),
+                None => return Ok(None),
+            },
+        };
+
+        Ok(self
+            .repo
+            .try_find_reference(candidate.as_str())?
+            .map(|_| candidate))
+    }
+
+    /// Returns `true` when `ancestor` is reachable from `descendant` (or they are the same commit).
+    pub fn is_ancestor(&self, ancestor: &str, descendant: &str) -> Result<bool> {
+        let ancestor_id = self.resolve_commit(ancestor)?;
+        let descendant_id = self.resolve_commit(descendant)?;
+
+        if ancestor_id == descendant_id {
+            return Ok(true);
+        }
+
+        match self.repo.merge_base(ancestor_id, descendant_id) {
+            Ok(base) => Ok(base.detach() == ancestor_id),
+            Err(gix::repository::merge_base::Error::NotFound { .. }) => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub(crate) fn resolve_commit(&self, rev: &str) -> Result<ObjectId> {
+        let id = self
+            .repo
+            .rev_parse_single(rev)
+            .with_context(|| format!("Failed to resolve '{rev}'"))?;
+        Ok(id.object()?.peel_to_commit()?.id)
+    }
+
     fn unique_commit_count(&self, start: ObjectId, hide: ObjectId) -> Result<usize> {
         let walk = self.repo.rev_walk([start]).with_hidden([hide]).all()?;
 
@@ -142,3 +249,100 @@ fn collect_staged_change(output: &mut Vec<String>, change: IndexChange) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::testing::TestRepo;
+
+    #[test]
+    fn ahead_behind_counts_divergent_branches() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        repo.run_git(["checkout", "-b", "feature"])
+            .expect("create feature");
+        for n in 0..3 {
+            repo.write(format!("feature-{n}.txt"), "feature\n")
+                .expect("write");
+            repo.commit_all(&format!("feature {n}")).expect("commit");
+        }
+        repo.run_git(["checkout", "main"]).expect("checkout main");
+        repo.write("main.txt", "main\n").expect("write");
+        repo.commit_all("main moves on").expect("commit");
+
+        assert_eq!(repo.ahead_behind("feature", "main").expect("count"), (3, 1));
+        assert_eq!(repo.ahead_behind("main", "feature").expect("count"), (1, 3));
+        assert_eq!(repo.ahead_behind("main", "main").expect("count"), (0, 0));
+        assert!(repo.ahead_behind("feature", "missing").is_err());
+    }
+
+    #[test]
+    fn is_ancestor_follows_history() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        repo.run_git(["checkout", "-b", "feature"])
+            .expect("create feature");
+        repo.write("feature.txt", "feature\n").expect("write file");
+        repo.commit_all("feature work").expect("commit feature");
+
+        assert!(
+            repo.is_ancestor("main", "feature")
+                .expect("main in feature")
+        );
+        assert!(
+            !repo
+                .is_ancestor("feature", "main")
+                .expect("feature not in main")
+        );
+        assert!(repo.is_ancestor("main", "main").expect("self ancestor"));
+    }
+
+    #[test]
+    fn conflicted_files_lists_unmerged_paths() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        repo.write("shared.txt", "base\n").expect("write");
+        repo.commit_all("add shared").expect("commit");
+
+        repo.run_git(["checkout", "-b", "feature"])
+            .expect("create feature");
+        repo.write("shared.txt", "feature\n").expect("write");
+        repo.commit_all("feature edit").expect("commit");
+        repo.run_git(["checkout", "main"]).expect("checkout main");
+        repo.write("shared.txt", "main\n").expect("write");
+        repo.commit_all("main edit").expect("commit");
+
+        assert!(!repo.has_conflicts().expect("no conflicts yet"));
+        assert!(repo.run_git(["merge", "feature"]).is_err());
+
+        assert_eq!(
+            repo.conflicted_files().expect("conflicts"),
+            vec![PathBuf::from("shared.txt")]
+        );
+        assert!(repo.has_conflicts().expect("has conflicts"));
+    }
+
+    #[test]
+    fn is_clean_detects_first_change() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        assert!(repo.is_clean().expect("fresh commit"));
+
+        repo.write(".gitignore", "*.log\n").expect("write ignore");
+        repo.commit_all("ignore logs").expect("commit ignore");
+        repo.write("debug.log", "noise\n").expect("write ignored");
+        assert!(repo.is_clean().expect("ignored file"));
+
+        repo.write("new.txt", "new\n").expect("write untracked");
+        assert!(repo.is_dirty().expect("untracked file"));
+    }
+}
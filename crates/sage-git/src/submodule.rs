@@ -0,0 +1,110 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use gix::bstr::ByteSlice;
+
+use super::Repo;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmoduleInfo {
+    /// Location relative to the repository root.
+    pub path: PathBuf,
+    pub url: String,
+    /// Commit checked out in the submodule; `None` until it's initialized.
+    pub head: Option<String>,
+    /// Cloned and checked out, i.e. `git submodule update --init` has run.
+    pub is_initialized: bool,
+    /// Has local changes or a checkout that differs from the recorded commit.
+    pub is_dirty: bool,
+}
+
+impl Repo {
+    /// Every submodule listed in `.gitmodules`, honoring each one's `ignore` setting.
+    pub fn submodule_status(&self) -> Result<Vec<SubmoduleInfo>> {
+        let Some(submodules) = self.repo.submodules()? else {
+            return Ok(Vec::new());
+        };
+
+        let mut infos = Vec::new();
+        for submodule in submodules {
+            let ignore = submodule.ignore()?.unwrap_or_default();
+            let status = submodule.status(ignore, true)?;
+
+            infos.push(SubmoduleInfo {
+                path: PathBuf::from(submodule.path()?.to_str_lossy().into_owned()),
+                url: submodule.url()?.to_bstring().to_str_lossy().into_owned(),
+                head: status.checked_out_head_id.map(|id| id.to_string()),
+                is_initialized: status.state.repository_exists && status.state.worktree_checkout,
+                is_dirty: status.is_dirty().unwrap_or(false),
+            });
+        }
+        Ok(infos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestRepo;
+
+    #[test]
+    fn lists_submodule_path_and_state() {
+        let library = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("library repo");
+        let library_head = library.last_commit("HEAD").expect("library head").id;
+
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        assert!(repo.submodule_status().expect("status").is_empty());
+
+        repo.run_git([
+            "-c",
+            "protocol.file.allow=always",
+            "submodule",
+            "add",
+            "-q",
+            library.path().to_str().unwrap(),
+            "libs/library",
+        ])
+        .expect("add submodule");
+        repo.run_git(["commit", "-q", "-m", "add library"])
+            .expect("commit");
+
+        let submodules = repo.submodule_status().expect("status");
+        assert_eq!(submodules.len(), 1);
+        let library_info = &submodules[0];
+        assert_eq!(library_info.path, PathBuf::from("libs/library"));
+        assert!(
+            library_info
+                .url
+                .ends_with(library.path().file_name().unwrap().to_str().unwrap())
+        );
+        assert!(library_info.is_initialized);
+        assert_eq!(library_info.head.as_deref(), Some(library_head.as_str()));
+        assert!(!library_info.is_dirty);
+
+        std::fs::write(repo.path().join("libs/library/README.md"), "changed\n")
+            .expect("edit submodule");
+        assert!(repo.submodule_status().expect("status")[0].is_dirty);
+
+        let clone_parent = tempfile::tempdir().expect("clone dir");
+        let clone = clone_parent.path().join("clone");
+        repo.run_git([
+            "clone",
+            "-q",
+            repo.path().to_str().unwrap(),
+            clone.to_str().unwrap(),
+        ])
+        .expect("clone");
+        let cloned = Repo::discover(&clone).expect("open clone");
+        let submodules = cloned.submodule_status().expect("status");
+        assert_eq!(submodules[0].path, PathBuf::from("libs/library"));
+        assert!(!submodules[0].is_initialized);
+        assert_eq!(submodules[0].head, None);
+        assert!(!submodules[0].is_dirty);
+    }
+}
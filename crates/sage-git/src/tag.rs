@@ -0,0 +1,98 @@
+use anyhow::{Result, anyhow};
+use gix::bstr::ByteSlice;
+
+use super::Repo;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagInfo {
+    pub name: String,
+    /// Object the tag ultimately points at (the commit for annotated tags).
+    pub target_id: String,
+    pub is_annotated: bool,
+    /// Only present for annotated tags.
+    pub message: Option<String>,
+    /// Only present for annotated tags.
+    pub tagger: Option<String>,
+}
+
+impl Repo {
+    pub fn tags(&self) -> Result<Vec<TagInfo>> {
+        let mut tags = Vec::new();
+
+        for reference in self.repo.references()?.tags()? {
+            let reference = reference.map_err(|err| anyhow!(err))?;
+            let name = reference.name().shorten().to_string();
+            let Some(id) = reference.try_id() else {
+                continue;
+            };
+
+            let object = id.object()?;
+            if object.kind != gix::object::Kind::Tag {
+                tags.push(TagInfo {
+                    name,
+                    target_id: id.to_hex().to_string(),
+                    is_annotated: false,
+                    message: None,
+                    tagger: None,
+                });
+                continue;
+            }
+
+            let tag = object.into_tag();
+            let decoded = tag.decode()?;
+            let message = decoded.message.to_str_lossy().trim_end().to_string();
+            let tagger = decoded
+                .tagger
+                .map(|signature| signature.name.to_str_lossy().into_owned());
+
+            tags.push(TagInfo {
+                name,
+                target_id: tag.target_id()?.to_hex().to_string(),
+                is_annotated: true,
+                message: Some(message),
+                tagger,
+            });
+        }
+
+        tags.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(tags)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::TestRepo;
+
+    #[test]
+    fn tags_distinguish_annotated_and_lightweight() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        repo.run_git(["tag", "-a", "v1.0.0", "-m", "Release 1.0.0"])
+            .expect("annotated tag");
+        repo.run_git(["tag", "nightly"]).expect("lightweight tag");
+
+        let head = repo
+            .get_current_commit()
+            .expect("head")
+            .to_hex()
+            .to_string();
+        let tags = repo.tags().expect("list tags");
+        assert_eq!(tags.len(), 2, "unexpected tags: {tags:?}");
+
+        let nightly = &tags[0];
+        assert_eq!(nightly.name, "nightly");
+        assert!(!nightly.is_annotated);
+        assert_eq!(nightly.target_id, head);
+        assert_eq!(nightly.message, None);
+        assert_eq!(nightly.tagger, None);
+
+        let release = &tags[1];
+        assert_eq!(release.name, "v1.0.0");
+        assert!(release.is_annotated);
+        assert_eq!(release.target_id, head);
+        assert_eq!(release.message.as_deref(), Some("Release 1.0.0"));
+        assert_eq!(release.tagger.as_deref(), Some("Test User"));
+    }
+}
@@ -117,6 +117,17 @@ impl TestRepo {
         TestRepoBuilder::default()
     }
 
+    /// A repository on `main` with a `feature` branch one commit ahead that adds
+    /// `feature.txt`. HEAD is left on `main`.
+    pub fn with_feature_branch() -> Result<Self> {
+        let repo = Self::builder().with_initial_commit().build()?;
+        repo.run_git(["checkout", "-q", "-b", "feature"])?;
+        repo.write("feature.txt", "feature\n")?;
+        repo.commit_all("feature work")?;
+        repo.run_git(["checkout", "-q", "main"])?;
+        Ok(repo)
+    }
+
     pub fn repo(&self) -> &Repo {
         &self.repo
     }
@@ -137,6 +148,15 @@ impl TestRepo {
         self.git().args(args).run()
     }
 
+    /// Runs `git` like [`TestRepo::run_git`] and returns what it printed to stdout.
+    pub fn git_stdout<I, S>(&self, args: I) -> Result<String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.git().args(args).stdout()
+    }
+
     pub fn write(&self, path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> Result<PathBuf> {
         let path = self.path().join(path);
         if let Some(parent) = path.parent() {
@@ -221,9 +241,14 @@ impl GitCommand {
     }
 
     pub fn run(self) -> Result<()> {
+        self.stdout().map(drop)
+    }
+
+    /// Like [`GitCommand::run`], returning the command's stdout.
+    pub fn stdout(self) -> Result<String> {
         let output = self.output()?;
         if output.status.success() {
-            return Ok(());
+            return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
         }
 
         let stdout = String::from_utf8_lossy(&output.stdout);
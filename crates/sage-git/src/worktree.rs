@@ -0,0 +1,132 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use super::Repo;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreeInfo {
+    pub path: PathBuf,
+    /// Checked out commit; `None` for bare entries.
+    pub head: Option<String>,
+    /// Short branch name; `None` when detached or bare.
+    pub branch: Option<String>,
+    pub is_locked: bool,
+}
+
+impl Repo {
+    /// Check out an existing `branch` into a new linked worktree at `path`.
+    pub fn worktree_add(&self, path: impl AsRef<Path>, branch: &str) -> Result<()> {
+        self.git()?
+            .arg("worktree")
+            .arg("add")
+            .arg(path.as_ref())
+            .arg(self.remove_ref(branch))
+            .run()
+    }
+
+    pub fn worktree_list(&self) -> Result<Vec<WorktreeInfo>> {
+        let output = self
+            .git()?
+            .args(["worktree", "list", "--porcelain"])
+            .run_with_output()?;
+        Ok(parse_worktree_list(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+
+    /// The worktree that currently has `branch` checked out, if any.
+    pub fn worktree_for_branch(&self, branch: &str) -> Result<Option<WorktreeInfo>> {
+        let branch = self.remove_ref(branch);
+        Ok(self
+            .worktree_list()?
+            .into_iter()
+            .find(|worktree| worktree.branch.as_deref() == Some(branch.as_str())))
+    }
+}
+
+fn parse_worktree_list(output: &str) -> Vec<WorktreeInfo> {
+    let mut worktrees = Vec::new();
+    let mut current: Option<WorktreeInfo> = None;
+
+    for line in output.lines() {
+        if let Some(path) = line.strip_prefix("worktree ") {
+            worktrees.extend(current.take());
+            current = Some(WorktreeInfo {
+                path: PathBuf::from(path),
+                head: None,
+                branch: None,
+                is_locked: false,
+            });
+            continue;
+        }
+
+        let Some(worktree) = current.as_mut() else {
+            continue;
+        };
+
+        if let Some(head) = line.strip_prefix("HEAD ") {
+            worktree.head = Some(head.to_string());
+        } else if let Some(branch) = line.strip_prefix("branch ") {
+            let branch = branch.strip_prefix("refs/heads/").unwrap_or(branch);
+            worktree.branch = Some(branch.to_string());
+        } else if line == "locked" || line.starts_with("locked ") {
+            worktree.is_locked = true;
+        }
+    }
+
+    worktrees.extend(current);
+    worktrees
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestRepo;
+
+    #[test]
+    fn worktree_add_lists_new_worktree() {
+        let repo = TestRepo::builder()
+            .with_initial_commit()
+            .build()
+            .expect("temp repo");
+        repo.create_branch("feature").expect("create branch");
+
+        let parent = tempfile::tempdir().expect("worktree parent");
+        let path = parent.path().join("feature");
+        repo.worktree_add(&path, "feature").expect("add worktree");
+
+        let worktrees = repo.worktree_list().expect("list worktrees");
+        assert_eq!(worktrees.len(), 2, "unexpected worktrees: {worktrees:?}");
+
+        let expected = path.canonicalize().expect("canonical path");
+        let linked = worktrees
+            .iter()
+            .find(|worktree| worktree.path.canonicalize().ok() == Some(expected.clone()))
+            .expect("new worktree listed");
+        assert_eq!(linked.branch.as_deref(), Some("feature"));
+        assert!(linked.head.is_some());
+        assert!(!linked.is_locked);
+
+        let found = repo
+            .worktree_for_branch("feature")
+            .expect("lookup")
+            .expect("feature worktree");
+        assert_eq!(found.path, linked.path);
+    }
+
+    #[test]
+    fn parses_detached_and_locked_entries() {
+        let output = "worktree /repo\nHEAD 1111\nbranch refs/heads/main\n\n\
+                      worktree /repo-wt\nHEAD 2222\ndetached\nlocked moving disks\n";
+        let worktrees = parse_worktree_list(output);
+
+        assert_eq!(worktrees.len(), 2);
+        assert_eq!(worktrees[0].branch.as_deref(), Some("main"));
+        assert!(!worktrees[0].is_locked);
+        assert_eq!(worktrees[1].path, PathBuf::from("/repo-wt"));
+        assert_eq!(worktrees[1].head.as_deref(), Some("2222"));
+        assert_eq!(worktrees[1].branch, None);
+        assert!(worktrees[1].is_locked);
+    }
+}
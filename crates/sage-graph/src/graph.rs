@@ -7,6 +7,27 @@ use serde::{Deserialize, Serialize};
 use crate::{BranchInfo, Stack};
 use sage_git::Repo;
 
+/// A mismatch between a tracked parent relationship and the actual git history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Discrepancy {
+    /// The tracked branch no longer exists locally.
+    MissingBranch { branch: String },
+    /// The recorded parent branch no longer exists locally.
+    MissingParent { branch: String, parent: String },
+    /// The recorded parent has moved and is no longer an ancestor, so the branch needs a restack.
+    ParentNotAncestor { branch: String, parent: String },
+}
+
+impl Discrepancy {
+    pub fn branch(&self) -> &str {
+        match self {
+            Self::MissingBranch { branch }
+            | Self::MissingParent { branch, .. }
+            | Self::ParentNotAncestor { branch, .. } => branch,
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct SageGraph {
     stacks: HashMap<String, Stack>,
@@ -37,6 +58,23 @@ impl SageGraph {
         Ok(graph)
     }
 
+    /// Like [`SageGraph::load`], but an unparseable graph file is moved to
+    /// `sage_graph.json.bak` and replaced with an empty graph.
+    ///
+    /// Returns the backup path alongside the graph when that happened.
+    pub fn load_or_recover(repo: &Repo) -> Result<(Self, Option<PathBuf>)> {
+        let path = Self::storage_path(repo);
+        let backup = match fs::read_to_string(&path) {
+            Ok(content) if serde_json::from_str::<SageGraph>(&content).is_err() => {
+                let backup = path.with_extension("json.bak");
+                fs::rename(&path, &backup).context("backing up corrupt graph file")?;
+                Some(backup)
+            }
+            _ => None,
+        };
+        Ok((Self::load(repo)?, backup))
+    }
+
     pub fn save(&mut self, repo: &Repo) -> Result<()> {
         self.capture_repo_environment(repo);
         let path = Self::storage_path(repo);
@@ -198,6 +236,30 @@ impl SageGraph {
         Ok(())
     }
 
+    /// Stop tracking `branch`, whether loose or in a stack. Returns `false` if it wasn't tracked.
+    ///
+    /// Fails if other tracked branches still build on it.
+    pub fn untrack_branch(&mut self, branch: &str) -> Result<bool> {
+        if self.is_loose(branch) {
+            self.remove_loose_branch(branch)?;
+            return Ok(true);
+        }
+
+        let Some(stack_name) = self.branch_to_stack.get(branch).cloned() else {
+            return Ok(false);
+        };
+        let stack = self
+            .stacks
+            .get_mut(&stack_name)
+            .ok_or_else(|| anyhow::anyhow!("stack \"{stack_name}\" not found"))?;
+        stack.remove_branch(branch)?;
+        if stack.branches.is_empty() {
+            self.stacks.remove(&stack_name);
+        }
+        self.branch_to_stack.remove(branch);
+        Ok(true)
+    }
+
     pub fn is_loose(&self, branch: &str) -> bool {
         self.loose_branches.contains_key(branch)
     }
@@ -212,6 +274,25 @@ impl SageGraph {
             .or_else(|| self.loose_branches.get(branch))
     }
 
+    /// Record the pull request opened for `branch`. Returns `false` if the branch isn't tracked.
+    pub fn set_pr_number(&mut self, branch: &str, pr_number: Option<u64>) -> bool {
+        let info = match self.branch_to_stack.get(branch) {
+            Some(stack) => self
+                .stacks
+                .get_mut(stack)
+                .and_then(|stack| stack.branches.get_mut(branch)),
+            None => self.loose_branches.get_mut(branch),
+        };
+
+        match info {
+            Some(info) => {
+                info.pr_number = pr_number;
+                true
+            }
+            None => false,
+        }
+    }
+
     fn get_depth(&self, branch: &str) -> Option<usize> {
         self.get_info(branch).map(|info| info.depth)
     }
@@ -245,6 +326,50 @@ impl SageGraph {
         Ok(())
     }
 
+    /// Checks every tracked parent relationship against the repository history.
+    pub fn validate_against_repo(&self, repo: &Repo) -> Result<Vec<Discrepancy>> {
+        let mut discrepancies = Vec::new();
+
+        let tracked = self
+            .stacks
+            .values()
+            .flat_map(|stack| stack.branches.values())
+            .chain(self.loose_branches.values());
+
+        for info in tracked {
+            if info.parent == info.name {
+                continue;
+            }
+
+            if !repo.has_branch(info.name.clone())? {
+                discrepancies.push(Discrepancy::MissingBranch {
+                    branch: info.name.clone(),
+                });
+                continue;
+            }
+
+            if !repo.has_branch(info.parent.clone())? {
+                discrepancies.push(Discrepancy::MissingParent {
+                    branch: info.name.clone(),
+                    parent: info.parent.clone(),
+                });
+                continue;
+            }
+
+            let parent_ref = repo.as_ref(&info.parent);
+            let branch_ref = repo.as_ref(&info.name);
+            if !repo.is_ancestor(&parent_ref, &branch_ref)? {
+                discrepancies.push(Discrepancy::ParentNotAncestor {
+                    branch: info.name.clone(),
+                    parent: info.parent.clone(),
+                });
+            }
+        }
+
+        discrepancies.sort_by(|a, b| a.branch().cmp(b.branch()));
+        Ok(discrepancies)
+    }
+
     pub fn repo_root(&self) -> Option<&PathBuf> {
         self.repo_root.as_ref()
     }
@@ -337,6 +462,27 @@ mod tests {
         assert_eq!(all.len(), 3);
     }
 
+    #[test]
+    fn corrupt_graph_file_is_backed_up_and_reset() {
+        let repo = test_repo();
+        let mut graph = graph_with_main(&repo);
+        graph.save(&repo).unwrap();
+
+        let path = repo.git_dir().join("sage_graph.json");
+        fs::write(&path, "{ not json").unwrap();
+        assert!(SageGraph::load(&repo).is_err());
+
+        let (graph, backup) = SageGraph::load_or_recover(&repo).unwrap();
+        let backup = backup.expect("backup path");
+        assert_eq!(backup, repo.git_dir().join("sage_graph.json.bak"));
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "{ not json");
+        assert!(!path.exists());
+        assert!(graph.get_info("main").is_some());
+
+        let (_, backup) = SageGraph::load_or_recover(&repo).unwrap();
+        assert_eq!(backup, None);
+    }
+
     #[test]
     fn repo_context_captured() {
         let repo = test_repo();
@@ -427,6 +573,70 @@ mod tests {
         assert!(graph.is_loose("main"));
     }
 
+    #[test]
+    fn untrack_branch_removes_stack_leaves() {
+        let repo = test_repo();
+        let mut graph = graph_with_main(&repo);
+        graph
+            .create_stack(
+                &repo,
+                "feat".to_owned(),
+                "base".to_owned(),
+                "main".to_owned(),
+            )
+            .unwrap();
+        graph
+            .add_to_stack(&repo, "feat", "base", "child".to_owned())
+            .unwrap();
+
+        assert!(graph.untrack_branch("base").is_err());
+        assert!(graph.untrack_branch("child").unwrap());
+        assert!(!graph.is_tracked("child"));
+        assert_eq!(
+            graph.stack_for_branch("base").unwrap().children("base"),
+            &[] as &[String]
+        );
+
+        assert!(graph.untrack_branch("base").unwrap());
+        assert!(graph.stack_for_branch("base").is_none());
+        assert!(!graph.untrack_branch("unknown").unwrap());
+    }
+
+    #[test]
+    fn validate_reports_diverged_and_missing_branches() {
+        let repo = test_repo();
+        repo.run_git(["checkout", "-b", "feature"]).unwrap();
+        repo.write("feature.txt", "feature\n").unwrap();
+        repo.commit_all("feature work").unwrap();
+        repo.run_git(["checkout", "main"]).unwrap();
+
+        let mut graph = graph_with_main(&repo);
+        graph
+            .add_loose_branch(&repo, "feature".to_owned(), "main".to_owned())
+            .unwrap();
+        assert!(graph.validate_against_repo(&repo).unwrap().is_empty());
+
+        repo.write("main.txt", "main moved\n").unwrap();
+        repo.commit_all("main work").unwrap();
+        graph
+            .add_loose_branch(&repo, "gone".to_owned(), "main".to_owned())
+            .unwrap();
+
+        let discrepancies = graph.validate_against_repo(&repo).unwrap();
+        assert_eq!(
+            discrepancies,
+            vec![
+                Discrepancy::ParentNotAncestor {
+                    branch: "feature".to_owned(),
+                    parent: "main".to_owned(),
+                },
+                Discrepancy::MissingBranch {
+                    branch: "gone".to_owned(),
+                },
+            ]
+        );
+    }
+
     fn test_repo() -> TestRepo {
         TestRepo::builder()
             .with_initial_commit()
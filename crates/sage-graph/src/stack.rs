@@ -65,6 +65,22 @@ impl Stack {
         Ok(())
     }
 
+    /// Drop a branch that has no children of its own.
+    pub fn remove_branch(&mut self, branch: &str) -> Result<()> {
+        if !self.children(branch).is_empty() {
+            bail!("branch \"{branch}\" has children");
+        }
+        let info = self
+            .branches
+            .remove(branch)
+            .ok_or_else(|| anyhow::anyhow!("branch \"{branch}\" not found"))?;
+        self.children.remove(branch);
+        if let Some(siblings) = self.children.get_mut(&info.parent) {
+            siblings.retain(|child| child != branch);
+        }
+        Ok(())
+    }
+
     pub fn descendants(&self, start: &str) -> Vec<String> {
         let mut result = Vec::new();
         let mut to_visit = vec![start.to_owned()];